@@ -17,7 +17,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("  API Key: {}...", &config.api_key[..20]);
     println!("  Endpoint: {}", config.endpoint_url);
 
-    let client = Client::from_config(config.clone());
+    let client = Client::from_config(config.clone())?;
 
     // Let's also test with a direct HTTP call to compare
     println!("\nTesting direct reqwest call...");
@@ -0,0 +1,742 @@
+//! Full base backup and restore
+//!
+//! [`BaseHandle::backup`] writes a base to a directory:
+//!
+//! - `manifest.json`: a [`BackupManifest`] with timestamps and per-table counts
+//! - `schema.json`: the base schema as returned by [`BaseHandle::schema`]
+//! - `tables/<tableId>.ndjson`: one record per line
+//! - `comments/<tableId>.ndjson`: one `{"recordId", "comments"}` object per
+//!   commented record
+//! - `attachments/<tableId>/<recordId>/<attachmentId>-<filename>`: attachment
+//!   files, when [`BackupOptions::with_attachments`] is enabled
+//!
+//! [`BaseHandle::restore`] recreates the tables and fields of a backup in
+//! another base, copies the records and re-links `multipleRecordLinks` fields by
+//! mapping old record IDs to the new ones. Computed fields (formulas, rollups,
+//! lookups, counts and the like) cannot be created through the API and are
+//! skipped. Airtable creates the inverse side of each link field itself and
+//! names it after the linking table, so inverse fields may come back renamed.
+//! Comments are restored with their original author and time prepended, since
+//! they are re-posted by the restoring user.
+
+use crate::client::{BaseHandle, TableHandle};
+use crate::error::{Error, Result};
+use crate::models::{BaseSchema, Comment, FieldDefinition, FieldSchema, Record, TableDefinition};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Version of the backup layout written to the manifest
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Maximum number of records per batch request
+const BATCH_SIZE: usize = 10;
+
+/// What to include in a backup
+#[derive(Debug, Clone)]
+pub struct BackupOptions {
+    comments: bool,
+    attachments: bool,
+}
+
+impl Default for BackupOptions {
+    fn default() -> Self {
+        Self {
+            comments: true,
+            attachments: false,
+        }
+    }
+}
+
+impl BackupOptions {
+    /// Back up record comments (enabled by default; one request per record)
+    pub fn with_comments(mut self, comments: bool) -> Self {
+        self.comments = comments;
+        self
+    }
+
+    /// Download attachment files (disabled by default)
+    ///
+    /// Attachment URLs expire after a few hours, so restoring attachments from
+    /// an older backup only works when the files were downloaded.
+    pub fn with_attachments(mut self, attachments: bool) -> Self {
+        self.attachments = attachments;
+        self
+    }
+}
+
+/// Contents of `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Backup layout version ([`BACKUP_FORMAT_VERSION`])
+    pub version: u32,
+    /// Base that was backed up
+    #[serde(rename = "baseId")]
+    pub base_id: String,
+    /// When the backup started
+    #[serde(rename = "startedAt")]
+    pub started_at: DateTime<Utc>,
+    /// When the backup finished
+    #[serde(rename = "completedAt")]
+    pub completed_at: DateTime<Utc>,
+    /// Whether comments were backed up
+    pub comments: bool,
+    /// Whether attachment files were downloaded
+    pub attachments: bool,
+    /// Per-table counts
+    pub tables: Vec<BackupTable>,
+}
+
+/// Counts for one backed-up table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupTable {
+    /// Table ID in the backed-up base
+    pub id: String,
+    /// Table name
+    pub name: String,
+    /// Number of records
+    pub records: usize,
+    /// Number of comments
+    pub comments: usize,
+    /// Number of downloaded attachment files
+    pub attachments: usize,
+}
+
+impl fmt::Display for BackupManifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Backed up base {} ({} tables)",
+            self.base_id,
+            self.tables.len()
+        )?;
+        for table in &self.tables {
+            writeln!(
+                f,
+                "  {}: {} records, {} comments, {} attachments",
+                table.name, table.records, table.comments, table.attachments
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Comments of one record, as stored in `comments/<tableId>.ndjson`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordComments {
+    #[serde(rename = "recordId")]
+    record_id: String,
+    comments: Vec<Comment>,
+}
+
+/// Outcome of [`BaseHandle::restore`]
+#[derive(Debug, Clone, Default)]
+pub struct RestoreReport {
+    /// Restored tables, in backup order
+    pub tables: Vec<RestoredTable>,
+    /// Fields that could not be recreated, as `Table.Field (type)`
+    pub skipped_fields: Vec<String>,
+}
+
+/// Counts for one restored table
+#[derive(Debug, Clone)]
+pub struct RestoredTable {
+    /// Table name
+    pub name: String,
+    /// Table ID in the backup
+    pub old_id: String,
+    /// Table ID in the target base
+    pub new_id: String,
+    /// Records created
+    pub records: usize,
+    /// Records whose link fields were re-linked
+    pub linked: usize,
+    /// Comments re-posted
+    pub comments: usize,
+    /// Attachment files uploaded
+    pub attachments: usize,
+}
+
+impl fmt::Display for RestoreReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Restored {} tables", self.tables.len())?;
+        for table in &self.tables {
+            writeln!(
+                f,
+                "  {} ({} -> {}): {} records, {} re-linked, {} comments, {} attachments",
+                table.name,
+                table.old_id,
+                table.new_id,
+                table.records,
+                table.linked,
+                table.comments,
+                table.attachments
+            )?;
+        }
+        if !self.skipped_fields.is_empty() {
+            writeln!(f, "Skipped fields: {}", self.skipped_fields.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl BaseHandle {
+    /// Back up the schema, records and optionally comments and attachments to `dir`
+    ///
+    /// The directory is created if needed; existing backup files are overwritten.
+    pub async fn backup<P: AsRef<Path>>(
+        &self,
+        dir: P,
+        options: &BackupOptions,
+    ) -> Result<BackupManifest> {
+        let dir = dir.as_ref();
+        let started_at = Utc::now();
+        fs::create_dir_all(dir.join("tables"))?;
+        if options.comments {
+            fs::create_dir_all(dir.join("comments"))?;
+        }
+
+        let schema = self.schema().await?;
+        fs::write(dir.join("schema.json"), serde_json::to_vec_pretty(&schema)?)?;
+
+        let mut tables = Vec::new();
+        for table_schema in &schema.tables {
+            let table = self.table(&table_schema.id);
+            let records = table.all().await?;
+            write_ndjson(
+                &dir.join("tables")
+                    .join(format!("{}.ndjson", table_schema.id)),
+                &records,
+            )?;
+
+            let mut comment_count = 0;
+            if options.comments {
+                let mut entries = Vec::new();
+                for record in &records {
+                    let comments = table.comments(&record.id).await?;
+                    if !comments.is_empty() {
+                        comment_count += comments.len();
+                        entries.push(RecordComments {
+                            record_id: record.id.clone(),
+                            comments,
+                        });
+                    }
+                }
+                write_ndjson(
+                    &dir.join("comments")
+                        .join(format!("{}.ndjson", table_schema.id)),
+                    &entries,
+                )?;
+            }
+
+            let mut attachment_count = 0;
+            if options.attachments {
+                let attachment_fields: Vec<&str> = table_schema
+                    .fields
+                    .iter()
+                    .filter(|f| f.field_type == "multipleAttachments")
+                    .map(|f| f.name.as_str())
+                    .collect();
+                for record in &records {
+                    for attachment in attachment_fields
+                        .iter()
+                        .filter_map(|name| record.fields.get(*name))
+                        .filter_map(Value::as_array)
+                        .flatten()
+                    {
+                        let (Some(id), Some(url)) = (
+                            attachment.get("id").and_then(Value::as_str),
+                            attachment.get("url").and_then(Value::as_str),
+                        ) else {
+                            continue;
+                        };
+                        let path =
+                            attachment_path(dir, &table_schema.id, &record.id, attachment, id);
+                        let bytes = self.client().download(url).await?;
+                        fs::create_dir_all(path.parent().unwrap_or(dir))?;
+                        fs::write(&path, bytes)?;
+                        attachment_count += 1;
+                    }
+                }
+            }
+
+            tables.push(BackupTable {
+                id: table_schema.id.clone(),
+                name: table_schema.name.clone(),
+                records: records.len(),
+                comments: comment_count,
+                attachments: attachment_count,
+            });
+        }
+
+        let manifest = BackupManifest {
+            version: BACKUP_FORMAT_VERSION,
+            base_id: self.id().to_string(),
+            started_at,
+            completed_at: Utc::now(),
+            comments: options.comments,
+            attachments: options.attachments,
+            tables,
+        };
+        fs::write(
+            dir.join("manifest.json"),
+            serde_json::to_vec_pretty(&manifest)?,
+        )?;
+        Ok(manifest)
+    }
+
+    /// Recreate the tables, fields, records and comments of a backup in this base
+    ///
+    /// The base must not already contain tables with the backed-up names.
+    pub async fn restore<P: AsRef<Path>>(&self, dir: P) -> Result<RestoreReport> {
+        let dir = dir.as_ref();
+        let manifest: BackupManifest = read_json(&dir.join("manifest.json"))?;
+        if manifest.version > BACKUP_FORMAT_VERSION {
+            return Err(Error::Other(format!(
+                "Backup format version {} is newer than the supported version {}",
+                manifest.version, BACKUP_FORMAT_VERSION
+            )));
+        }
+        let schema: BaseSchema = read_json(&dir.join("schema.json"))?;
+        let mut report = RestoreReport::default();
+
+        // Create every table with its plain fields first, so link fields can
+        // point at tables that come later in the backup
+        let mut table_ids: HashMap<String, String> = HashMap::new();
+        let mut plan: Vec<TablePlan> = Vec::new();
+        for table in &schema.tables {
+            let mut fields: Vec<FieldDefinition> = Vec::new();
+            let mut writable: HashMap<String, FieldKind> = HashMap::new();
+            let mut links = Vec::new();
+
+            let primary = table.fields.iter().find(|f| f.id == table.primary_field_id);
+            let others = table
+                .fields
+                .iter()
+                .filter(|f| f.id != table.primary_field_id);
+            for (is_primary, field) in primary
+                .map(|f| (true, f))
+                .into_iter()
+                .chain(others.map(|f| (false, f)))
+            {
+                if field.field_type == "multipleRecordLinks" {
+                    links.push(field.clone());
+                } else if is_primary && field.is_computed() {
+                    // Every table needs a primary field; keep the values as text
+                    fields.push(FieldDefinition::new(&field.name, "singleLineText"));
+                    writable.insert(field.name.clone(), FieldKind::Text);
+                } else if !field.is_computed() {
+                    fields.push(field_definition(field));
+                    writable.insert(
+                        field.name.clone(),
+                        FieldKind::for_field_type(&field.field_type),
+                    );
+                } else {
+                    report.skipped_fields.push(format!(
+                        "{}.{} ({})",
+                        table.name, field.name, field.field_type
+                    ));
+                }
+            }
+            if fields.is_empty() {
+                return Err(Error::Other(format!(
+                    "Cannot restore table '{}': its primary field is a link field",
+                    table.name
+                )));
+            }
+
+            let created = self
+                .create_table(TableDefinition::new(&table.name, fields))
+                .await?;
+            table_ids.insert(table.id.clone(), created.id.clone());
+            plan.push(TablePlan {
+                old_id: table.id.clone(),
+                new_id: created.id,
+                name: table.name.clone(),
+                writable,
+                links,
+            });
+        }
+
+        // Add link fields; Airtable creates (and maintains) the inverse side
+        let mut inverse_fields: HashSet<String> = HashSet::new();
+        for table in &mut plan {
+            let handle = self.table(&table.new_id);
+            let mut kept = Vec::new();
+            for field in std::mem::take(&mut table.links) {
+                if inverse_fields.contains(&field.id) {
+                    continue;
+                }
+                let options = field.options.clone().unwrap_or_default();
+                let linked = options
+                    .get("linkedTableId")
+                    .and_then(Value::as_str)
+                    .and_then(|id| table_ids.get(id));
+                let Some(linked) = linked else {
+                    report.skipped_fields.push(format!(
+                        "{}.{} (link to unknown table)",
+                        table.name, field.name
+                    ));
+                    continue;
+                };
+                if let Some(inverse) = options.get("inverseLinkFieldId").and_then(Value::as_str) {
+                    inverse_fields.insert(inverse.to_string());
+                }
+                let mut definition = FieldDefinition::new(&field.name, "multipleRecordLinks")
+                    .with_options(json!({ "linkedTableId": linked }));
+                definition.description = field.description.clone();
+                handle
+                    .create_field(serde_json::to_value(&definition)?)
+                    .await?;
+                kept.push(field);
+            }
+            table.links = kept;
+        }
+
+        // Create records, remembering which new record each old one became
+        let mut record_ids: HashMap<String, String> = HashMap::new();
+        let mut table_records: Vec<Vec<Record>> = Vec::new();
+        for table in &plan {
+            let records: Vec<Record> =
+                read_ndjson(&dir.join("tables").join(format!("{}.ndjson", table.old_id)))?;
+            let handle = self.table(&table.new_id);
+            for chunk in records.chunks(BATCH_SIZE) {
+                let fields: Vec<Value> = chunk
+                    .iter()
+                    .map(|record| writable_fields(record, &table.writable, dir, &table.old_id))
+                    .collect();
+                let created = handle.batch_create(fields).await?;
+                for (old, new) in chunk.iter().zip(created) {
+                    record_ids.insert(old.id.clone(), new.id);
+                }
+            }
+            table_records.push(records);
+        }
+
+        for (table, records) in plan.iter().zip(&table_records) {
+            let handle = self.table(&table.new_id);
+            let new_id = |old: &str| record_ids.get(old).cloned();
+
+            // Re-link records through the old-to-new record ID map
+            let mut updates = Vec::new();
+            for record in records {
+                let mut fields = Map::new();
+                for link in &table.links {
+                    if let Some(Value::Array(ids)) = record.fields.get(&link.name) {
+                        let mapped: Vec<Value> = ids
+                            .iter()
+                            .filter_map(Value::as_str)
+                            .filter_map(&new_id)
+                            .map(Value::String)
+                            .collect();
+                        fields.insert(link.name.clone(), Value::Array(mapped));
+                    }
+                }
+                if let (false, Some(id)) = (fields.is_empty(), new_id(&record.id)) {
+                    updates.push(json!({ "id": id, "fields": fields }));
+                }
+            }
+            let linked = updates.len();
+            for chunk in updates.chunks(BATCH_SIZE) {
+                handle.batch_update(chunk.to_vec()).await?;
+            }
+
+            let attachments = upload_attachments(&handle, dir, table, records, &new_id).await?;
+
+            // Re-post comments oldest first so they list in their original order
+            let mut comments = 0;
+            let comments_path = dir
+                .join("comments")
+                .join(format!("{}.ndjson", table.old_id));
+            if comments_path.exists() {
+                let entries: Vec<RecordComments> = read_ndjson(&comments_path)?;
+                for entry in entries {
+                    let Some(record_id) = new_id(&entry.record_id) else {
+                        continue;
+                    };
+                    let mut original = entry.comments;
+                    original.sort_by_key(|c| c.created_time);
+                    for comment in original {
+                        let text = format!(
+                            "[{} at {}] {}",
+                            comment.author.name,
+                            comment.created_time.to_rfc3339(),
+                            comment.text
+                        );
+                        handle.add_comment(&record_id, &text).await?;
+                        comments += 1;
+                    }
+                }
+            }
+
+            report.tables.push(RestoredTable {
+                name: table.name.clone(),
+                old_id: table.old_id.clone(),
+                new_id: table.new_id.clone(),
+                records: records.len(),
+                linked,
+                comments,
+                attachments,
+            });
+        }
+
+        Ok(report)
+    }
+}
+
+/// A table being restored
+struct TablePlan {
+    old_id: String,
+    new_id: String,
+    name: String,
+    /// Non-link fields written when records are created
+    writable: HashMap<String, FieldKind>,
+    /// Link fields re-linked once every record exists
+    links: Vec<FieldSchema>,
+}
+
+/// How a backed-up value is converted before it is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    /// Written as backed up
+    Plain,
+    /// Computed primary field restored as text
+    Text,
+    /// Attachments, re-created from their URL or uploaded from downloaded files
+    Attachments,
+    /// Collaborators, matched by email
+    Collaborators,
+}
+
+impl FieldKind {
+    fn for_field_type(field_type: &str) -> Self {
+        match field_type {
+            "multipleAttachments" => FieldKind::Attachments,
+            "singleCollaborator" | "multipleCollaborators" => FieldKind::Collaborators,
+            _ => FieldKind::Plain,
+        }
+    }
+}
+
+/// Field definition that recreates a backed-up field
+///
+/// Select choices lose their IDs, which Airtable assigns on creation.
+fn field_definition(field: &FieldSchema) -> FieldDefinition {
+    let mut definition = FieldDefinition::new(&field.name, &field.field_type);
+    definition.description = field.description.clone();
+    if let Some(mut options) = field.options.clone() {
+        if let Some(Value::Array(choices)) = options.get_mut("choices") {
+            for choice in choices.iter_mut().filter_map(Value::as_object_mut) {
+                choice.remove("id");
+            }
+        }
+        definition.options = Some(options);
+    }
+    definition
+}
+
+/// Fields of a backed-up record that can be written when creating it
+fn writable_fields(
+    record: &Record,
+    writable: &HashMap<String, FieldKind>,
+    dir: &Path,
+    table_id: &str,
+) -> Value {
+    let mut fields = Map::new();
+    for (name, value) in &record.fields {
+        let Some(kind) = writable.get(name) else {
+            continue;
+        };
+        let value = match kind {
+            FieldKind::Plain => value.clone(),
+            FieldKind::Text => match value {
+                Value::String(_) => value.clone(),
+                other => Value::String(other.to_string()),
+            },
+            FieldKind::Attachments => {
+                // Downloaded files are uploaded once the record exists
+                let attachments: Vec<Value> = value
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(|a| local_attachment(dir, table_id, &record.id, a).is_none())
+                    .filter_map(|a| {
+                        let url = a.get("url")?;
+                        Some(json!({ "url": url, "filename": a.get("filename") }))
+                    })
+                    .collect();
+                if attachments.is_empty() {
+                    continue;
+                }
+                Value::Array(attachments)
+            }
+            FieldKind::Collaborators => collaborator_refs(value),
+        };
+        fields.insert(name.clone(), value);
+    }
+    Value::Object(fields)
+}
+
+/// Reduce collaborator objects to a reference Airtable accepts on write
+fn collaborator_refs(value: &Value) -> Value {
+    let reference = |c: &Value| match (c.get("email"), c.get("id")) {
+        (Some(email), _) => json!({ "email": email }),
+        (None, Some(id)) => json!({ "id": id }),
+        (None, None) => c.clone(),
+    };
+    match value {
+        Value::Array(collaborators) => Value::Array(collaborators.iter().map(reference).collect()),
+        other => reference(other),
+    }
+}
+
+/// Upload the downloaded attachment files of a table's records
+async fn upload_attachments(
+    handle: &TableHandle,
+    dir: &Path,
+    table: &TablePlan,
+    records: &[Record],
+    new_id: &impl Fn(&str) -> Option<String>,
+) -> Result<usize> {
+    let mut uploaded = 0;
+    for record in records {
+        let Some(record_id) = new_id(&record.id) else {
+            continue;
+        };
+        for (name, kind) in &table.writable {
+            if *kind != FieldKind::Attachments {
+                continue;
+            }
+            for attachment in record
+                .fields
+                .get(name)
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let Some(path) = local_attachment(dir, &table.old_id, &record.id, attachment)
+                else {
+                    continue;
+                };
+                let filename = attachment
+                    .get("filename")
+                    .and_then(Value::as_str)
+                    .unwrap_or("attachment");
+                let content_type = attachment
+                    .get("type")
+                    .and_then(Value::as_str)
+                    .unwrap_or("application/octet-stream");
+                handle
+                    .upload_attachment(&record_id, name, &fs::read(&path)?, filename, content_type)
+                    .await?;
+                uploaded += 1;
+            }
+        }
+    }
+    Ok(uploaded)
+}
+
+/// Where a downloaded attachment is stored
+fn attachment_path(
+    dir: &Path,
+    table_id: &str,
+    record_id: &str,
+    attachment: &Value,
+    id: &str,
+) -> PathBuf {
+    let filename = attachment
+        .get("filename")
+        .and_then(Value::as_str)
+        .unwrap_or("attachment")
+        .replace(['/', '\\'], "_");
+    dir.join("attachments")
+        .join(table_id)
+        .join(record_id)
+        .join(format!("{}-{}", id, filename))
+}
+
+/// Path of an attachment's downloaded file, if the backup has one
+fn local_attachment(
+    dir: &Path,
+    table_id: &str,
+    record_id: &str,
+    attachment: &Value,
+) -> Option<PathBuf> {
+    let id = attachment.get("id").and_then(Value::as_str)?;
+    let path = attachment_path(dir, table_id, record_id, attachment, id);
+    path.is_file().then_some(path)
+}
+
+fn write_ndjson<T: Serialize>(path: &Path, items: &[T]) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for item in items {
+        serde_json::to_writer(&mut writer, item)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn read_ndjson<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<Vec<T>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut items = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            items.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(items)
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| Error::Other(format!("Cannot read {}: {}", path.display(), e)))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_definition_drops_choice_ids() {
+        let field = FieldSchema {
+            id: "fldA".to_string(),
+            name: "Status".to_string(),
+            field_type: "singleSelect".to_string(),
+            options: Some(
+                json!({ "choices": [{ "id": "selX", "name": "Todo", "color": "blueLight2" }] }),
+            ),
+            description: None,
+        };
+
+        let definition = field_definition(&field);
+        assert_eq!(
+            definition.options,
+            Some(json!({ "choices": [{ "name": "Todo", "color": "blueLight2" }] }))
+        );
+        assert!(!field.is_computed());
+
+        let formula = FieldSchema {
+            field_type: "formula".to_string(),
+            ..field
+        };
+        assert!(formula.is_computed());
+    }
+
+    #[test]
+    fn test_collaborator_refs_prefer_email() {
+        let value =
+            json!([{ "id": "usrA", "email": "a@example.com", "name": "A" }, { "id": "usrB" }]);
+        assert_eq!(
+            collaborator_refs(&value),
+            json!([{ "email": "a@example.com" }, { "id": "usrB" }])
+        );
+    }
+}
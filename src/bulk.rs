@@ -0,0 +1,273 @@
+//! Streaming bulk writes from newline-delimited JSON
+//!
+//! [`TableHandle::bulk_write`] reads one JSON value per line, groups lines into
+//! batches of 10 and reports the outcome of every line as soon as its batch
+//! completes, so arbitrarily long inputs can be piped through in constant
+//! memory. Accepted lines depend on the [`BulkOperation`]:
+//!
+//! | Operation | Line |
+//! |-----------|------|
+//! | create, upsert | `{"fields": {...}}` or a bare fields object |
+//! | update | `{"id": "recXXX", "fields": {...}}` |
+//! | delete | `"recXXX"`, `{"id": "recXXX"}` or an unquoted record ID |
+//!
+//! Blank lines are skipped. A line that cannot be parsed fails on its own; a
+//! batch rejected by Airtable fails every line in it.
+
+use crate::client::TableHandle;
+use crate::error::Result;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fmt;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// Maximum number of records per batch request
+const BATCH_SIZE: usize = 10;
+
+/// Write applied to every input line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkOperation {
+    /// Create records
+    Create,
+    /// Update records by ID (only the given fields change)
+    Update,
+    /// Create or update records matching on the given key fields
+    Upsert(Vec<String>),
+    /// Delete records by ID
+    Delete,
+}
+
+/// Result of one successfully written line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BulkOutcome {
+    /// 1-based input line number
+    pub line: usize,
+    /// `created`, `updated` or `deleted`
+    pub status: &'static str,
+    /// Record ID
+    pub id: String,
+}
+
+/// A line that could not be written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkFailure {
+    /// 1-based input line number
+    pub line: usize,
+    /// What went wrong
+    pub error: String,
+}
+
+impl fmt::Display for BulkFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+/// Totals of a [`TableHandle::bulk_write`] run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BulkSummary {
+    /// Lines written successfully
+    pub succeeded: usize,
+    /// Lines that failed
+    pub failed: usize,
+}
+
+/// A parsed input line waiting for its batch
+struct Pending {
+    line: usize,
+    /// Record payload (create/update/upsert) or record ID (delete)
+    item: Value,
+}
+
+impl BulkOperation {
+    /// Parse one input line into the payload sent for it
+    fn parse(&self, text: &str) -> std::result::Result<Value, String> {
+        if let BulkOperation::Delete = self {
+            let id = match serde_json::from_str::<Value>(text) {
+                Ok(Value::String(id)) => id,
+                Ok(Value::Object(object)) => object
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .ok_or("expected an object with an \"id\"")?
+                    .to_string(),
+                Ok(_) => return Err("expected a record ID".to_string()),
+                Err(_) => text.to_string(),
+            };
+            return if id.starts_with("rec") && !id.contains(char::is_whitespace) {
+                Ok(Value::String(id))
+            } else {
+                Err(format!("'{}' is not a record ID", id))
+            };
+        }
+
+        let value: Value =
+            serde_json::from_str(text).map_err(|e| format!("invalid JSON: {}", e))?;
+        let object = value.as_object().ok_or("expected a JSON object")?;
+        let fields = match object.get("fields") {
+            Some(Value::Object(_)) => object["fields"].clone(),
+            Some(_) => return Err("\"fields\" must be an object".to_string()),
+            None if *self == BulkOperation::Update => {
+                return Err("expected {\"id\": ..., \"fields\": {...}}".to_string())
+            }
+            None => value.clone(),
+        };
+
+        match self {
+            BulkOperation::Update => {
+                let id = object
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .ok_or("missing \"id\"")?;
+                Ok(json!({ "id": id, "fields": fields }))
+            }
+            _ => Ok(fields),
+        }
+    }
+}
+
+impl TableHandle {
+    /// Apply `operation` to every NDJSON line of `input`, in batches of 10
+    ///
+    /// `on_result` is called once per non-blank line, in input order within each
+    /// batch. Only errors reading `input` are returned; write errors are reported
+    /// per line.
+    pub async fn bulk_write<R, F>(
+        &self,
+        operation: &BulkOperation,
+        input: R,
+        mut on_result: F,
+    ) -> Result<BulkSummary>
+    where
+        R: AsyncBufRead + Unpin,
+        F: FnMut(std::result::Result<BulkOutcome, BulkFailure>),
+    {
+        let mut summary = BulkSummary::default();
+        let mut report = |result: std::result::Result<BulkOutcome, BulkFailure>| {
+            match result {
+                Ok(_) => summary.succeeded += 1,
+                Err(_) => summary.failed += 1,
+            }
+            on_result(result);
+        };
+
+        let mut lines = input.lines();
+        let mut line = 0;
+        let mut batch: Vec<Pending> = Vec::new();
+        while let Some(text) = lines.next_line().await? {
+            line += 1;
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            match operation.parse(text) {
+                Ok(item) => batch.push(Pending { line, item }),
+                Err(error) => report(Err(BulkFailure { line, error })),
+            }
+            if batch.len() == BATCH_SIZE {
+                self.write_bulk_batch(operation, std::mem::take(&mut batch), &mut report)
+                    .await;
+            }
+        }
+        if !batch.is_empty() {
+            self.write_bulk_batch(operation, batch, &mut report).await;
+        }
+        Ok(summary)
+    }
+
+    async fn write_bulk_batch<F>(
+        &self,
+        operation: &BulkOperation,
+        batch: Vec<Pending>,
+        report: &mut F,
+    ) where
+        F: FnMut(std::result::Result<BulkOutcome, BulkFailure>),
+    {
+        let lines: Vec<usize> = batch.iter().map(|p| p.line).collect();
+        let items: Vec<Value> = batch.into_iter().map(|p| p.item).collect();
+
+        let result: Result<Vec<(&'static str, String)>> = match operation {
+            BulkOperation::Create => self
+                .batch_create(items)
+                .await
+                .map(|records| records.into_iter().map(|r| ("created", r.id)).collect()),
+            BulkOperation::Update => self
+                .batch_update(items)
+                .await
+                .map(|records| records.into_iter().map(|r| ("updated", r.id)).collect()),
+            BulkOperation::Upsert(keys) => {
+                let records = items.into_iter().map(|f| json!({ "fields": f })).collect();
+                let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+                self.batch_upsert_detailed(records, &keys)
+                    .await
+                    .map(|response| {
+                        response
+                            .records
+                            .into_iter()
+                            .map(|r| {
+                                let status = if response.created_records.contains(&r.id) {
+                                    "created"
+                                } else {
+                                    "updated"
+                                };
+                                (status, r.id)
+                            })
+                            .collect()
+                    })
+            }
+            BulkOperation::Delete => {
+                let ids: Vec<String> = items
+                    .into_iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect();
+                self.batch_delete(&ids)
+                    .await
+                    .map(|()| ids.into_iter().map(|id| ("deleted", id)).collect())
+            }
+        };
+
+        match result {
+            Ok(outcomes) => {
+                for (line, (status, id)) in lines.into_iter().zip(outcomes) {
+                    report(Ok(BulkOutcome { line, status, id }));
+                }
+            }
+            Err(e) => {
+                for line in lines {
+                    report(Err(BulkFailure {
+                        line,
+                        error: e.to_string(),
+                    }));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines_per_operation() {
+        assert_eq!(
+            BulkOperation::Create.parse(r#"{"Name": "Ada"}"#),
+            Ok(json!({ "Name": "Ada" }))
+        );
+        assert_eq!(
+            BulkOperation::Upsert(vec![]).parse(r#"{"fields": {"Name": "Ada"}}"#),
+            Ok(json!({ "Name": "Ada" }))
+        );
+        assert_eq!(
+            BulkOperation::Update.parse(r#"{"id": "rec1", "fields": {"Name": "Ada"}}"#),
+            Ok(json!({ "id": "rec1", "fields": { "Name": "Ada" } }))
+        );
+        assert!(BulkOperation::Update.parse(r#"{"Name": "Ada"}"#).is_err());
+        assert_eq!(BulkOperation::Delete.parse("recABC"), Ok(json!("recABC")));
+        assert_eq!(
+            BulkOperation::Delete.parse(r#"{"id": "recABC"}"#),
+            Ok(json!("recABC"))
+        );
+        assert!(BulkOperation::Delete.parse("42").is_err());
+        assert!(BulkOperation::Create.parse("[1, 2]").is_err());
+    }
+}
@@ -0,0 +1,456 @@
+//! Response caching for RSAirtable reads
+//!
+//! [`ResponseCache`] is a [`Middleware`] that serves repeated GET requests for
+//! records, schemas, `whoami` and the base list from a [`CacheStore`] until
+//! their time-to-live expires. Successful writes through the same client drop
+//! the cached reads of the table (or base schema) they touched.
+//!
+//! Two stores are provided: [`MemoryCache`], a bounded LRU, and [`DiskCache`],
+//! which keeps one JSON file per response so cached reads survive between runs.
+//! Invalidation matches URLs, so a table read by name is not invalidated by a
+//! write that addresses it by table ID.
+
+use crate::error::Result;
+use crate::middleware::{Middleware, RequestContext, ResponseAction};
+use crate::transport::{HttpResponse, Method};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// Read endpoints that can be cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheEndpoint {
+    /// Record listing (`GET /{baseId}/{table}`)
+    List,
+    /// Single record (`GET /{baseId}/{table}/{recordId}`)
+    Record,
+    /// Base schema (`GET /meta/bases/{baseId}/tables`)
+    Schema,
+    /// Current user (`GET /meta/whoami`)
+    Whoami,
+    /// Accessible bases (`GET /meta/bases`)
+    Bases,
+}
+
+impl CacheEndpoint {
+    /// Classify an endpoint template (see [`RequestContext::endpoint`])
+    fn from_template(template: &str) -> Option<Self> {
+        match template {
+            "/{baseId}/{table}" => Some(CacheEndpoint::List),
+            "/{baseId}/{table}/{recordId}" => Some(CacheEndpoint::Record),
+            "/meta/bases/{baseId}/tables" => Some(CacheEndpoint::Schema),
+            "/meta/whoami" => Some(CacheEndpoint::Whoami),
+            "/meta/bases" => Some(CacheEndpoint::Bases),
+            _ => None,
+        }
+    }
+}
+
+/// A cached response with its expiry time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers
+    pub headers: Vec<(String, String)>,
+    /// Response body (Airtable responses are UTF-8 JSON)
+    pub body: String,
+    /// When the entry stops being served
+    pub expires_at: SystemTime,
+}
+
+impl CachedResponse {
+    fn new(response: &HttpResponse, ttl: Duration) -> Self {
+        Self {
+            status: response.status,
+            headers: response.headers.clone(),
+            body: String::from_utf8_lossy(&response.body).into_owned(),
+            expires_at: SystemTime::now() + ttl,
+        }
+    }
+
+    /// Whether the entry has expired
+    pub fn is_expired(&self) -> bool {
+        SystemTime::now() >= self.expires_at
+    }
+
+    fn to_response(&self) -> HttpResponse {
+        HttpResponse {
+            status: self.status,
+            headers: self.headers.clone(),
+            body: self.body.clone().into_bytes(),
+        }
+    }
+}
+
+/// Storage backend for cached responses, keyed by request URL
+pub trait CacheStore: fmt::Debug + Send + Sync {
+    /// Look up an entry (expired entries may be returned; the cache checks expiry)
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Store an entry, replacing any previous one
+    fn put(&self, key: &str, response: CachedResponse);
+
+    /// Remove every entry whose key starts with `prefix`
+    fn remove_prefix(&self, prefix: &str);
+
+    /// Remove all entries
+    fn clear(&self) {
+        self.remove_prefix("");
+    }
+}
+
+/// In-memory store evicting the least recently used entry when full
+#[derive(Debug)]
+pub struct MemoryCache {
+    capacity: usize,
+    state: Mutex<MemoryState>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryState {
+    /// Entries with the tick at which they were last used
+    entries: HashMap<String, (CachedResponse, u64)>,
+    tick: u64,
+}
+
+impl MemoryCache {
+    /// Create a store holding at most `capacity` responses
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::new(MemoryState::default()),
+        }
+    }
+
+    /// Number of cached responses
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    /// Whether the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for MemoryCache {
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
+
+impl CacheStore for MemoryCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut state = self.state.lock().unwrap();
+        state.tick += 1;
+        let tick = state.tick;
+        state.entries.get_mut(key).map(|(response, used)| {
+            *used = tick;
+            response.clone()
+        })
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        let mut state = self.state.lock().unwrap();
+        state.tick += 1;
+        let tick = state.tick;
+
+        if !state.entries.contains_key(key) && state.entries.len() >= self.capacity {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                state.entries.remove(&oldest);
+            }
+        }
+        state.entries.insert(key.to_string(), (response, tick));
+    }
+
+    fn remove_prefix(&self, prefix: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.entries.retain(|key, _| !key.starts_with(prefix));
+    }
+}
+
+/// On-disk store keeping one JSON file per cached response
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    key: String,
+    response: CachedResponse,
+}
+
+impl DiskCache {
+    /// Store cached responses in `dir` (created on first write)
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Cache directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", fnv1a(key)))
+    }
+
+    fn read(path: &Path) -> Option<DiskEntry> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+}
+
+impl CacheStore for DiskCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        Self::read(&self.path(key))
+            .filter(|entry| entry.key == key)
+            .map(|entry| entry.response)
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        let entry = DiskEntry {
+            key: key.to_string(),
+            response,
+        };
+        // Caching is best effort; a failed write only means a later cache miss
+        if std::fs::create_dir_all(&self.dir).is_ok() {
+            if let Ok(content) = serde_json::to_string(&entry) {
+                let _ = std::fs::write(self.path(key), content);
+            }
+        }
+    }
+
+    fn remove_prefix(&self, prefix: &str) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        for path in entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+        {
+            if path.extension().is_some_and(|ext| ext == "json")
+                && Self::read(&path).is_some_and(|entry| entry.key.starts_with(prefix))
+            {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+}
+
+/// 64-bit FNV-1a hash (stable across runs, used for cache file names)
+fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Middleware caching read responses with per-endpoint TTLs
+///
+/// Install with [`Client::with_cache`](crate::Client::with_cache). Entries are
+/// keyed by the full request URL including the query string, so different
+/// filters, fields or pages are cached separately, and by a hash of the API
+/// token, so a shared store never serves one token's reads to another.
+#[derive(Debug)]
+pub struct ResponseCache {
+    store: Box<dyn CacheStore>,
+    default_ttl: Duration,
+    ttls: HashMap<CacheEndpoint, Duration>,
+}
+
+impl ResponseCache {
+    /// Cache reads in the given store for 60 seconds
+    pub fn new<S: CacheStore + 'static>(store: S) -> Self {
+        Self {
+            store: Box::new(store),
+            default_ttl: Duration::from_secs(60),
+            ttls: HashMap::new(),
+        }
+    }
+
+    /// Cache reads in memory, keeping at most `capacity` responses
+    pub fn in_memory(capacity: usize) -> Self {
+        Self::new(MemoryCache::new(capacity))
+    }
+
+    /// Cache reads on disk in `dir`
+    pub fn on_disk<P: Into<PathBuf>>(dir: P) -> Self {
+        Self::new(DiskCache::new(dir))
+    }
+
+    /// Set the TTL for endpoints without their own TTL
+    pub fn with_default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    /// Set the TTL for one endpoint (`Duration::ZERO` disables caching it)
+    pub fn with_ttl(mut self, endpoint: CacheEndpoint, ttl: Duration) -> Self {
+        self.ttls.insert(endpoint, ttl);
+        self
+    }
+
+    /// TTL applied to an endpoint
+    pub fn ttl(&self, endpoint: CacheEndpoint) -> Duration {
+        self.ttls
+            .get(&endpoint)
+            .copied()
+            .unwrap_or(self.default_ttl)
+    }
+
+    /// Drop all cached responses
+    pub fn clear(&self) {
+        self.store.clear();
+    }
+
+    /// TTL for a cacheable request, or `None` if it must not be cached
+    fn cacheable(&self, ctx: &RequestContext) -> Option<Duration> {
+        if ctx.request.method != Method::Get {
+            return None;
+        }
+        let endpoint = CacheEndpoint::from_template(&ctx.endpoint().0)?;
+        Some(self.ttl(endpoint)).filter(|ttl| !ttl.is_zero())
+    }
+
+    /// Store key of a request URL: the URL prefixed with a hash of the request's token
+    fn key(ctx: &RequestContext, url: &str) -> String {
+        let token = ctx
+            .request
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("authorization"))
+            .map_or("", |(_, value)| value.as_str());
+        format!("{:016x} {}", fnv1a(token), url)
+    }
+
+    /// URL prefixes of the cached reads a write invalidates
+    fn invalidated_prefixes(ctx: &RequestContext) -> Vec<String> {
+        let endpoint_url = ctx.endpoint_url.trim_end_matches('/');
+        let Some(path) = ctx.request.url.strip_prefix(endpoint_url) else {
+            return Vec::new();
+        };
+        let path = path.split('?').next().unwrap_or(path);
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let base_id = ctx.endpoint().1;
+
+        match (segments.as_slice(), base_id) {
+            // New base: the base list (and, harmlessly, cached schemas)
+            (["meta", "bases"], _) => vec![format!("{}/meta/bases", endpoint_url)],
+            // Schema changes: /meta/bases/{baseId}/tables/...
+            (["meta", "bases", ..], Some(base_id)) => {
+                vec![format!("{}/meta/bases/{}/", endpoint_url, base_id)]
+            }
+            // Record writes: /{baseId}/{table}[/...]; stop at the table name so
+            // "Cases" does not invalidate "CasesArchive"
+            ([_, table, ..], Some(base_id)) => {
+                let table_url = format!("{}/{}/{}", endpoint_url, base_id, table);
+                vec![format!("{}?", table_url), format!("{}/", table_url)]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Middleware for ResponseCache {
+    async fn before_request(&self, ctx: &mut RequestContext) -> Result<Option<HttpResponse>> {
+        if self.cacheable(ctx).is_none() {
+            return Ok(None);
+        }
+        Ok(self
+            .store
+            .get(&Self::key(ctx, &ctx.request.url))
+            .filter(|cached| !cached.is_expired())
+            .map(|cached| cached.to_response()))
+    }
+
+    async fn after_response(
+        &self,
+        ctx: &RequestContext,
+        result: &mut Result<HttpResponse>,
+    ) -> ResponseAction {
+        let Ok(response) = result else {
+            return ResponseAction::Continue;
+        };
+        if !response.is_success() {
+            return ResponseAction::Continue;
+        }
+
+        if ctx.request.method == Method::Get {
+            if let Some(ttl) = self.cacheable(ctx) {
+                let key = Self::key(ctx, &ctx.request.url);
+                self.store.put(&key, CachedResponse::new(response, ttl));
+            }
+        } else {
+            for prefix in Self::invalidated_prefixes(ctx) {
+                self.store.remove_prefix(&Self::key(ctx, &prefix));
+            }
+        }
+        ResponseAction::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached(body: &str) -> CachedResponse {
+        CachedResponse::new(&HttpResponse::new(200, body), Duration::from_secs(60))
+    }
+
+    #[test]
+    fn test_memory_cache_evicts_least_recently_used() {
+        let cache = MemoryCache::new(2);
+        cache.put("a", cached("1"));
+        cache.put("b", cached("2"));
+        assert!(cache.get("a").is_some());
+        cache.put("c", cached("3"));
+
+        assert!(cache.get("b").is_none(), "b was least recently used");
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("c").is_some());
+
+        cache.remove_prefix("a");
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_disk_cache_round_trip() {
+        let dir = std::env::temp_dir().join(format!("rsairtable-cache-{}", std::process::id()));
+        let cache = DiskCache::new(&dir);
+        cache.put("https://api.airtable.com/v0/appX/Cases", cached("[]"));
+        cache.put("https://api.airtable.com/v0/appX/Other", cached("{}"));
+
+        assert_eq!(
+            cache
+                .get("https://api.airtable.com/v0/appX/Cases")
+                .unwrap()
+                .body,
+            "[]"
+        );
+        cache.remove_prefix("https://api.airtable.com/v0/appX/Cases");
+        assert!(cache
+            .get("https://api.airtable.com/v0/appX/Cases")
+            .is_none());
+        assert!(cache
+            .get("https://api.airtable.com/v0/appX/Other")
+            .is_some());
+
+        cache.clear();
+        assert!(cache
+            .get("https://api.airtable.com/v0/appX/Other")
+            .is_none());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,337 @@
+//! HTTP record/replay cassettes for RSAirtable
+//!
+//! A [`CassetteTransport`] wraps another [`Transport`]. In record mode it forwards
+//! every request and appends the request/response pair to a JSON cassette file;
+//! in replay mode it serves responses from the cassette without touching the
+//! network and fails on requests that were not recorded.
+//!
+//! The `Authorization` header is scrubbed before anything is written to disk.
+//! Enable cassettes with [`Config::with_cassette`](crate::Config::with_cassette)
+//! or the `AIRTABLE_CASSETTE` and `AIRTABLE_CASSETTE_MODE` environment variables.
+
+use crate::error::{Error, Result};
+use crate::transport::{redact_header, HttpRequest, HttpResponse, RequestBody, Transport};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+/// Whether a cassette records new interactions or replays existing ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send requests and save them to the cassette (overwrites the file)
+    Record,
+    /// Serve responses from the cassette; unrecorded requests fail
+    Replay,
+}
+
+impl FromStr for CassetteMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "record" => Ok(CassetteMode::Record),
+            "replay" => Ok(CassetteMode::Replay),
+            other => Err(Error::config(format!(
+                "Invalid cassette mode '{}': expected 'record' or 'replay'",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for CassetteMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CassetteMode::Record => f.write_str("record"),
+            CassetteMode::Replay => f.write_str("replay"),
+        }
+    }
+}
+
+/// Contents of a cassette file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    /// Recorded interactions in the order they happened
+    pub interactions: Vec<Interaction>,
+}
+
+/// A recorded request/response pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// Request as sent (with secrets scrubbed)
+    pub request: RecordedRequest,
+    /// Response as received
+    pub response: RecordedResponse,
+}
+
+/// A recorded request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// HTTP method
+    pub method: String,
+    /// Absolute URL including query string
+    pub url: String,
+    /// Request headers (Authorization scrubbed)
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// JSON body, or a summary of a multipart upload
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+}
+
+/// A recorded response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// Body, when it was valid JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+    /// Body, when it was not valid JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl RecordedRequest {
+    fn from_request(request: &HttpRequest) -> Self {
+        let headers = request
+            .headers
+            .iter()
+            .map(|(name, value)| (name.clone(), redact_header(name, value).to_string()))
+            .collect();
+
+        let body = match &request.body {
+            RequestBody::Empty => None,
+            RequestBody::Json(body) => Some(body.clone()),
+            RequestBody::Multipart(file) => Some(serde_json::json!({
+                "multipart": {
+                    "fieldName": file.field_name,
+                    "filename": file.filename,
+                    "contentType": file.content_type,
+                    "size": file.bytes.len()
+                }
+            })),
+        };
+
+        Self {
+            method: request.method.to_string(),
+            url: request.url.clone(),
+            headers,
+            body,
+        }
+    }
+
+    /// Whether a live request matches this recording (method, URL and body)
+    fn matches(&self, other: &RecordedRequest) -> bool {
+        self.method == other.method && self.url == other.url && self.body == other.body
+    }
+}
+
+impl RecordedResponse {
+    fn from_response(response: &HttpResponse) -> Self {
+        let (body, text) = match serde_json::from_slice(&response.body) {
+            Ok(body) => (Some(body), None),
+            Err(_) if response.body.is_empty() => (None, None),
+            Err(_) => (
+                None,
+                Some(String::from_utf8_lossy(&response.body).into_owned()),
+            ),
+        };
+
+        Self {
+            status: response.status,
+            headers: response.headers.clone(),
+            body,
+            text,
+        }
+    }
+
+    fn to_response(&self) -> HttpResponse {
+        let body = match (&self.body, &self.text) {
+            (Some(body), _) => body.to_string().into_bytes(),
+            (None, Some(text)) => text.clone().into_bytes(),
+            (None, None) => Vec::new(),
+        };
+
+        HttpResponse {
+            status: self.status,
+            headers: self.headers.clone(),
+            body,
+        }
+    }
+}
+
+impl Cassette {
+    /// Load a cassette file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            Error::config(format!(
+                "Failed to read cassette '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save the cassette as pretty-printed JSON, creating parent directories
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Transport that records to or replays from a cassette file
+#[derive(Debug)]
+pub struct CassetteTransport {
+    path: PathBuf,
+    mode: CassetteMode,
+    inner: Arc<dyn Transport>,
+    state: Mutex<CassetteState>,
+}
+
+#[derive(Debug, Default)]
+struct CassetteState {
+    cassette: Cassette,
+    /// Which interactions have been replayed already
+    used: Vec<bool>,
+}
+
+impl CassetteTransport {
+    /// Create a cassette transport in the given mode
+    ///
+    /// Replay mode loads the cassette immediately and fails if it cannot be read;
+    /// record mode starts an empty cassette that is written after every request.
+    pub fn new<P: Into<PathBuf>, T: Transport + 'static>(
+        path: P,
+        mode: CassetteMode,
+        inner: T,
+    ) -> Result<Self> {
+        let path = path.into();
+        let cassette = match mode {
+            CassetteMode::Record => Cassette::default(),
+            CassetteMode::Replay => Cassette::load(&path)?,
+        };
+        let used = vec![false; cassette.interactions.len()];
+
+        Ok(Self {
+            path,
+            mode,
+            inner: Arc::new(inner),
+            state: Mutex::new(CassetteState { cassette, used }),
+        })
+    }
+
+    /// Cassette file path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Cassette mode
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Number of recorded interactions not yet replayed
+    pub fn unused_interactions(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.used.iter().filter(|used| !**used).count()
+    }
+
+    fn replay(&self, request: &HttpRequest) -> Result<HttpResponse> {
+        let recorded = RecordedRequest::from_request(request);
+        let mut state = self.state.lock().unwrap();
+        let CassetteState { cassette, used } = &mut *state;
+
+        let index = cassette
+            .interactions
+            .iter()
+            .enumerate()
+            .position(|(i, interaction)| !used[i] && interaction.request.matches(&recorded))
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "Unexpected request in cassette replay '{}': {} {}",
+                    self.path.display(),
+                    recorded.method,
+                    recorded.url
+                ))
+            })?;
+
+        used[index] = true;
+        Ok(cassette.interactions[index].response.to_response())
+    }
+
+    async fn record(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let recorded = RecordedRequest::from_request(&request);
+        let response = self.inner.send(request).await?;
+
+        let snapshot = {
+            let mut state = self.state.lock().unwrap();
+            state.cassette.interactions.push(Interaction {
+                request: recorded,
+                response: RecordedResponse::from_response(&response),
+            });
+            state.used.push(true);
+            state.cassette.clone()
+        };
+        snapshot.save(&self.path)?;
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Transport for CassetteTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        match self.mode {
+            CassetteMode::Record => self.record(request).await,
+            CassetteMode::Replay => self.replay(&request),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::REDACTED;
+
+    #[test]
+    fn test_cassette_mode_parsing() {
+        assert_eq!(
+            "Record".parse::<CassetteMode>().unwrap(),
+            CassetteMode::Record
+        );
+        assert_eq!(
+            "replay".parse::<CassetteMode>().unwrap(),
+            CassetteMode::Replay
+        );
+        assert!("rewind".parse::<CassetteMode>().is_err());
+    }
+
+    #[test]
+    fn test_recorded_request_scrubs_authorization() {
+        let request = HttpRequest::get("https://api.airtable.com/v0/meta/whoami")
+            .header("Authorization", "Bearer patSecret")
+            .header("User-Agent", "rsairtable/0.1.0");
+        let recorded = RecordedRequest::from_request(&request);
+
+        assert_eq!(
+            recorded.headers[0],
+            ("Authorization".to_string(), REDACTED.to_string())
+        );
+        assert!(!serde_json::to_string(&recorded)
+            .unwrap()
+            .contains("patSecret"));
+    }
+}
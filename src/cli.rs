@@ -3,10 +3,20 @@
 //! This module implements a CLI that's functionally compatible with pyairtable's CLI,
 //! using the same command structure and arguments.
 
+use chrono::{DateTime, Utc};
+use clap::parser::ValueSource;
 use clap::{Arg, ArgMatches, Command};
-use rsairtable::{BaseSchema, Client, Config};
+use rsairtable::client::{BaseHandle, TableHandle};
+use rsairtable::{BackupOptions, BaseSchema, BulkOperation, ClaimState, Client, Config, ImportOptions, InMemoryMetrics, LinkExpander, NameKind, SchemaCache, SchemaSpec, TableDefinition};
+use rsairtable::completion;
+use rsairtable::output::{self, OutputFormat};
+use rsairtable::profile::{self, DefaultExport, Profile, ProfileFile, TokenSource};
+use rsairtable::resolve::{is_base_id, is_table_id, parse_alias_target};
 use rsairtable::views::process_with_view;
+use std::collections::HashMap;
+use std::io::Write;
 use std::process;
+use std::sync::Arc;
 use std::env;
 
 #[tokio::main]
@@ -15,12 +25,93 @@ async fn main() {
     // Priority: .env first, then airtable.env as fallback if .env has no tokens
     load_env_files();
 
-    let matches = build_cli().get_matches();
+    let matches = build_cli().get_matches_from(expand_alias_args(env::args().collect()));
 
-    if let Err(e) = run_command(matches).await {
+    if let Err(e) = init_logging(&matches) {
         eprintln!("Error: {}", e);
         process::exit(1);
     }
+
+    // `base schema diff` exits like diff(1): 1 when the schemas differ, 2 on errors
+    let is_diff = matches
+        .subcommand_matches("base")
+        .and_then(|m| m.subcommand_matches("schema"))
+        .and_then(|m| m.subcommand_matches("diff"))
+        .is_some();
+    let error_code = if is_diff { 2 } else { 1 };
+
+    let metrics = Arc::new(InMemoryMetrics::new());
+    let stats = matches.get_one::<String>("stats").cloned();
+    let result = run_command(matches, metrics.clone()).await;
+
+    // Print API usage to stderr so stdout stays machine-readable
+    match stats.as_deref() {
+        Some("prometheus") => eprint!("{}", metrics.snapshot().to_prometheus()),
+        Some(_) => eprint!("{}", metrics.snapshot()),
+        None => {}
+    }
+
+    match result {
+        Ok(()) => {}
+        Err(e) if e.is::<SchemaDrift>() => process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(error_code);
+        }
+    }
+}
+
+/// Differences found by `base schema diff`, which fail the command with status 1
+#[derive(Debug)]
+struct SchemaDrift;
+
+impl std::fmt::Display for SchemaDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the schemas differ")
+    }
+}
+
+impl std::error::Error for SchemaDrift {}
+
+/// Set up tracing output when `--log-format` or `RUST_LOG` is given
+///
+/// Spans are logged when they close, so every operation reports its duration
+/// along with its base, table, status, retry and record count fields.
+#[cfg(feature = "tracing")]
+fn init_logging(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    use tracing_subscriber::fmt::format::FmtSpan;
+    use tracing_subscriber::EnvFilter;
+
+    let format = matches.get_one::<String>("log-format");
+    if format.is_none() && env::var("RUST_LOG").is_err() {
+        return Ok(());
+    }
+
+    let default_filter = if matches.get_flag("verbose") {
+        "rsairtable=debug"
+    } else {
+        "rsairtable=info"
+    };
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_filter));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_span_events(FmtSpan::CLOSE);
+
+    match format.map(String::as_str) {
+        Some("json") => builder.json().try_init().map_err(|e| e.to_string())?,
+        _ => builder.try_init().map_err(|e| e.to_string())?,
+    }
+    Ok(())
+}
+
+/// Without the `tracing` feature there is nothing to log
+#[cfg(not(feature = "tracing"))]
+fn init_logging(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    if matches.get_one::<String>("log-format").is_some() {
+        return Err("--log-format requires rsairtable to be built with the `tracing` feature".into());
+    }
+    Ok(())
 }
 
 /// Load environment files with fallback logic
@@ -47,7 +138,7 @@ fn build_cli() -> Command {
     Command::new("rsairtable")
         .version("0.1.0")
         .about("Rust client for Airtable API - compatible with pyairtable")
-        .after_help("DEFAULT: When run without arguments, fetches all records from Cases table and saves to cases.json\nHINT: Use 'rsairtable base [BASE_ID] --help' to see table operations, then 'rsairtable base [BASE_ID] table <TABLE_NAME> --help' for record operations\nBASE_ID can be omitted if BASE environment variable is set or if you have only one base")
+        .after_help("DEFAULT: When run without arguments, fetches all records from Cases table and saves to cases.json (configure with [profiles.NAME.default_export], preview with --default-dry-run)\nHINT: Use 'rsairtable base [BASE_ID] --help' to see table operations, then 'rsairtable base [BASE_ID] table <TABLE_NAME> --help' for record operations\nBASE_ID can be omitted if BASE environment variable is set or if you have only one base")
         .arg(
             Arg::new("key")
                 .short('k')
@@ -73,6 +164,15 @@ fn build_cli() -> Command {
                 .help("Environment variable containing your API key")
                 .global(true),
         )
+        .arg(
+            Arg::new("profile")
+                .short('p')
+                .long("profile")
+                .value_name("NAME")
+                .help("Use a named profile from the config file (see 'rsairtable config')")
+                .env("RSAIRTABLE_PROFILE")
+                .global(true),
+        )
         .arg(
             Arg::new("verbose")
                 .short('v')
@@ -81,6 +181,31 @@ fn build_cli() -> Command {
                 .help("Print verbose output")
                 .global(true),
         )
+        .arg(
+            Arg::new("log-format")
+                .long("log-format")
+                .value_name("FORMAT")
+                .value_parser(["text", "json"])
+                .help("Write tracing logs to stderr as text or JSON (requires the `tracing` feature)")
+                .global(true),
+        )
+        .arg(
+            Arg::new("stats")
+                .long("stats")
+                .value_name("FORMAT")
+                .num_args(0..=1)
+                .default_missing_value("text")
+                .require_equals(true)
+                .value_parser(["text", "prometheus"])
+                .help("Print API request statistics to stderr when the command finishes")
+                .global(true),
+        )
+        .arg(
+            Arg::new("default-dry-run")
+                .long("default-dry-run")
+                .action(clap::ArgAction::SetTrue)
+                .help("Print what running without a command would export, without running it"),
+        )
         .arg(
             Arg::new("help-detail")
                 .long("help-detail")
@@ -89,7 +214,159 @@ fn build_cli() -> Command {
                 .global(true),
         )
         .subcommand(Command::new("whoami").about("Print current user information"))
-        .subcommand(Command::new("bases").about("List all available bases"))
+        .subcommand(
+            Command::new("config")
+                .about("Manage named profiles in the config file")
+                .after_help("The config file is $XDG_CONFIG_HOME/rsairtable/config.toml (~/.config/rsairtable/config.toml), or $RSAIRTABLE_CONFIG.\nEXAMPLE: rsairtable config add firm --token-env FIRM_PAT --base appXXXXXXXXXXXXXX --table cases=Cases --default")
+                .subcommand_required(true)
+                .subcommand(Command::new("list").about("List profiles"))
+                .subcommand(
+                    Command::new("add")
+                        .about("Add a profile, or replace one with the same name")
+                        .arg(
+                            Arg::new("name")
+                                .value_name("NAME")
+                                .help("Profile name")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("token")
+                                .long("token")
+                                .value_name("TOKEN")
+                                .help("Store the token in the config file"),
+                        )
+                        .arg(
+                            Arg::new("token-file")
+                                .long("token-file")
+                                .value_name("PATH")
+                                .help("Read the token from a file"),
+                        )
+                        .arg(
+                            Arg::new("token-env")
+                                .long("token-env")
+                                .value_name("VAR")
+                                .help("Read the token from an environment variable"),
+                        )
+                        .group(
+                            clap::ArgGroup::new("token-source")
+                                .args(["token", "token-file", "token-env"])
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("base")
+                                .long("base")
+                                .value_name("BASE_ID")
+                                .help("Default base for the profile"),
+                        )
+                        .arg(
+                            Arg::new("table")
+                                .long("table")
+                                .value_name("ALIAS=TABLE")
+                                .help("Table alias in the default base (repeatable)")
+                                .action(clap::ArgAction::Append),
+                        )
+                        .arg(
+                            Arg::new("endpoint-url")
+                                .long("endpoint-url")
+                                .value_name("URL")
+                                .help("API endpoint URL"),
+                        )
+                        .arg(
+                            Arg::new("timeout")
+                                .long("timeout")
+                                .value_name("SECONDS")
+                                .help("Request timeout in seconds")
+                                .value_parser(clap::value_parser!(u64)),
+                        )
+                        .arg(
+                            Arg::new("max-retries")
+                                .long("max-retries")
+                                .value_name("NUMBER")
+                                .help("Maximum retries for failed requests")
+                                .value_parser(clap::value_parser!(u32)),
+                        )
+                        .arg(
+                            Arg::new("default")
+                                .long("default")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Make this the default profile"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("alias")
+                        .about("Add an alias for a base or table (in --profile, or shared by all profiles)")
+                        .after_help("EXAMPLE: rsairtable config alias cases appXXXXXXXXXXXXXX/tblYYYYYYYYYYYYYY\nThen: rsairtable @cases records")
+                        .arg(
+                            Arg::new("name")
+                                .value_name("ALIAS")
+                                .help("Alias name, used as @ALIAS")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("target")
+                                .value_name("BASE[/TABLE]")
+                                .help("Base ID or name, optionally followed by /table ID or name")
+                                .required(true),
+                        ),
+                )
+                .subcommand(
+                    Command::new("validate")
+                        .about("Check profiles for missing tokens and invalid settings")
+                        .arg(
+                            Arg::new("name")
+                                .value_name("NAME")
+                                .help("Profile to check (default: all)"),
+                        )
+                        .arg(
+                            Arg::new("online")
+                                .long("online")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Also check each token against the Airtable API"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("bases")
+                .about("List all available bases")
+                .args(listing_output_args())
+                .subcommand(
+                    Command::new("create")
+                        .about("Create a new base in a workspace")
+                        .arg(
+                            Arg::new("workspace")
+                                .short('W')
+                                .long("workspace")
+                                .value_name("WORKSPACE_ID")
+                                .help("Workspace to create the base in (e.g., wspXXXXXXXXXXXXXX)")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("name")
+                                .long("name")
+                                .value_name("NAME")
+                                .help("Name of the new base")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("tables")
+                                .short('j')
+                                .long("tables")
+                                .value_name("JSON")
+                                .help("Table definitions as a JSON array of {name, description, fields}"),
+                        )
+                        .arg(
+                            Arg::new("tables-file")
+                                .long("tables-file")
+                                .value_name("PATH")
+                                .help("File containing table definitions as a JSON array"),
+                        )
+                        .group(
+                            clap::ArgGroup::new("table-definitions")
+                                .args(["tables", "tables-file"])
+                                .required(true),
+                        ),
+                ),
+        )
         .subcommand(
             Command::new("base")
                 .about("Base operations")
@@ -101,10 +378,110 @@ fn build_cli() -> Command {
                         .env("BASE")
                         .required(false),
                 )
-                .subcommand(Command::new("schema").about("Print base schema"))
-                .subcommand(Command::new("collaborators").about("Print base collaborators"))
-                .subcommand(Command::new("shares").about("Print base shares"))
+                .subcommand(
+                    Command::new("schema")
+                        .about("Print base schema")
+                        .args(listing_output_args())
+                        .args_conflicts_with_subcommands(true)
+                        .subcommand(
+                            Command::new("diff")
+                                .about("Compare the schema with another base or a saved schema, exiting with status 1 on differences and 2 on errors")
+                                .after_help("Tables, fields and views are matched by ID, then by name.\nEXAMPLE: rsairtable base Staging schema diff --against Production\nEXAMPLE: rsairtable base appXXXXXXXXXXXXXX schema diff --against schema.json --format json")
+                                .arg(
+                                    Arg::new("against")
+                                        .long("against")
+                                        .value_name("BASE|FILE")
+                                        .help("Base ID, name or alias, a schema JSON file, or a backup directory")
+                                        .required(true),
+                                )
+                                .arg(
+                                    Arg::new("format")
+                                        .long("format")
+                                        .value_name("FORMAT")
+                                        .help("Output format")
+                                        .value_parser(["text", "json"])
+                                        .default_value("text"),
+                                ),
+                        )
+                        .subcommand(
+                            Command::new("migrate")
+                                .about("Plan or apply the changes that make the base match a schema file")
+                                .after_help("FILE is TOML, YAML (with the `yaml` feature) or JSON listing every table with its fields (name, type, options, description).\nThe metadata API cannot remove tables or fields or change field types and options; those changes are listed for making in Airtable.\nA plan with destructive changes is only applied with --allow-destructive.\nEXAMPLE: rsairtable base appXXXXXXXXXXXXXX schema migrate schema.toml\nEXAMPLE: rsairtable base appXXXXXXXXXXXXXX schema migrate schema.toml --apply")
+                                .arg(
+                                    Arg::new("file")
+                                        .value_name("FILE")
+                                        .help("Schema file (.toml, .yaml, .yml or .json)")
+                                        .required(true),
+                                )
+                                .arg(
+                                    Arg::new("plan")
+                                        .long("plan")
+                                        .action(clap::ArgAction::SetTrue)
+                                        .help("Show the changes without making them (default)"),
+                                )
+                                .arg(
+                                    Arg::new("apply")
+                                        .long("apply")
+                                        .action(clap::ArgAction::SetTrue)
+                                        .conflicts_with("plan")
+                                        .help("Show the changes, then make them"),
+                                )
+                                .arg(
+                                    Arg::new("allow-destructive")
+                                        .long("allow-destructive")
+                                        .action(clap::ArgAction::SetTrue)
+                                        .requires("apply")
+                                        .help("Apply a plan that removes tables, fields or select choices or changes field types; those changes are left for making in Airtable"),
+                                )
+                                .arg(
+                                    Arg::new("format")
+                                        .long("format")
+                                        .value_name("FORMAT")
+                                        .help("Output format")
+                                        .value_parser(["text", "json"])
+                                        .default_value("text"),
+                                ),
+                        ),
+                )
+                .subcommand(Command::new("collaborators").about("Print base collaborators, invite links and interfaces (Enterprise)"))
+                .subcommand(Command::new("shares").about("Print base invite links (Enterprise)"))
                 .subcommand(Command::new("orm").about("Generate Rust structs for base tables"))
+                .subcommand(
+                    Command::new("backup")
+                        .about("Back up the base schema, records, comments and attachments to a directory")
+                        .after_help("Writes manifest.json, schema.json, tables/<tableId>.ndjson and comments/<tableId>.ndjson.\nEXAMPLE: rsairtable base appXXXXXXXXXXXXXX backup --out backups/2024-06-01 --attachments")
+                        .arg(
+                            Arg::new("out")
+                                .long("out")
+                                .value_name("DIR")
+                                .help("Directory to write the backup to (created if missing)")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("attachments")
+                                .long("attachments")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Download attachment files"),
+                        )
+                        .arg(
+                            Arg::new("no-comments")
+                                .long("no-comments")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Skip record comments (saves one request per record)"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("restore")
+                        .about("Recreate the tables, records and comments of a backup in this base")
+                        .after_help("Computed fields (formulas, rollups, lookups, ...) are skipped and link fields are re-linked to the restored records.\nEXAMPLE: rsairtable base appYYYYYYYYYYYYYY restore --from backups/2024-06-01")
+                        .arg(
+                            Arg::new("from")
+                                .long("from")
+                                .value_name("DIR")
+                                .help("Backup directory written by 'base backup'")
+                                .required(true),
+                        ),
+                )
                 .subcommand(
                     Command::new("table")
                         .about("Table operations")
@@ -118,6 +495,7 @@ fn build_cli() -> Command {
                         .subcommand(
                             Command::new("records")
                                 .about("Retrieve records from table")
+                                .args(listing_output_args())
                                 .arg(
                                     Arg::new("formula")
                                         .short('w')
@@ -151,7 +529,7 @@ fn build_cli() -> Command {
                                         .short('S')
                                         .long("sort")
                                         .value_name("FIELD")
-                                        .help("Sort records by field(s)")
+                                        .help("Sort records by field(s); \"Field desc\" sorts descending")
                                         .action(clap::ArgAction::Append),
                                 )
                                 .arg(
@@ -167,7 +545,7 @@ fn build_cli() -> Command {
                                         .short('D')
                                         .long("direction")
                                         .value_name("DIRECTION")
-                                        .help("Sort direction (asc/desc)")
+                                        .help("Sort direction (asc/desc) of the --sort field at the same position")
                                         .value_parser(["asc", "desc"])
                                         .action(clap::ArgAction::Append),
                                 )
@@ -185,34 +563,76 @@ fn build_cli() -> Command {
                                         .conflicts_with_all(["offset", "limit"]),
                                 ),
                         )
-                        .subcommand(Command::new("schema").about("Print table schema"))
+                        .subcommand(
+                            Command::new("schema")
+                                .about("Print table schema")
+                                .args(listing_output_args()),
+                        )
+                        .subcommand(
+                            Command::new("get")
+                                .about("Get a single record by ID")
+                                .after_help("EXAMPLE: rsairtable base appXXX table Cases get recXXXXXXXXXXXXXX --expand Client --expand Judge --depth 2")
+                                .arg(
+                                    Arg::new("record-id")
+                                        .value_name("RECORD_ID")
+                                        .help("Record ID to get")
+                                        .required(true),
+                                )
+                                .arg(
+                                    Arg::new("expand")
+                                        .short('x')
+                                        .long("expand")
+                                        .value_name("FIELD")
+                                        .help("Replace linked record IDs in FIELD with the linked records ('*' for every link field)")
+                                        .action(clap::ArgAction::Append),
+                                )
+                                .arg(
+                                    Arg::new("depth")
+                                        .long("depth")
+                                        .value_name("LEVELS")
+                                        .help("How many levels of links to expand")
+                                        .value_parser(clap::value_parser!(usize))
+                                        .default_value("1")
+                                        .requires("expand"),
+                                ),
+                        )
                         .subcommand(
                             Command::new("create")
                                 .about("Create a new record")
+                                .after_help(STDIN_HELP)
                                 .arg(
                                     Arg::new("fields")
                                         .short('j')
                                         .long("fields")
                                         .value_name("JSON")
                                         .help("Record fields as JSON")
-                                        .required(true),
+                                        .required_unless_present("stdin"),
                                 )
+                                .arg(stdin_arg("fields objects, optionally wrapped as {\"fields\": {...}}", &["fields"]))
                                 .arg(
                                     Arg::new("typecast")
                                         .short('t')
                                         .long("typecast")
                                         .action(clap::ArgAction::SetTrue)
-                                        .help("Enable automatic typecasting"),
+                                        .help("Enable automatic typecasting")
+                                        .conflicts_with("stdin"),
+                                )
+                                .arg(
+                                    Arg::new("validate")
+                                        .long("validate")
+                                        .action(clap::ArgAction::SetTrue)
+                                        .help("Check fields against the table schema before sending"),
                                 ),
                         )
                         .subcommand(
                             Command::new("update")
                                 .about("Update an existing record")
+                                .after_help(STDIN_HELP)
                                 .arg(
                                     Arg::new("record-id")
                                         .value_name("RECORD_ID")
                                         .help("Record ID to update")
-                                        .required(true),
+                                        .required_unless_present("stdin"),
                                 )
                                 .arg(
                                     Arg::new("fields")
@@ -220,112 +640,616 @@ fn build_cli() -> Command {
                                         .long("fields")
                                         .value_name("JSON")
                                         .help("Record fields as JSON")
-                                        .required(true),
+                                        .required_unless_present("stdin"),
                                 )
+                                .arg(stdin_arg("{\"id\": \"recXXX\", \"fields\": {...}} objects", &["record-id", "fields"]))
                                 .arg(
                                     Arg::new("typecast")
                                         .short('t')
                                         .long("typecast")
                                         .action(clap::ArgAction::SetTrue)
-                                        .help("Enable automatic typecasting"),
+                                        .help("Enable automatic typecasting")
+                                        .conflicts_with("stdin"),
+                                )
+                                .arg(
+                                    Arg::new("validate")
+                                        .long("validate")
+                                        .action(clap::ArgAction::SetTrue)
+                                        .help("Check fields against the table schema before sending"),
+                                ),
+                        )
+                        .subcommand(
+                            Command::new("upsert")
+                                .about("Create a record, or update the record matching on --key fields")
+                                .after_help(STDIN_HELP)
+                                .arg(
+                                    Arg::new("fields")
+                                        .short('j')
+                                        .long("fields")
+                                        .value_name("JSON")
+                                        .help("Record fields as JSON")
+                                        .required_unless_present("stdin"),
+                                )
+                                .arg(stdin_arg("fields objects, optionally wrapped as {\"fields\": {...}}", &["fields"]))
+                                .arg(
+                                    Arg::new("key")
+                                        .long("key")
+                                        .value_name("FIELD")
+                                        .help("Field to match existing records on (repeatable)")
+                                        .action(clap::ArgAction::Append)
+                                        .required(true),
+                                )
+                                .arg(
+                                    Arg::new("validate")
+                                        .long("validate")
+                                        .action(clap::ArgAction::SetTrue)
+                                        .help("Check fields against the table schema before sending"),
+                                ),
+                        )
+                        .subcommand(
+                            Command::new("delete")
+                                .about("Delete a record")
+                                .after_help(STDIN_HELP)
+                                .arg(
+                                    Arg::new("record-id")
+                                        .value_name("RECORD_ID")
+                                        .help("Record ID to delete")
+                                        .required_unless_present("stdin"),
+                                )
+                                .arg(stdin_arg("record IDs, as \"recXXX\", {\"id\": \"recXXX\"} or bare recXXX", &["record-id"])),
+                        )
+                        .subcommand(
+                            Command::new("import")
+                                .about("Import records from a CSV file")
+                                .after_help("Headers are matched to fields by name (exactly, then case-insensitively) unless --mapping says otherwise. Values are converted to each field's type; linked records are looked up by the linked table's primary field.\nRows that cannot be imported are written to --rejected (default: FILE.rejected.csv).\nEXAMPLE: rsairtable base appXXX table Cases import cases.csv --date-format %m/%d/%Y --upsert \"Case Number\"")
+                                .arg(
+                                    Arg::new("file")
+                                        .value_name("FILE")
+                                        .help("CSV file with a header row")
+                                        .required(true),
+                                )
+                                .arg(
+                                    Arg::new("mapping")
+                                        .long("mapping")
+                                        .value_name("PATH")
+                                        .help("JSON object mapping CSV headers to field names or IDs"),
+                                )
+                                .arg(
+                                    Arg::new("date-format")
+                                        .long("date-format")
+                                        .value_name("FORMAT")
+                                        .help("chrono format for date cells (default: ISO 8601)"),
+                                )
+                                .arg(
+                                    Arg::new("delimiter")
+                                        .long("delimiter")
+                                        .value_name("CHAR")
+                                        .help("Cell separator, e.g. ';' or 'tab' [default: ,]")
+                                        .value_parser(parse_delimiter),
+                                )
+                                .arg(
+                                    Arg::new("list-delimiter")
+                                        .long("list-delimiter")
+                                        .value_name("CHAR")
+                                        .help("Separator for multiple selects, links and attachments [default: ,]")
+                                        .value_parser(parse_delimiter),
+                                )
+                                .arg(
+                                    Arg::new("upsert")
+                                        .long("upsert")
+                                        .value_name("FIELD")
+                                        .help("Update records matching on this field instead of creating duplicates (repeatable)")
+                                        .action(clap::ArgAction::Append),
+                                )
+                                .arg(
+                                    Arg::new("rejected")
+                                        .long("rejected")
+                                        .value_name("PATH")
+                                        .help("Where to write rows that could not be imported"),
                                 ),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("completions")
+                .about("Print a shell completion script for bash, zsh or fish")
+                .after_help("Base, table, field and view names complete from a local schema cache, filled by 'completions refresh' and by the bases and schema commands. Completion never calls the API.\nEXAMPLES:\n  source <(rsairtable completions bash)                # ~/.bashrc\n  source <(rsairtable completions zsh)                 # ~/.zshrc, after compinit\n  rsairtable completions fish > ~/.config/fish/completions/rsairtable.fish\n  rsairtable completions refresh")
+                .args_conflicts_with_subcommands(true)
+                .subcommand_negates_reqs(true)
+                .arg(
+                    Arg::new("shell")
+                        .value_name("SHELL")
+                        .value_parser(["bash", "zsh", "fish"])
+                        .required(true),
+                )
+                .subcommand(
+                    Command::new("refresh")
+                        .about("Fetch base, table, field and view names into the completion cache")
+                        .arg(
+                            Arg::new("base")
+                                .long("base")
+                                .value_name("BASE")
+                                .help("Only refresh this base (repeatable) [default: every base]")
+                                .action(clap::ArgAction::Append),
+                        ),
+                )
+                .subcommand(
+                    Command::new("names")
+                        .about("Print cached names, one per line (used by the completion scripts)")
+                        .hide(true)
+                        .arg(
+                            Arg::new("kind")
+                                .value_name("KIND")
+                                .value_parser(NameKind::NAMES)
+                                .required(true),
                         )
+                        .arg(Arg::new("base").long("base").value_name("BASE"))
+                        .arg(Arg::new("table").long("table").value_name("TABLE")),
+                ),
+        )
+        .subcommand(
+            Command::new("shell")
+                .about("Explore bases interactively with history and tab completion (requires the `shell` feature)")
+                .after_help("Type 'help' in the shell for its commands. Base, table, field and view names complete with Tab.\nEXAMPLES:\n  rsairtable shell\n  rsairtable shell @cases\n  rsairtable shell \"Law Firm/Cases\"")
+                .arg(
+                    Arg::new("target")
+                        .value_name("TARGET")
+                        .help("Base or BASE/TABLE (name, ID or @alias) to start in [default: the profile's base]"),
+                ),
+        )
+        .subcommand(
+            Command::new("tui")
+                .about("Browse a table in a full-screen grid (requires the `tui` feature)")
+                .after_help("Keys: arrows move, Enter opens a record with comments, e edits a cell, / filters with a formula, s sorts by the column, v picks a view, r reloads, q quits.\nEXAMPLE: rsairtable tui base appXXXXXXXXXXXXXX table Cases")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("base")
+                        .about("Base to browse")
+                        .arg(
+                            Arg::new("base-id")
+                                .value_name("BASE_ID")
+                                .help("Base ID, name or @alias [env: BASE]")
+                                .env("BASE"),
+                        )
+                        .subcommand_required(true)
                         .subcommand(
-                            Command::new("delete").about("Delete a record").arg(
-                                Arg::new("record-id")
-                                    .value_name("RECORD_ID")
-                                    .help("Record ID to delete")
-                                    .required(true),
-                            ),
+                            Command::new("table")
+                                .about("Table to browse")
+                                .arg(
+                                    Arg::new("table-name")
+                                        .value_name("TABLE_NAME")
+                                        .help("Table name, ID or @alias")
+                                        .required(true),
+                                ),
                         ),
                 ),
         )
+        .subcommand(
+            Command::new("sync")
+                .about("Mirror tables into a local SQLite database (requires the `sync` feature)")
+                .after_help("The first sync of a table pulls every record; later syncs fetch only records changed since the last sync and remove deleted ones.\nEXAMPLE: rsairtable sync --db cases.sqlite --table Cases")
+                .arg(
+                    Arg::new("db")
+                        .long("db")
+                        .value_name("PATH")
+                        .help("SQLite database file (created if missing)")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("table")
+                        .long("table")
+                        .value_name("TABLE_NAME")
+                        .help("Table to mirror (repeat for several tables)")
+                        .action(clap::ArgAction::Append)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("base")
+                        .long("base")
+                        .value_name("BASE_ID")
+                        .help("Base ID (e.g., appXXXXXXXXXXXXXX) [env: BASE]")
+                        .env("BASE"),
+                )
+                .arg(
+                    Arg::new("full")
+                        .long("full")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Pull every record instead of only changes"),
+                ),
+        )
         .subcommand(
             Command::new("enterprise")
-                .about("Enterprise operations")
-                .subcommand(Command::new("audit-log").about("Retrieve audit log"))
-                .subcommand(Command::new("users").about("List enterprise users"))
-                .subcommand(Command::new("claims").about("List enterprise claims")),
+                .about("Enterprise operations (requires an Enterprise plan admin token)")
+                .arg(
+                    Arg::new("account-id")
+                        .value_name("ACCOUNT_ID")
+                        .help("Enterprise account ID (e.g., entXXXXXXXXXXXXXX) [env: ENTERPRISE_ACCOUNT]")
+                        .env("ENTERPRISE_ACCOUNT")
+                        .required(true),
+                )
+                .subcommand(Command::new("info").about("Print enterprise account information"))
+                .subcommand(
+                    Command::new("audit-log")
+                        .about("Retrieve audit log events")
+                        .arg(
+                            Arg::new("start-time")
+                                .long("start-time")
+                                .value_name("RFC3339")
+                                .help("Only include events at or after this time (e.g., 2024-01-01T00:00:00Z)"),
+                        )
+                        .arg(
+                            Arg::new("end-time")
+                                .long("end-time")
+                                .value_name("RFC3339")
+                                .help("Only include events before this time"),
+                        )
+                        .arg(
+                            Arg::new("event-type")
+                                .long("event-type")
+                                .value_name("TYPE")
+                                .help("Only include events of this type")
+                                .action(clap::ArgAction::Append),
+                        )
+                        .arg(
+                            Arg::new("user")
+                                .long("user")
+                                .value_name("USER_ID")
+                                .help("Only include events performed by this user"),
+                        )
+                        .arg(
+                            Arg::new("model-id")
+                                .long("model-id")
+                                .value_name("ID")
+                                .help("Only include events affecting this base, workspace, etc."),
+                        )
+                        .arg(
+                            Arg::new("page-size")
+                                .long("page-size")
+                                .value_name("NUMBER")
+                                .help("Number of events per page (max 1000)")
+                                .value_parser(clap::value_parser!(u32)),
+                        )
+                        .arg(
+                            Arg::new("sort-order")
+                                .long("sort-order")
+                                .value_name("ORDER")
+                                .help("Event order")
+                                .value_parser(["ascending", "descending"]),
+                        )
+                        .arg(
+                            Arg::new("next")
+                                .long("next")
+                                .value_name("CURSOR")
+                                .help("Continue from specified pagination cursor"),
+                        )
+                        .arg(
+                            Arg::new("all")
+                                .long("all")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Retrieve all events by following pagination cursors")
+                                .conflicts_with("next"),
+                        )
+                        .args(listing_output_args()),
+                )
+                .subcommand(
+                    Command::new("users")
+                        .about("Look up enterprise users by ID or email")
+                        .arg(
+                            Arg::new("user")
+                                .value_name("USER_ID_OR_EMAIL")
+                                .help("User ID (usrXXX) or email address")
+                                .required(true)
+                                .num_args(1..),
+                        )
+                        .args(listing_output_args()),
+                )
+                .subcommand(
+                    Command::new("grant-admin")
+                        .about("Grant enterprise admin access to users")
+                        .arg(
+                            Arg::new("user")
+                                .value_name("USER_ID_OR_EMAIL")
+                                .help("User ID (usrXXX) or email address")
+                                .required(true)
+                                .num_args(1..),
+                        ),
+                )
+                .subcommand(
+                    Command::new("revoke-admin")
+                        .about("Revoke enterprise admin access from users")
+                        .arg(
+                            Arg::new("user")
+                                .value_name("USER_ID_OR_EMAIL")
+                                .help("User ID (usrXXX) or email address")
+                                .required(true)
+                                .num_args(1..),
+                        ),
+                )
+                .subcommand(
+                    Command::new("claims")
+                        .about("Claim users into (or release them from) enterprise management")
+                        .arg(
+                            Arg::new("managed")
+                                .long("managed")
+                                .value_name("USER_ID_OR_EMAIL")
+                                .help("Claim this user as managed by the enterprise")
+                                .action(clap::ArgAction::Append),
+                        )
+                        .arg(
+                            Arg::new("unmanaged")
+                                .long("unmanaged")
+                                .value_name("USER_ID_OR_EMAIL")
+                                .help("Release this user from enterprise management")
+                                .action(clap::ArgAction::Append),
+                        )
+                        .group(
+                            clap::ArgGroup::new("claim-users")
+                                .args(["managed", "unmanaged"])
+                                .multiple(true)
+                                .required(true),
+                        ),
+                ),
+        )
+}
+
+/// Shared help for commands that accept `--stdin`
+const STDIN_HELP: &str = "With --stdin, one JSON value is read per line and written in batches of 10. Each written record is printed to stdout as {\"line\", \"status\", \"id\"}; failures go to stderr with their line number.\nEXAMPLE: jq -c '.[]' cases.json | rsairtable base appXXX table Cases create --stdin";
+
+/// `--stdin` flag for bulk record writes
+fn stdin_arg(lines: &'static str, conflicts: &[&'static str]) -> Arg {
+    Arg::new("stdin")
+        .long("stdin")
+        .action(clap::ArgAction::SetTrue)
+        .help(format!("Read newline-delimited JSON from stdin: {}", lines))
+        .conflicts_with_all(conflicts)
+}
+
+/// Apply a bulk write to NDJSON read from stdin, streaming one result line per record
+async fn run_bulk(table: &TableHandle, operation: BulkOperation) -> Result<(), Box<dyn std::error::Error>> {
+    let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+    let mut stdout = std::io::stdout().lock();
+    let mut write_error = None;
+
+    let summary = table
+        .bulk_write(&operation, stdin, |result| match result {
+            Ok(outcome) => {
+                let line = serde_json::to_string(&outcome).unwrap_or_default();
+                if let Err(e) = writeln!(stdout, "{}", line).and_then(|_| stdout.flush()) {
+                    write_error.get_or_insert(e);
+                }
+            }
+            Err(failure) => eprintln!("❌ {}", failure),
+        })
+        .await?;
+
+    if let Some(e) = write_error {
+        return Err(e.into());
+    }
+    if summary.failed > 0 {
+        return Err(format!(
+            "{} of {} lines failed",
+            summary.failed,
+            summary.failed + summary.succeeded
         )
+        .into());
+    }
+    Ok(())
+}
+
+/// `--format` and `--output` arguments for record, schema, base and enterprise listings
+fn listing_output_args() -> [Arg; 2] {
+    [
+        Arg::new("format")
+            .long("format")
+            .value_name("FORMAT")
+            .help("Output format (default: the command's JSON or text output)")
+            .value_parser(OutputFormat::NAMES),
+        Arg::new("output")
+            .short('o')
+            .long("output")
+            .value_name("FILE")
+            .help("Write output to FILE instead of stdout"),
+    ]
+}
+
+/// Format selected with `--format`, if any
+fn listing_format(matches: &ArgMatches) -> Option<OutputFormat> {
+    matches
+        .get_one::<String>("format")
+        .map(|name| name.parse().expect("value_parser only accepts known formats"))
+}
+
+/// Writer for `--output FILE`, or stdout
+fn output_writer(matches: &ArgMatches) -> Result<Box<dyn Write>, Box<dyn std::error::Error>> {
+    Ok(match matches.get_one::<String>("output") {
+        Some(path) => Box::new(std::io::BufWriter::new(std::fs::File::create(path)?)),
+        None => Box::new(std::io::stdout().lock()),
+    })
 }
 
-async fn run_command(matches: ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+/// Print listed records in the selected format, through a data view if requested
+///
+/// Without `--format`, records are printed as the `[records, offset]` pair
+/// earlier versions printed. Tabular formats order columns by the table schema.
+async fn print_records(
+    table: &TableHandle,
+    matches: &ArgMatches,
+    records: Vec<rsairtable::Record>,
+    offset: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let format = listing_format(matches);
+    let mut out = output_writer(matches)?;
+
+    if let Some(data_view) = matches.get_one::<String>("data-view") {
+        let view_result = process_with_view(data_view, records)?;
+        output::write_value(&mut out, format.unwrap_or(OutputFormat::Json), &view_result)?;
+    } else if let Some(format) = format {
+        let field_order: Vec<String> = if format.is_tabular() {
+            match table.schema().await {
+                Ok(schema) => schema.fields.into_iter().map(|f| f.name).collect(),
+                Err(_) => Vec::new(),
+            }
+        } else {
+            Vec::new()
+        };
+        output::write_records(&mut out, format, &records, &field_order)?;
+    } else {
+        writeln!(out, "{}", serde_json::to_string_pretty(&(records, offset))?)?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+async fn run_command(
+    matches: ArgMatches,
+    metrics: Arc<InMemoryMetrics>,
+) -> Result<(), Box<dyn std::error::Error>> {
     // Check for help-detail flag first
     if matches.get_flag("help-detail") {
         print_detailed_help();
         return Ok(());
     }
 
-    // Get API key from various sources with priority order
-    let api_key = get_api_key(&matches)?;
+    // Profiles are managed without an API key
+    if let Some(("config", config_matches)) = matches.subcommand() {
+        return run_config(config_matches).await;
+    }
+    // Completion reads only local files so it never waits on the network
+    if let Some(("completions", completions_matches)) = matches.subcommand() {
+        if completions_matches.subcommand_name() != Some("refresh") {
+            return run_completions(&matches, completions_matches);
+        }
+    }
+
+    if matches.get_flag("default-dry-run") && matches.subcommand().is_some() {
+        return Err("--default-dry-run only applies when no command is given".into());
+    }
 
-    // Create client
-    let mut config = Config::new(api_key);
+    // Get API key and profile settings from various sources with priority order
+    let mut config = build_config(&matches)?;
     if matches.get_flag("verbose") {
         config = config.with_verbose(true);
     }
 
-    let client = Client::from_config(config);
+    // The dry run describes the default command from the same configuration it runs with
+    if matches.get_flag("default-dry-run") {
+        print_default_plan(&config, &default_export(&config)?);
+        return Ok(());
+    }
+
+    let client = Client::from_config(config)?.with_metrics(metrics.clone());
 
     match matches.subcommand() {
         Some(("whoami", _)) => {
             let user_info = client.whoami().await?;
             println!("{}", serde_json::to_string_pretty(&user_info)?);
         }
-        Some(("bases", _)) => {
-            let bases = client.bases().await?;
-            for base in bases {
-                println!("{} - {}", base.id, base.name);
+        Some(("bases", bases_matches)) => match bases_matches.subcommand() {
+            Some(("create", create_matches)) => {
+                let workspace_id = create_matches.get_one::<String>("workspace").unwrap();
+                let name = create_matches.get_one::<String>("name").unwrap();
+                let tables_json = match create_matches.get_one::<String>("tables-file") {
+                    Some(path) => std::fs::read_to_string(path)?,
+                    None => create_matches.get_one::<String>("tables").unwrap().clone(),
+                };
+                let tables: Vec<TableDefinition> = serde_json::from_str(&tables_json)?;
+
+                let created = client.create_base(workspace_id, name, tables).await?;
+                println!("✅ Created base: {}", created.id);
+                println!("{}", serde_json::to_string_pretty(&created)?);
             }
-        }
-        Some(("base", base_matches)) => {
-            let base_id = match base_matches.get_one::<String>("base-id") {
-                Some(id) => id.clone(),
-                None => {
-                    // Try to auto-detect base if only one is available
-                    let bases = client.bases().await?;
-                    if bases.len() == 1 {
-                        println!("Auto-detected base: {} - {}", bases[0].id, bases[0].name);
-                        bases[0].id.clone()
-                    } else if bases.is_empty() {
-                        return Err(Box::new(rsairtable::Error::config("No bases found. Check your API key and permissions.")));
-                    } else {
-                        println!("Multiple bases available:");
-                        for base in &bases {
-                            println!("  {} - {}", base.id, base.name);
+            _ => {
+                let bases = client.bases().await?;
+                update_completion_cache(&client, |cache| cache.store_bases(&bases));
+                let mut out = output_writer(bases_matches)?;
+                match listing_format(bases_matches) {
+                    Some(format) => output::write_value(&mut out, format, &bases)?,
+                    None => {
+                        for base in bases {
+                            writeln!(out, "{} - {}", base.id, base.name)?;
                         }
-                        return Err(Box::new(rsairtable::Error::config(
-                            "Multiple bases found. Please specify BASE_ID or set BASE environment variable."
-                        )));
                     }
                 }
-            };
+            }
+        },
+        Some(("sync", sync_matches)) => {
+            run_sync(&client, sync_matches).await?;
+        }
+        Some(("completions", completions_matches)) => {
+            let refresh_matches = completions_matches.subcommand_matches("refresh").expect("only refresh needs a client");
+            let cache = SchemaCache::open_default(client.config().profile.as_deref()).ok_or("Cannot locate the cache directory: HOME is not set")?;
+            let mut only = Vec::new();
+            for base in refresh_matches.get_many::<String>("base").into_iter().flatten() {
+                only.push(if is_base_id(base) { base.clone() } else { client.resolve(base).await?.base_id });
+            }
+            let stored = cache.refresh(&client, &only).await?;
+            println!("✅ Cached names from {} base(s) in {}", stored, cache.dir().display());
+        }
+        Some(("shell", shell_matches)) => {
+            run_shell(client, shell_matches, metrics).await?;
+        }
+        Some(("tui", tui_matches)) => {
+            run_tui(&client, tui_matches).await?;
+        }
+        Some(("base", base_matches)) => {
+            let base_id = resolve_base_id(&client, Some(base_matches), "base-id").await?;
             let base = client.base(&base_id);
 
             match base_matches.subcommand() {
-                Some(("schema", _)) => {
+                Some(("schema", schema_matches)) => {
+                    if let Some(("migrate", migrate_matches)) = schema_matches.subcommand() {
+                        run_migrate(&base, migrate_matches).await?;
+                        return Ok(());
+                    }
                     let schema = base.schema().await?;
-                    println!("{}", serde_json::to_string_pretty(&schema)?);
+                    update_completion_cache(&client, |cache| cache.store_schema(&base_id, &schema));
+                    if let Some(("diff", diff_matches)) = schema_matches.subcommand() {
+                        let against = diff_matches.get_one::<String>("against").unwrap();
+                        let diff = load_schema(&client, against).await?.diff(&schema);
+                        if diff_matches.get_one::<String>("format").map(String::as_str) == Some("json") {
+                            println!("{}", serde_json::to_string_pretty(&diff)?);
+                        } else {
+                            print!("{}", diff);
+                        }
+                        if !diff.is_empty() {
+                            // Drift fails the command so deploys can be gated on it
+                            return Err(SchemaDrift.into());
+                        }
+                    } else {
+                        let format = listing_format(schema_matches).unwrap_or(OutputFormat::Json);
+                        output::write_schema(&mut output_writer(schema_matches)?, format, &schema)?;
+                    }
                 }
                 Some(("collaborators", _)) => {
-                    // Note: Collaborators endpoint is not available in the public API
-                    println!(
-                        "Collaborators information is not available via the public Airtable API"
-                    );
-                    println!("Use the Airtable web interface to manage collaborators");
+                    // Requires an Enterprise plan token
+                    let collaborators = base.collaborators().await?;
+                    println!("{}", serde_json::to_string_pretty(&collaborators)?);
                 }
                 Some(("shares", _)) => {
-                    // Note: Shares endpoint is not available in the public API
-                    println!("Shares information is not available via the public Airtable API");
-                    println!("Use the Airtable web interface to manage shares");
+                    // Requires an Enterprise plan token
+                    let shares = base.shares().await?;
+                    println!("{}", serde_json::to_string_pretty(&shares)?);
                 }
                 Some(("orm", _)) => {
                     let schema = base.schema().await?;
                     generate_rust_structs(&schema);
                 }
+                Some(("backup", backup_matches)) => {
+                    let out = backup_matches.get_one::<String>("out").unwrap();
+                    let options = BackupOptions::default()
+                        .with_comments(!backup_matches.get_flag("no-comments"))
+                        .with_attachments(backup_matches.get_flag("attachments"));
+                    let manifest = base.backup(out, &options).await?;
+                    print!("{}", manifest);
+                }
+                Some(("restore", restore_matches)) => {
+                    let from = restore_matches.get_one::<String>("from").unwrap();
+                    let report = base.restore(from).await?;
+                    print!("{}", report);
+                }
                 Some(("table", table_matches)) => {
                     let table_name = table_matches.get_one::<String>("table-name").unwrap();
-                    let table = base.table(table_name);
+                    let table = base.table(&resolve_table_name(&client, &base, table_name).await?);
 
                     match table_matches.subcommand() {
                         Some(("records", record_matches)) => {
@@ -350,8 +1274,8 @@ async fn run_command(matches: ArgMatches) -> Result<(), Box<dyn std::error::Erro
                                 query = query.fields(&field_refs);
                             }
 
-                            if let Some(sorts) = record_matches.get_many::<String>("sort") {
-                                let sort_list: Vec<String> = sorts.cloned().collect();
+                            let sort = sort_specs(record_matches)?;
+                            if let Some(sort_list) = sort.clone() {
                                 query = query.sort(sort_list);
                             }
 
@@ -388,8 +1312,7 @@ async fn run_command(matches: ArgMatches) -> Result<(), Box<dyn std::error::Erro
                                             field_list.iter().map(|s| s.as_str()).collect();
                                         current_query = current_query.fields(&field_refs);
                                     }
-                                    if let Some(sorts) = record_matches.get_many::<String>("sort") {
-                                        let sort_list: Vec<String> = sorts.cloned().collect();
+                                    if let Some(sort_list) = sort.clone() {
                                         current_query = current_query.sort(sort_list);
                                     }
 
@@ -434,15 +1357,8 @@ async fn run_command(matches: ArgMatches) -> Result<(), Box<dyn std::error::Erro
                                     );
                                 }
 
-                                // Process with data view if specified
-                                if let Some(data_view) = record_matches.get_one::<String>("data-view") {
-                                    let view_result = process_with_view(data_view, all_records)?;
-                                    println!("{}", serde_json::to_string_pretty(&view_result)?);
-                                } else {
-                                    // Output all records in the same format as regular queries
-                                    let result = (all_records, None::<String>);
-                                    println!("{}", serde_json::to_string_pretty(&result)?);
-                                }
+                                // Output all records in the same format as regular queries
+                                print_records(&table, record_matches, all_records, None).await?;
                             } else {
                                 let records = query.execute().await?;
 
@@ -459,20 +1375,30 @@ async fn run_command(matches: ArgMatches) -> Result<(), Box<dyn std::error::Erro
                                     }
                                 }
 
-                                // Process with data view if specified
-                                if let Some(data_view) = record_matches.get_one::<String>("data-view") {
-                                    let view_result = process_with_view(data_view, records.0)?;
-                                    println!("{}", serde_json::to_string_pretty(&view_result)?);
-                                } else {
-                                    println!("{}", serde_json::to_string_pretty(&records)?);
-                                }
+                                print_records(&table, record_matches, records.0, records.1).await?;
                             }
                         }
-                        Some(("schema", _)) => {
+                        Some(("schema", schema_matches)) => {
                             let schema = table.schema().await?;
-                            println!("{}", serde_json::to_string_pretty(&schema)?);
+                            let mut out = output_writer(schema_matches)?;
+                            match listing_format(schema_matches).unwrap_or(OutputFormat::Json) {
+                                format if format.is_tabular() => {
+                                    let schema = BaseSchema { tables: vec![schema] };
+                                    output::write_schema(&mut out, format, &schema)?;
+                                }
+                                format => output::write_value(&mut out, format, &schema)?,
+                            }
                         }
                         Some(("create", create_matches)) => {
+                            let table = if create_matches.get_flag("validate") {
+                                table.validated().await?
+                            } else {
+                                table
+                            };
+                            if create_matches.get_flag("stdin") {
+                                return run_bulk(&table, BulkOperation::Create).await;
+                            }
+
                             let fields_json = create_matches.get_one::<String>("fields").unwrap();
                             let fields: serde_json::Value = serde_json::from_str(fields_json)?;
                             let typecast = create_matches.get_flag("typecast");
@@ -487,6 +1413,15 @@ async fn run_command(matches: ArgMatches) -> Result<(), Box<dyn std::error::Erro
                             println!("{}", serde_json::to_string_pretty(&record)?);
                         }
                         Some(("update", update_matches)) => {
+                            let table = if update_matches.get_flag("validate") {
+                                table.validated().await?
+                            } else {
+                                table
+                            };
+                            if update_matches.get_flag("stdin") {
+                                return run_bulk(&table, BulkOperation::Update).await;
+                            }
+
                             let record_id = update_matches.get_one::<String>("record-id").unwrap();
                             let fields_json = update_matches.get_one::<String>("fields").unwrap();
                             let fields: serde_json::Value = serde_json::from_str(fields_json)?;
@@ -501,11 +1436,54 @@ async fn run_command(matches: ArgMatches) -> Result<(), Box<dyn std::error::Erro
                             println!("✅ Updated record: {}", record.id);
                             println!("{}", serde_json::to_string_pretty(&record)?);
                         }
+                        Some(("get", get_matches)) => {
+                            let record_id = get_matches.get_one::<String>("record-id").unwrap();
+                            let mut record = table.get(record_id).await?;
+
+                            let expand: Vec<&str> = get_matches.get_many::<String>("expand").into_iter().flatten().map(String::as_str).collect();
+                            if !expand.is_empty() {
+                                let depth = *get_matches.get_one::<usize>("depth").unwrap();
+                                let mut expander = LinkExpander::new(&base).await?;
+                                expander.expand(&mut record, table.name(), &expand, depth).await?;
+                            }
+                            println!("{}", serde_json::to_string_pretty(&record)?);
+                        }
+                        Some(("upsert", upsert_matches)) => {
+                            let keys: Vec<String> = upsert_matches.get_many::<String>("key").into_iter().flatten().cloned().collect();
+                            let table = if upsert_matches.get_flag("validate") {
+                                table.validated().await?
+                            } else {
+                                table
+                            };
+                            if upsert_matches.get_flag("stdin") {
+                                return run_bulk(&table, BulkOperation::Upsert(keys)).await;
+                            }
+
+                            let fields_json = upsert_matches.get_one::<String>("fields").unwrap();
+                            let fields: serde_json::Value = serde_json::from_str(fields_json)?;
+                            let key_refs: Vec<&str> = keys.iter().map(String::as_str).collect();
+                            let response = table
+                                .batch_upsert_detailed(vec![serde_json::json!({ "fields": fields })], &key_refs)
+                                .await?;
+                            let record = &response.records[0];
+                            if response.created_records.contains(&record.id) {
+                                println!("✅ Created record: {}", record.id);
+                            } else {
+                                println!("✅ Updated record: {}", record.id);
+                            }
+                            println!("{}", serde_json::to_string_pretty(record)?);
+                        }
                         Some(("delete", delete_matches)) => {
+                            if delete_matches.get_flag("stdin") {
+                                return run_bulk(&table, BulkOperation::Delete).await;
+                            }
                             let record_id = delete_matches.get_one::<String>("record-id").unwrap();
                             table.delete(record_id).await?;
                             println!("✅ Deleted record: {}", record_id);
                         }
+                        Some(("import", import_matches)) => {
+                            run_import(&table, import_matches).await?;
+                        }
                         _ => {
                             eprintln!("No table subcommand specified");
                             process::exit(1);
@@ -519,20 +1497,127 @@ async fn run_command(matches: ArgMatches) -> Result<(), Box<dyn std::error::Erro
             }
         }
         Some(("enterprise", enterprise_matches)) => {
+            let account_id = enterprise_matches.get_one::<String>("account-id").unwrap();
+            let enterprise = client.enterprise(account_id);
+
             match enterprise_matches.subcommand() {
-                Some(("audit-log", _)) => {
-                    println!("Enterprise audit log is not available via the public Airtable API");
-                    println!("Use the Airtable Enterprise Admin Panel for audit logs");
+                Some(("info", _)) => {
+                    let info = enterprise.info().await?;
+                    println!("{}", serde_json::to_string_pretty(&info)?);
+                }
+                Some(("audit-log", audit_matches)) => {
+                    let mut query = enterprise.audit_log();
+
+                    if let Some(start_time) = audit_matches.get_one::<String>("start-time") {
+                        query = query.start_time(parse_timestamp(start_time)?);
+                    }
+                    if let Some(end_time) = audit_matches.get_one::<String>("end-time") {
+                        query = query.end_time(parse_timestamp(end_time)?);
+                    }
+                    if let Some(event_types) = audit_matches.get_many::<String>("event-type") {
+                        for event_type in event_types {
+                            query = query.event_type(event_type);
+                        }
+                    }
+                    if let Some(user) = audit_matches.get_one::<String>("user") {
+                        query = query.originating_user_id(user);
+                    }
+                    if let Some(model_id) = audit_matches.get_one::<String>("model-id") {
+                        query = query.model_id(model_id);
+                    }
+                    if let Some(page_size) = audit_matches.get_one::<u32>("page-size") {
+                        query = query.page_size(*page_size);
+                    }
+                    if let Some(sort_order) = audit_matches.get_one::<String>("sort-order") {
+                        query = query.sort_order(sort_order);
+                    }
+                    if let Some(next) = audit_matches.get_one::<String>("next") {
+                        query = query.next(Some(next.clone()));
+                    }
+
+                    let format = listing_format(audit_matches);
+                    let mut out = output_writer(audit_matches)?;
+                    let verbose = matches.get_flag("verbose");
+
+                    if audit_matches.get_flag("all") {
+                        if format == Some(OutputFormat::Ndjson) {
+                            // Stream each page as it arrives so large logs can be piped into a SIEM
+                            let mut total = 0;
+                            loop {
+                                let (events, next) = query.execute().await?;
+                                total += events.len();
+                                output::write_value(&mut out, OutputFormat::Ndjson, &events)?;
+                                out.flush()?;
+                                if verbose {
+                                    eprintln!("Retrieved {} events (total: {})", events.len(), total);
+                                }
+                                if next.is_none() || events.is_empty() {
+                                    break;
+                                }
+                                query = query.next(next);
+                            }
+                        } else {
+                            let events = query.all().await?;
+                            if verbose {
+                                eprintln!("Retrieved {} events", events.len());
+                            }
+                            output::write_value(&mut out, format.unwrap_or(OutputFormat::Json), &events)?;
+                        }
+                    } else {
+                        let (events, next) = query.execute().await?;
+                        if verbose {
+                            if next.is_some() {
+                                eprintln!("Retrieved {} events (more available, use --all or --next)", events.len());
+                            } else {
+                                eprintln!("Retrieved {} events", events.len());
+                            }
+                        }
+                        match format {
+                            Some(format) => output::write_value(&mut out, format, &events)?,
+                            None => {
+                                let page = serde_json::json!({ "events": events, "offset": next });
+                                output::write_value(&mut out, OutputFormat::Json, &page)?;
+                            }
+                        }
+                    }
+                    out.flush()?;
                 }
-                Some(("users", _)) => {
-                    println!(
-                        "Enterprise users management is not available via the public Airtable API"
-                    );
-                    println!("Use the Airtable Enterprise Admin Panel for user management");
+                Some(("users", users_matches)) => {
+                    let (emails, user_ids): (Vec<&str>, Vec<&str>) = users_matches
+                        .get_many::<String>("user")
+                        .unwrap()
+                        .map(|s| s.as_str())
+                        .partition(|user| user.contains('@'));
+                    let users = enterprise.users(&user_ids, &emails).await?;
+
+                    let mut out = output_writer(users_matches)?;
+                    let format = listing_format(users_matches).unwrap_or(OutputFormat::Json);
+                    output::write_value(&mut out, format, &users)?;
+                    out.flush()?;
+                }
+                Some((command @ ("grant-admin" | "revoke-admin"), admin_matches)) => {
+                    let users: Vec<&str> = admin_matches
+                        .get_many::<String>("user")
+                        .unwrap()
+                        .map(|s| s.as_str())
+                        .collect();
+                    let result = if command == "grant-admin" {
+                        enterprise.grant_admin(&users).await?
+                    } else {
+                        enterprise.revoke_admin(&users).await?
+                    };
+                    println!("{}", serde_json::to_string_pretty(&result)?);
                 }
-                Some(("claims", _)) => {
-                    println!("Enterprise claims information is not available via the public Airtable API");
-                    println!("Use the Airtable Enterprise Admin Panel for claims management");
+                Some(("claims", claims_matches)) => {
+                    let mut claims: Vec<(&str, ClaimState)> = Vec::new();
+                    if let Some(users) = claims_matches.get_many::<String>("managed") {
+                        claims.extend(users.map(|user| (user.as_str(), ClaimState::Managed)));
+                    }
+                    if let Some(users) = claims_matches.get_many::<String>("unmanaged") {
+                        claims.extend(users.map(|user| (user.as_str(), ClaimState::Unmanaged)));
+                    }
+                    let result = enterprise.claim_users(&claims).await?;
+                    println!("{}", serde_json::to_string_pretty(&result)?);
                 }
                 _ => {
                     eprintln!("No enterprise subcommand specified");
@@ -541,48 +1626,650 @@ async fn run_command(matches: ArgMatches) -> Result<(), Box<dyn std::error::Erro
             }
         }
         _ => {
-            // Default behavior: run 'base table Cases records' and save to cases.json
-            run_default_command(client).await?;
+            // Default behavior: export the profile's default table (Cases → cases.json)
+            let export = default_export(client.config())?;
+            run_default_command(client, &export).await?;
         }
     }
 
     Ok(())
 }
 
-/// Run the default command: get all records from Cases table and save to cases.json
-async fn run_default_command(client: Client) -> Result<(), Box<dyn std::error::Error>> {
-    // Auto-detect base if only one is available
+/// Pick the base: command line, then the profile's default base, then `BASE`, then the only base
+///
+/// Aliases and base names are resolved to base IDs.
+async fn resolve_base_id(client: &Client, matches: Option<&ArgMatches>, arg: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let base_id = matches.and_then(|m| m.get_one::<String>(arg));
+    let chosen = if matches.and_then(|m| m.value_source(arg)) == Some(ValueSource::CommandLine) {
+        base_id
+    } else {
+        client.config().default_base.as_ref().or(base_id)
+    };
+    if let Some(id) = chosen {
+        if is_base_id(id) {
+            return Ok(id.clone());
+        }
+        return Ok(client.resolve(id).await?.base_id);
+    }
+
     let bases = client.bases().await?;
-    let base_id = if bases.len() == 1 {
+    if bases.len() == 1 {
         println!("Auto-detected base: {} - {}", bases[0].id, bases[0].name);
-        bases[0].id.clone()
+        Ok(bases[0].id.clone())
     } else if bases.is_empty() {
-        return Err(Box::new(rsairtable::Error::config("No bases found. Check your API key and permissions.")));
+        Err(Box::new(rsairtable::Error::config("No bases found. Check your API key and permissions.")))
     } else {
         println!("Multiple bases available:");
         for base in &bases {
             println!("  {} - {}", base.id, base.name);
         }
-        return Err(Box::new(rsairtable::Error::config(
+        Err(Box::new(rsairtable::Error::config(
             "Multiple bases found. Please specify BASE_ID or set BASE environment variable."
-        )));
+        )))
+    }
+}
+
+/// `-S` sort specs, each with the `-D` direction at the same position appended
+fn sort_specs(matches: &ArgMatches) -> Result<Option<Vec<String>>, Box<dyn std::error::Error>> {
+    let Some(fields) = matches.get_many::<String>("sort") else {
+        if matches.contains_id("direction") {
+            return Err("--direction needs a --sort field to apply to".into());
+        }
+        return Ok(None);
+    };
+    let fields: Vec<&String> = fields.collect();
+    let directions: Vec<&String> = matches.get_many::<String>("direction").into_iter().flatten().collect();
+    if directions.len() > fields.len() {
+        return Err(format!("{} --direction value(s) for {} --sort field(s)", directions.len(), fields.len()).into());
+    }
+    let specs = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match directions.get(i) {
+            Some(direction) => format!("{} {}", field, direction),
+            None => field.to_string(),
+        })
+        .collect();
+    Ok(Some(specs))
+}
+
+/// Resolve an alias or table name to the table to use in `base`
+///
+/// Table IDs are used as given. Names are looked up in the completion cache and
+/// then in the schema, so unique case-insensitive matches work; if the token
+/// may not read the schema (missing `schema.bases:read` scope) the name is used
+/// as given.
+async fn resolve_table_name(client: &Client, base: &BaseHandle, name: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let alias = name.strip_prefix('@').unwrap_or(name);
+    let name = if name.starts_with('@') || client.config().aliases.contains_key(alias) {
+        let target = client.resolve(name).await?;
+        if target.base_id != base.id() {
+            return Err(format!("Alias '{}' is in base {}, not {}", alias, target.base_id, base.id()).into());
+        }
+        target.table.ok_or_else(|| format!("Alias '{}' names a base, not a table", alias))?
+    } else {
+        client.config().table_aliases.get(name).cloned().unwrap_or_else(|| name.to_string())
+    };
+
+    if is_table_id(&name) {
+        return Ok(name);
+    }
+    if let Some(table_id) = SchemaCache::open_default(client.config().profile.as_deref()).and_then(|cache| cache.table_id(base.id(), &name)) {
+        return Ok(table_id);
+    }
+    match base.resolve_table(&name).await {
+        Ok(table_id) => Ok(table_id),
+        Err(rsairtable::Error::Api { status: 403, .. }) => Ok(name),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Expand `rsairtable @alias ...` into `base @alias [table @alias] ...`
+///
+/// The alias names a table unless it is followed by a base-level command.
+fn expand_alias_args(args: Vec<String>) -> Vec<String> {
+    const BASE_COMMANDS: [&str; 7] = ["schema", "collaborators", "shares", "orm", "backup", "restore", "table"];
+
+    // Global options followed by a separate value, e.g. `-k KEY` (not `--stats[=FORMAT]`)
+    let value_options: Vec<String> = build_cli()
+        .get_arguments()
+        .filter(|arg| arg.get_action().takes_values() && !arg.is_require_equals_set())
+        .flat_map(|arg| {
+            let short = arg.get_short().map(|short| format!("-{}", short));
+            let long = arg.get_long().map(|long| format!("--{}", long));
+            short.into_iter().chain(long)
+        })
+        .collect();
+
+    let mut index = 1;
+    while index < args.len() {
+        let arg = &args[index];
+        if value_options.contains(arg) {
+            index += 2;
+        } else if arg.starts_with('-') {
+            index += 1;
+        } else {
+            break;
+        }
+    }
+    if !args.get(index).is_some_and(|arg| arg.starts_with('@') && arg.len() > 1) {
+        return args;
+    }
+
+    let alias = args[index].clone();
+    let mut expanded = args[..index].to_vec();
+    expanded.push("base".to_string());
+    expanded.push(alias.clone());
+    match args.get(index + 1) {
+        Some(next) if BASE_COMMANDS.contains(&next.as_str()) || next.starts_with('-') => {}
+        None => {}
+        Some(_) => {
+            expanded.push("table".to_string());
+            expanded.push(alias);
+        }
+    }
+    expanded.extend_from_slice(&args[index + 1..]);
+    expanded
+}
+
+/// List, add or validate profiles in the config file
+async fn run_config(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let path = profile::default_path().ok_or("Cannot locate the config file: HOME is not set")?;
+    let mut file = ProfileFile::load(&path)?;
+
+    match matches.subcommand() {
+        Some(("add", add_matches)) => {
+            let name = add_matches.get_one::<String>("name").unwrap();
+            let mut new_profile = Profile::default();
+            if let Some(token) = add_matches.get_one::<String>("token") {
+                new_profile.set_token_source(TokenSource::Literal(token.clone()));
+            } else if let Some(token_file) = add_matches.get_one::<String>("token-file") {
+                new_profile.set_token_source(TokenSource::File(token_file.into()));
+            } else if let Some(token_env) = add_matches.get_one::<String>("token-env") {
+                new_profile.set_token_source(TokenSource::Env(token_env.clone()));
+            }
+            new_profile.base = add_matches.get_one::<String>("base").cloned();
+            for alias in add_matches.get_many::<String>("table").into_iter().flatten() {
+                let (alias, table) = alias
+                    .split_once('=')
+                    .ok_or_else(|| format!("--table '{}' must be ALIAS=TABLE", alias))?;
+                new_profile.tables.insert(alias.trim().to_string(), table.trim().to_string());
+            }
+            new_profile.endpoint_url = add_matches.get_one::<String>("endpoint-url").cloned();
+            new_profile.timeout_seconds = add_matches.get_one::<u64>("timeout").copied();
+            new_profile.max_retries = add_matches.get_one::<u32>("max-retries").copied();
+
+            let replaced = file.profiles.insert(name.clone(), new_profile).is_some();
+            if add_matches.get_flag("default") {
+                file.default_profile = Some(name.clone());
+            }
+            file.save(&path)?;
+            let verb = if replaced { "Updated" } else { "Added" };
+            println!("✅ {} profile '{}' in {}", verb, name, path.display());
+        }
+        Some(("alias", alias_matches)) => {
+            let name = alias_matches.get_one::<String>("name").unwrap().trim_start_matches('@');
+            let target = alias_matches.get_one::<String>("target").unwrap();
+            parse_alias_target(target)?;
+            let aliases = match alias_matches.get_one::<String>("profile") {
+                Some(profile_name) => &mut file
+                    .profiles
+                    .get_mut(profile_name)
+                    .ok_or_else(|| format!("Profile '{}' not found", profile_name))?
+                    .aliases,
+                None => &mut file.aliases,
+            };
+            aliases.insert(name.to_string(), target.clone());
+            file.save(&path)?;
+            println!("✅ @{} → {}", name, target);
+        }
+        Some(("validate", validate_matches)) => {
+            let names: Vec<String> = match validate_matches.get_one::<String>("name") {
+                Some(name) => vec![file.profile(Some(name))?.0.to_string()],
+                None => file.profiles.keys().cloned().collect(),
+            };
+            if names.is_empty() {
+                return Err(format!("No profiles defined in {}", path.display()).into());
+            }
+
+            let mut failed = 0;
+            for name in &names {
+                let entry = &file.profiles[name];
+                let mut problems = entry.problems();
+                if problems.is_empty() && validate_matches.get_flag("online") {
+                    let client = Client::from_config(entry.to_config()?)?;
+                    if let Err(e) = client.whoami().await {
+                        problems.push(format!("token rejected: {}", e));
+                    }
+                }
+                if problems.is_empty() {
+                    println!("✅ {}", name);
+                } else {
+                    failed += 1;
+                    for problem in problems {
+                        println!("❌ {}: {}", name, problem);
+                    }
+                }
+            }
+            if failed > 0 {
+                return Err(format!("{} of {} profiles have problems", failed, names.len()).into());
+            }
+        }
+        _ => {
+            println!("# {}", path.display());
+            for (name, entry) in &file.profiles {
+                let marker = if file.default_profile.as_ref() == Some(name) { "*" } else { " " };
+                let token = entry
+                    .token_source()
+                    .map(|source| source.to_string())
+                    .unwrap_or_else(|_| "no token".to_string());
+                let base = entry.base.as_deref().unwrap_or("-");
+                println!("{} {}\t{}\t{}", marker, name, base, token);
+                for (alias, target) in &entry.aliases {
+                    println!("    @{} → {}", alias, target);
+                }
+            }
+            for (alias, target) in &file.aliases {
+                println!("  @{} → {}", alias, target);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Mirror tables into a local SQLite database
+#[cfg(feature = "sync")]
+async fn run_sync(client: &Client, matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let base_id = resolve_base_id(client, Some(matches), "base").await?;
+    let base = client.base(&base_id);
+    let db = matches.get_one::<String>("db").expect("--db is required");
+    let mut mirror = rsairtable::sync::SqliteMirror::open(db)?;
+
+    for table_name in matches.get_many::<String>("table").into_iter().flatten() {
+        let table = base.table(&resolve_table_name(client, &base, table_name).await?);
+        let report = if matches.get_flag("full") {
+            mirror.resync_table(&table).await?
+        } else {
+            mirror.sync_table(&table).await?
+        };
+        println!("{}", report);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "sync"))]
+async fn run_sync(_client: &Client, _matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    Err("sync requires rsairtable to be built with the `sync` feature".into())
+}
+
+/// Print a completion script, or cached names for the `names` helper
+fn run_completions(matches: &ArgMatches, completions_matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(("names", names_matches)) = completions_matches.subcommand() {
+        let profile_name = matches.get_one::<String>("profile").map(String::as_str);
+        for name in completion_names(profile_name, names_matches)? {
+            println!("{}", name);
+        }
+        return Ok(());
+    }
+
+    let shell = completions_matches.get_one::<String>("shell").expect("SHELL is required");
+    let generator: clap_complete::Shell = shell.parse()?;
+    let mut out = std::io::stdout();
+    clap_complete::generate(generator, &mut build_cli(), "rsairtable", &mut out);
+    if let Some(hook) = completion::dynamic_hook(shell) {
+        out.write_all(hook.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Cached names for `completions names`, resolving aliases and the default base from the config file
+fn completion_names(profile_name: Option<&str>, matches: &ArgMatches) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let kind: NameKind = matches.get_one::<String>("kind").expect("KIND is required").parse()?;
+    let file = ProfileFile::load_default().unwrap_or_default();
+    let found = file.profile(profile_name).ok();
+    let profile = found.map(|(_, profile)| profile);
+    let Some(cache) = SchemaCache::open_default(found.map(|(name, _)| name).or(profile_name)) else {
+        return Ok(Vec::new());
+    };
+
+    let given = |id: &str| matches.get_one::<String>(id).filter(|v| !v.is_empty()).cloned();
+    let mut base = given("base")
+        .or_else(|| profile.and_then(|p| p.base.clone()))
+        .or_else(|| env::var("BASE").ok().filter(|b| !b.is_empty()));
+    let mut table = given("table");
+
+    // `@alias` (or a bare alias) names BASE[/TABLE]; profile table aliases name a table
+    let alias = |value: &str| {
+        let name = value.strip_prefix('@').unwrap_or(value);
+        profile
+            .and_then(|p| p.aliases.get(name))
+            .or_else(|| file.aliases.get(name))
+            .and_then(|target| parse_alias_target(target).ok())
+            .map(|(base, table)| (base.to_string(), table.map(str::to_string)))
+    };
+    if let Some((alias_base, _)) = base.as_deref().and_then(alias) {
+        base = Some(alias_base);
+    }
+    if let Some((alias_base, alias_table)) = table.as_deref().and_then(alias) {
+        base = Some(alias_base);
+        table = alias_table;
+    } else if let Some(name) = table.as_deref().and_then(|t| profile.and_then(|p| p.tables.get(t.strip_prefix('@').unwrap_or(t)))) {
+        table = Some(name.clone());
+    }
+
+    Ok(cache.names(kind, base.as_deref(), table.as_deref()))
+}
+
+/// Schema of a base, or read from a schema JSON file or backup directory
+async fn load_schema(client: &Client, source: &str) -> Result<BaseSchema, Box<dyn std::error::Error>> {
+    let path = std::path::Path::new(source);
+    let file = if path.is_dir() { path.join("schema.json") } else { path.to_path_buf() };
+    if file.is_file() || source.ends_with(".json") {
+        let json = std::fs::read_to_string(&file)
+            .map_err(|e| rsairtable::Error::config(format!("Cannot read schema file {}: {}", file.display(), e)))?;
+        return Ok(serde_json::from_str(&json)?);
+    }
+    let base_id = if is_base_id(source) { source.to_string() } else { client.resolve(source).await?.base_id };
+    let schema = client.base(&base_id).schema().await?;
+    update_completion_cache(client, |cache| cache.store_schema(&base_id, &schema));
+    Ok(schema)
+}
+
+/// Show or apply the migration from a base's schema to a schema file
+async fn run_migrate(base: &BaseHandle, matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let spec = SchemaSpec::from_file(matches.get_one::<String>("file").unwrap())?;
+    let json = matches.get_one::<String>("format").map(String::as_str) == Some("json");
+
+    let live = base.schema().await?;
+    let plan = spec.plan(&live);
+
+    if !matches.get_flag("apply") {
+        if json {
+            println!("{}", serde_json::to_string_pretty(&plan)?);
+        } else {
+            print!("{}", plan);
+            if !plan.is_empty() {
+                println!("Run with --apply to make these changes");
+            }
+        }
+        return Ok(());
+    }
+
+    // Show the plan, then apply it
+    if !json {
+        print!("{}", plan);
+        if plan.is_empty() {
+            return Ok(());
+        }
+        println!();
+    }
+    let report = base.apply_plan(&live, plan.clone(), matches.get_flag("allow-destructive")).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "plan": plan, "report": report }))?);
+    } else {
+        print!("{}", report);
+    }
+    match report.failed {
+        Some(failed) => Err(format!("Migration stopped after {} change(s): {}", report.applied.len(), failed.error).into()),
+        None => Ok(()),
+    }
+}
+
+/// Store names fetched by a command in the client profile's completion cache, ignoring failures
+fn update_completion_cache(client: &Client, update: impl FnOnce(&SchemaCache) -> rsairtable::Result<()>) {
+    if let Some(cache) = SchemaCache::open_default(client.config().profile.as_deref()) {
+        let _ = update(&cache);
+    }
+}
+
+/// Runs shell commands with the CLI's own parser and `run_command`
+#[cfg(feature = "shell")]
+struct ShellRunner {
+    /// Global options given before `shell`, e.g. `--profile firm`
+    global_args: Vec<String>,
+    metrics: Arc<InMemoryMetrics>,
+}
+
+#[cfg(feature = "shell")]
+#[async_trait::async_trait(?Send)]
+impl rsairtable::shell::CommandRunner for ShellRunner {
+    async fn run(&self, args: Vec<String>) -> rsairtable::Result<()> {
+        let argv = std::iter::once("rsairtable".to_string())
+            .chain(self.global_args.iter().cloned())
+            .chain(args);
+        let matches = match build_cli().try_get_matches_from(argv) {
+            Ok(matches) => matches,
+            Err(e) if e.kind() == clap::error::ErrorKind::DisplayHelp => return Ok(e.print()?),
+            Err(e) => return Err(rsairtable::Error::config(e.render().to_string().trim_end())),
+        };
+        run_command(matches, Arc::clone(&self.metrics))
+            .await
+            .map_err(|e| rsairtable::Error::Other(e.to_string()))
+    }
+}
+
+#[cfg(feature = "shell")]
+async fn run_shell(client: Client, matches: &ArgMatches, metrics: Arc<InMemoryMetrics>) -> Result<(), Box<dyn std::error::Error>> {
+    let target = matches
+        .get_one::<String>("target")
+        .or(client.config().default_base.as_ref())
+        .cloned();
+    let mut global_args = Vec::new();
+    for id in ["key", "key-file", "key-env", "profile"] {
+        if let (Some(ValueSource::CommandLine), Some(value)) = (matches.value_source(id), matches.get_one::<String>(id)) {
+            global_args.extend([format!("--{}", id), value.clone()]);
+        }
+    }
+    if matches.get_flag("verbose") {
+        global_args.push("--verbose".to_string());
+    }
+    let mut shell = rsairtable::shell::Shell::new(client, ShellRunner { global_args, metrics });
+    if let Some(target) = target {
+        shell.use_target(&target).await?;
+    }
+    shell.run().await?;
+    Ok(())
+}
+
+#[cfg(not(feature = "shell"))]
+async fn run_shell(_client: Client, _matches: &ArgMatches, _metrics: Arc<InMemoryMetrics>) -> Result<(), Box<dyn std::error::Error>> {
+    Err("shell requires rsairtable to be built with the `shell` feature".into())
+}
+
+#[cfg(feature = "tui")]
+async fn run_tui(client: &Client, matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let base_matches = matches.subcommand_matches("base").expect("base is required");
+    let base_id = resolve_base_id(client, Some(base_matches), "base-id").await?;
+    let base = client.base(&base_id);
+    let table_matches = base_matches.subcommand_matches("table").expect("table is required");
+    let table_name = table_matches.get_one::<String>("table-name").expect("TABLE_NAME is required");
+    let table = base.table(&resolve_table_name(client, &base, table_name).await?);
+    rsairtable::tui::Browser::new(table).await?.run().await?;
+    Ok(())
+}
+
+#[cfg(not(feature = "tui"))]
+async fn run_tui(_client: &Client, _matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    Err("tui requires rsairtable to be built with the `tui` feature".into())
+}
+
+/// Parse a single-character delimiter (`tab` or `\t` for a tab)
+fn parse_delimiter(value: &str) -> Result<char, String> {
+    match value {
+        "tab" | "\\t" | "\t" => Ok('\t'),
+        _ => {
+            let mut chars = value.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(c),
+                _ => Err(format!("expected a single character, got '{}'", value)),
+            }
+        }
+    }
+}
+
+/// Import a CSV file into a table and write rejected rows next to it
+async fn run_import(table: &TableHandle, matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let file = matches.get_one::<String>("file").expect("FILE is required");
+    let input = std::fs::read_to_string(file)?;
+
+    let mut options = ImportOptions::default();
+    if let Some(path) = matches.get_one::<String>("mapping") {
+        let mapping: HashMap<String, String> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        options = options.with_mapping(mapping);
+    }
+    if let Some(format) = matches.get_one::<String>("date-format") {
+        options = options.with_date_format(format);
+    }
+    let delimiter = matches.get_one::<char>("delimiter").copied().unwrap_or(',');
+    options = options.with_delimiter(delimiter);
+    if let Some(delimiter) = matches.get_one::<char>("list-delimiter") {
+        options = options.with_list_delimiter(*delimiter);
+    }
+    let keys: Vec<String> = matches.get_many::<String>("upsert").into_iter().flatten().cloned().collect();
+    options = options.with_upsert_keys(keys);
+
+    let report = table.import_csv(&input, &options).await?;
+    for (header, field) in &report.columns {
+        eprintln!("  {} -> {}", header, field);
+    }
+    println!("✅ {}", report);
+
+    if !report.rejected.is_empty() {
+        let path = matches
+            .get_one::<String>("rejected")
+            .cloned()
+            .unwrap_or_else(|| format!("{}.rejected.csv", file.trim_end_matches(".csv")));
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(&path)?);
+        report.write_rejected(&mut writer, delimiter)?;
+        eprintln!("⚠️  Wrote {} rejected rows to {}", report.rejected.len(), path);
+    }
+    Ok(())
+}
+
+/// The export run without a command: the `default_export` of the profile `config` came from, or Cases → cases.json
+fn default_export(config: &Config) -> Result<DefaultExport, Box<dyn std::error::Error>> {
+    let Some(name) = config.profile.as_deref() else {
+        return Ok(DefaultExport::default());
+    };
+    let file = ProfileFile::load_default()?;
+    let (_, profile) = file.profile(Some(name))?;
+    Ok(profile.default_export.clone().unwrap_or_default())
+}
+
+/// Base of the default command unless it is auto-detected: the profile's base, then `BASE`
+fn default_base(config: &Config) -> Option<String> {
+    config.default_base.clone().or_else(|| env::var("BASE").ok().filter(|base| !base.is_empty()))
+}
+
+/// Describe the default export without contacting Airtable
+fn print_default_plan(config: &Config, export: &DefaultExport) {
+    let base = match default_base(config) {
+        Some(base) if config.default_base.is_none() => format!("{} (from BASE)", base),
+        Some(base) => base,
+        None => "auto-detect (the only accessible base)".to_string(),
+    };
+
+    println!("Default command{}:", config.profile.as_ref().map(|name| format!(" (profile '{}')", name)).unwrap_or_default());
+    println!("  base:    {}", base);
+    println!("  table:   {}", export.table);
+    println!("  view:    {}", export.view.as_deref().unwrap_or("-"));
+    println!("  formula: {}", export.formula.as_deref().unwrap_or("-"));
+    let fields = if export.fields.is_empty() { "all".to_string() } else { export.fields.join(", ") };
+    println!("  fields:  {}", fields);
+    println!("  output:  {} ({})", export.output.display(), export.format);
+}
+
+/// Run the default command: export every matching record and write the file atomically
+async fn run_default_command(client: Client, export: &DefaultExport) -> Result<(), Box<dyn std::error::Error>> {
+    let base_id = match default_base(client.config()) {
+        Some(base) if is_base_id(&base) => base,
+        Some(base) => client.resolve(&base).await?.base_id,
+        // Auto-detect base if only one is available
+        None => resolve_base_id(&client, None, "base-id").await?,
     };
 
     let base = client.base(&base_id);
-    let table = base.table("Cases");
-    
-    // Get all records from Cases table
-    println!("Fetching all records from Cases table...");
-    let (records, _) = table.list().execute().await?;
-    
-    // Save to cases.json
-    let json_output = serde_json::to_string_pretty(&records)?;
-    std::fs::write("cases.json", json_output)?;
-    
-    println!("Successfully saved {} records to cases.json", records.len());
+    let table = base.table(&resolve_table_name(&client, &base, &export.table).await?);
+
+    let mut builder = table.iterate();
+    if let Some(view) = &export.view {
+        builder = builder.view(view);
+    }
+    if let Some(formula) = &export.formula {
+        builder = builder.filter_by_formula(formula);
+    }
+    if !export.fields.is_empty() {
+        builder = builder.fields(&export.fields.iter().map(String::as_str).collect::<Vec<_>>());
+    }
+
+    println!("Fetching all records from {} table...", export.table);
+    let mut pages = builder.build().await?;
+    let mut records = Vec::new();
+    while let Some(page) = pages.next().await {
+        records.extend(page?);
+    }
+
+    let field_order = if !export.fields.is_empty() {
+        export.fields.clone()
+    } else if export.format.is_tabular() {
+        table.schema().await?.fields.into_iter().map(|field| field.name).collect()
+    } else {
+        Vec::new()
+    };
+    let mut contents = Vec::new();
+    output::write_records(&mut contents, export.format, &records, &field_order)?;
+    output::write_atomic(&export.output, &contents)?;
+
+    println!("Successfully saved {} records to {}", records.len(), export.output.display());
     Ok(())
 }
 
+/// Parse an RFC 3339 timestamp argument
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, Box<dyn std::error::Error>> {
+    let timestamp = DateTime::parse_from_rfc3339(value)
+        .map_err(|e| rsairtable::Error::config(format!("Invalid timestamp '{}': {}", value, e)))?;
+    Ok(timestamp.with_timezone(&Utc))
+}
+
+/// Build the client configuration from `--profile` or the API key arguments
+///
+/// With `--profile`, an explicit `--key`, `--key-file` or `--key-env` still
+/// overrides the profile's token. Without it, the default profile is only used
+/// when no API key is found anywhere else, and a config file that cannot be
+/// read only loses its shared aliases, with a warning.
+fn build_config(matches: &ArgMatches) -> Result<Config, Box<dyn std::error::Error>> {
+    let explicit_key = matches.value_source("key") == Some(ValueSource::CommandLine)
+        || matches.contains_id("key-file")
+        || matches.contains_id("key-env");
+
+    if let Some(name) = matches.get_one::<String>("profile") {
+        let mut config = Config::from_profile(Some(name))?;
+        if explicit_key {
+            config.api_key = get_api_key(matches)?;
+        }
+        return Ok(config);
+    }
+
+    match get_api_key(matches) {
+        Ok(api_key) => {
+            // Shared aliases apply without a profile too
+            let mut config = Config::new(api_key);
+            match ProfileFile::load_default() {
+                Ok(file) => config.aliases = file.aliases,
+                Err(e) => eprintln!("Warning: ignoring aliases in the config file: {}", e),
+            }
+            Ok(config)
+        }
+        Err(e) if explicit_key => Err(e),
+        Err(e) => {
+            if ProfileFile::load_default()?.profiles.is_empty() {
+                return Err(e);
+            }
+            Ok(Config::from_profile(None)?)
+        }
+    }
+}
+
 fn get_api_key(matches: &ArgMatches) -> Result<String, Box<dyn std::error::Error>> {
     // Priority order: CLI arg > key-file > key-env > environment variables
 
@@ -639,6 +2326,11 @@ rsairtable --key "patXXXXXXXXXXXXXX" whoami
 echo "patXXXXXXXXXXXXXX" > ~/.airtable_key
 rsairtable --key-file ~/.airtable_key whoami
 
+# Method 4: Named profiles in ~/.config/rsairtable/config.toml
+rsairtable config add firm --token-env FIRM_PAT --base appXXXXXXXXXXXXXX --default
+rsairtable config validate
+rsairtable --profile firm whoami
+
 👤 USER INFORMATION
 ------------------
 
@@ -648,9 +2340,13 @@ rsairtable whoami
 📚 BASE OPERATIONS
 -----------------
 
-# List all bases you have access to
+# List all bases you have access to (all pages)
 rsairtable bases
 
+# Create a new base in a workspace
+rsairtable bases create --workspace wspXXXXXXXXXXXXXX --name "Law Firm" \\
+  -j '[{{"name": "Cases", "fields": [{{"name": "Name", "type": "singleLineText"}}]}}]'
+
 # Get base schema (all tables and fields)
 rsairtable base schema                          # Uses BASE env var or auto-detects if only one base
 rsairtable base appXXXXXXXXXXXXXX schema        # Explicit base ID
@@ -673,11 +2369,12 @@ rsairtable base appXXXXXXXXXXXXXX table "TableName" schema  # Explicit base ID
 rsairtable base table "TableName" records              # Uses BASE env var or auto-detects if only one base
 rsairtable base appXXXXXXXXXXXXXX table "TableName" records  # Explicit base ID
 
-# Get specific record by ID (equivalent to a "get" command)
-rsairtable base table "TableName" records \\          # Uses BASE env var or auto-detects if only one base
-  -w "RECORD_ID()='recXXXXXXXXXXXXX'"
-rsairtable base appXXXXXXXXXXXXXX table "TableName" records \\  # Explicit base ID
-  -w "RECORD_ID()='recXXXXXXXXXXXXX'"
+# Get specific record by ID
+rsairtable base table "TableName" get recXXXXXXXXXXXXXX              # Uses BASE env var or auto-detects if only one base
+rsairtable base appXXXXXXXXXXXXXX table "TableName" get recXXXXXXXXXXXXXX  # Explicit base ID
+
+# Expand linked records (repeat --expand, or '*' for every link field)
+rsairtable base table "Cases" get recXXXXXXXXXXXXXX --expand Client --expand Judge --depth 2
 
 # List specific number of records
 rsairtable base table "TableName" records -n 10       # Uses BASE env var or auto-detects if only one base
@@ -745,6 +2442,11 @@ rsairtable base table "TableName" create \\              # Uses BASE env var or
   -j '{{"Name": "Auto Convert", "Date": "2024-01-15"}}' \\
   --typecast
 
+# Check fields against the table schema before sending (reports every problem at once)
+rsairtable base table "TableName" create \\
+  -j '{{"Name": "Checked Record", "Status": "Active"}}' \\
+  --validate
+
 # Create record with multiple field types
 rsairtable base appXXXXXXXXXXXXXX table "TableName" create \\
   -j '{{
@@ -776,11 +2478,14 @@ rsairtable base appXXXXXXXXXXXXXX table "TableName" delete recXXXXXXXXXXXXX
 🏢 ADVANCED FEATURES
 -------------------
 
-# Enterprise operations (limitations noted)
-rsairtable enterprise audit-log
-rsairtable enterprise users
+# Enterprise operations (Enterprise plan admin tokens only)
+rsairtable enterprise entXXXXXXXXXXXXXX info
+rsairtable enterprise entXXXXXXXXXXXXXX audit-log --start-time 2024-01-01T00:00:00Z --all --format ndjson
+rsairtable enterprise entXXXXXXXXXXXXXX users usrXXXXXXXXXXXXXX jane@example.com
+rsairtable enterprise entXXXXXXXXXXXXXX grant-admin jane@example.com
+rsairtable enterprise entXXXXXXXXXXXXXX claims --managed jane@example.com
 
-# Base collaboration info (limitations noted)
+# Base collaborators, invite links and interfaces (Enterprise plan tokens only)
 rsairtable base appXXXXXXXXXXXXXX collaborators
 rsairtable base appXXXXXXXXXXXXXX shares
 
@@ -862,8 +2567,8 @@ pub struct Customer {{
 Some advanced features are not available via Airtable's public API:
 • Field creation/deletion (use Airtable web interface)
 • Direct file uploads (use URL-based attachments)
-• Base collaborator management (use Airtable web interface)
-• Enterprise audit logs (use Enterprise Admin Panel)
+• Base collaborators and shares (Enterprise plan tokens only)
+• Enterprise audit logs, users and claims (Enterprise plan admin tokens only)
 
 🛠️  TROUBLESHOOTING
 ------------------
@@ -901,7 +2606,7 @@ fn generate_rust_structs(schema: &BaseSchema) {
     println!("// Generated Rust structs for Airtable base");
     println!("// This is equivalent to pyairtable's ORM generation");
     println!();
-    println!("use serde::{{{}, {}}};", "Deserialize", "Serialize");
+    println!("use serde::{{Deserialize, Serialize}};");
     println!("use rsairtable::Record;");
     println!();
 
@@ -946,33 +2651,23 @@ fn generate_rust_structs(schema: &BaseSchema) {
         println!("}}\n");
     }
 
+    let first_table = schema
+        .tables
+        .first()
+        .map(|t| t.name.as_str())
+        .unwrap_or("TableName");
+    let first_struct = to_pascal_case(first_table);
+
     println!("// Usage example:");
     println!("// let client = rsairtable::Client::from_env()?;");
     println!(
         "// let table = client.base(\"your_base_id\").table(\"{}\");",
-        schema
-            .tables
-            .first()
-            .map(|t| &t.name)
-            .unwrap_or(&"TableName".to_string())
+        first_table
     );
     println!("// let records = table.list().execute().await?;");
     println!(
         "// let typed_records: Vec<{}> = records.into_iter().map({}::from_record).collect();",
-        to_pascal_case(
-            &schema
-                .tables
-                .first()
-                .map(|t| &t.name)
-                .unwrap_or(&"TableName".to_string())
-        ),
-        to_pascal_case(
-            &schema
-                .tables
-                .first()
-                .map(|t| &t.name)
-                .unwrap_or(&"TableName".to_string())
-        )
+        first_struct, first_struct
     );
 }
 
@@ -6,66 +6,122 @@
 //! with the Airtable API. The design closely follows pyairtable's client structure
 //! for maximum compatibility.
 
+use crate::cache::ResponseCache;
+use crate::cassette::CassetteTransport;
 use crate::config::Config;
+use crate::enterprise::EnterpriseHandle;
 use crate::error::{Error, Result};
+use crate::metrics::{Metrics, MetricsMiddleware, RecordAccess};
+#[cfg(feature = "tracing")]
+use crate::middleware::TracingMiddleware;
+use crate::middleware::{Middleware, RequestContext, ResponseAction, RetryMiddleware};
 use crate::models::*;
-use reqwest::{header, Client as HttpClient};
+use crate::transport::{
+    HttpRequest, HttpResponse, Method, MultipartFile, ReqwestTransport, Transport,
+};
+use crate::validation::WriteValidator;
 use serde_json::json;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use url::Url;
 
 /// Main client for interacting with Airtable API
 #[derive(Debug, Clone)]
 pub struct Client {
-    /// HTTP client for making requests
-    http_client: HttpClient,
+    /// Transport used to send HTTP requests
+    transport: Arc<dyn Transport>,
+    /// Middleware run around every request, in order
+    middleware: Vec<Arc<dyn Middleware>>,
+    /// Optional metrics hook for record counts
+    metrics: Option<Arc<dyn Metrics>>,
     /// Client configuration
-    config: Config,
+    pub(crate) config: Config,
 }
 
 impl Client {
     /// Create a new client with the given API key
     pub fn new(api_key: String) -> Self {
         let config = Config::new(api_key);
-        Self::from_config(config)
+        Self::with_transport(config, ReqwestTransport::new())
     }
 
     /// Create a new client from environment variables
     pub fn from_env() -> Result<Self> {
         let config = Config::from_env()?;
-        Ok(Self::from_config(config))
+        Self::from_config(config)
     }
 
     /// Create a new client from configuration
-    pub fn from_config(config: Config) -> Self {
-        let mut headers = header::HeaderMap::new();
-
-        // Set authorization header with Bearer token
+    ///
+    /// Wraps the transport in a [`CassetteTransport`] when a cassette is configured.
+    /// Fails if the API key cannot be sent as an HTTP header value or a replay
+    /// cassette cannot be loaded.
+    pub fn from_config(config: Config) -> Result<Self> {
         let auth_value = format!("Bearer {}", config.api_key);
-        headers.insert(
-            header::AUTHORIZATION,
-            header::HeaderValue::from_str(&auth_value).expect("Invalid API key format"),
-        );
-
-        // Set user agent
-        headers.insert(
-            header::USER_AGENT,
-            header::HeaderValue::from_static("rsairtable/0.1.0"),
-        );
+        reqwest::header::HeaderValue::from_str(&auth_value)
+            .map_err(|_| Error::config("Invalid API key format: not a valid header value"))?;
+
+        match config.cassette_path.clone() {
+            Some(path) => {
+                let mode = config.cassette_mode;
+                let transport = CassetteTransport::new(path, mode, ReqwestTransport::new())?;
+                Ok(Self::with_transport(config, transport))
+            }
+            None => Ok(Self::with_transport(config, ReqwestTransport::new())),
+        }
+    }
 
-        // Build HTTP client with timeout and headers
-        let http_client = HttpClient::builder()
-            .timeout(Duration::from_secs(config.timeout_seconds))
-            .default_headers(headers)
-            .build()
-            .expect("Failed to create HTTP client");
+    /// Create a new client that sends its requests through a custom transport
+    ///
+    /// The client starts with the default middleware: tracing (with the `tracing`
+    /// feature) and retries configured from `config.max_retries`.
+    pub fn with_transport<T: Transport + 'static>(config: Config, transport: T) -> Self {
+        let middleware: Vec<Arc<dyn Middleware>> = vec![
+            #[cfg(feature = "tracing")]
+            Arc::new(TracingMiddleware),
+            Arc::new(RetryMiddleware::new(config.max_retries)),
+        ];
 
         Self {
-            http_client,
+            transport: Arc::new(transport),
+            middleware,
+            metrics: None,
             config,
         }
     }
 
+    /// Append a middleware to the chain run around every request
+    ///
+    /// `before_request` hooks run in the order middleware was added and
+    /// `after_response` hooks in reverse order.
+    pub fn with_middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Remove all middleware, including the default retry and tracing middleware
+    pub fn without_middleware(mut self) -> Self {
+        self.middleware.clear();
+        self
+    }
+
+    /// Serve repeated reads from a response cache
+    ///
+    /// Appends the cache to the middleware chain; writes made through this client
+    /// (and its clones) invalidate the cached reads of the table they touch.
+    pub fn with_cache(self, cache: ResponseCache) -> Self {
+        self.with_middleware(cache)
+    }
+
+    /// Report requests, retries and records read/written to a metrics hook
+    ///
+    /// Appends a [`MetricsMiddleware`]; requests answered by middleware added
+    /// earlier (such as a cache) are not counted.
+    pub fn with_metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.metrics = Some(metrics.clone());
+        self.with_middleware(MetricsMiddleware::new(metrics))
+    }
+
     /// Get a base handle for the given base ID
     pub fn base(&self, base_id: &str) -> BaseHandle {
         BaseHandle {
@@ -75,37 +131,199 @@ impl Client {
     }
 
     /// Get user information (whoami)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "airtable.whoami", skip_all)
+    )]
     pub async fn whoami(&self) -> Result<UserInfo> {
         let url = format!("{}/meta/whoami", self.config.endpoint_url);
-        let response = self.http_client.get(&url).send().await?;
-
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
-        }
+        let response = self.send(HttpRequest::get(&url)).await?;
 
-        let user_info: UserInfo = response.json().await?;
+        let user_info: UserInfo = response.parse_json()?;
         Ok(user_info)
     }
 
-    /// List all accessible bases
+    /// Get an enterprise handle for the given enterprise account ID
+    pub fn enterprise(&self, account_id: &str) -> EnterpriseHandle {
+        EnterpriseHandle::new(self.clone(), account_id)
+    }
+
+    /// List all accessible bases (follows pagination to the last page)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "airtable.bases", skip_all)
+    )]
     pub async fn bases(&self) -> Result<Vec<BaseInfo>> {
+        let mut all_bases = Vec::new();
+        let mut bases = self.iterate_bases();
+        while let Some(page) = bases.next().await {
+            all_bases.extend(page?);
+        }
+        Ok(all_bases)
+    }
+
+    /// Create an iterator that fetches accessible bases one page at a time
+    pub fn iterate_bases(&self) -> BaseIterator {
+        BaseIterator {
+            client: self.clone(),
+            offset: None,
+            finished: false,
+        }
+    }
+
+    /// Get a single page of accessible bases, starting at the given offset
+    async fn bases_page(&self, offset: Option<&str>) -> Result<BasesResponse> {
+        let mut url = Url::parse(&format!("{}/meta/bases", self.config.endpoint_url))?;
+        if let Some(offset) = offset {
+            url.query_pairs_mut().append_pair("offset", offset);
+        }
+
+        let response = self.send(HttpRequest::get(url.as_str())).await?;
+
+        let bases_response: BasesResponse = response.parse_json()?;
+        Ok(bases_response)
+    }
+
+    /// Create a new base in a workspace with the given tables
+    ///
+    /// At least one table is required, and the first field of each table
+    /// becomes its primary field.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "airtable.create_base",
+            skip_all,
+            fields(workspace_id = %workspace_id)
+        )
+    )]
+    pub async fn create_base(
+        &self,
+        workspace_id: &str,
+        name: &str,
+        tables: Vec<TableDefinition>,
+    ) -> Result<CreatedBase> {
+        if tables.is_empty() {
+            return Err(Error::Api {
+                status: 400,
+                message: "Cannot create base without tables: at least one table is required"
+                    .to_string(),
+            });
+        }
+
+        let request_body = CreateBaseRequest {
+            name: name.to_string(),
+            workspace_id: workspace_id.to_string(),
+            tables,
+        };
+
         let url = format!("{}/meta/bases", self.config.endpoint_url);
-        let response = self.http_client.get(&url).send().await?;
+        let response = self
+            .send(HttpRequest::post(&url).json(serde_json::to_value(&request_body)?))
+            .await?;
+
+        let created: CreatedBase = response.parse_json()?;
+        Ok(created)
+    }
+
+    /// Send a request through the middleware chain with authentication, user agent
+    /// and timeout applied
+    ///
+    /// Non-2xx responses left by the middleware are turned into API errors.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "airtable.request",
+            skip_all,
+            fields(
+                method = %request.method,
+                path = %request_path(&request.url),
+                status,
+                latency_ms,
+                retries
+            )
+        )
+    )]
+    pub(crate) async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut request = request
+            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .header("User-Agent", "rsairtable/0.1.0");
+        request.timeout = Some(Duration::from_secs(self.config.timeout_seconds));
+
+        let mut ctx = RequestContext::new(request, &self.config.endpoint_url);
+        let result = loop {
+            // Run before hooks in order until one short-circuits
+            let mut ran = 0;
+            let mut short_circuit = None;
+            for middleware in &self.middleware {
+                ran += 1;
+                match middleware.before_request(&mut ctx).await {
+                    Ok(None) => {}
+                    Ok(Some(response)) => {
+                        short_circuit = Some(Ok(response));
+                        break;
+                    }
+                    Err(e) => {
+                        short_circuit = Some(Err(e));
+                        break;
+                    }
+                }
+            }
+
+            // A middleware that short-circuits does not see its own response
+            let (mut result, ran) = match short_circuit {
+                Some(result) => (result, ran - 1),
+                None => (self.transport.send(ctx.request.clone()).await, ran),
+            };
+
+            // Run after hooks in reverse order; the innermost retry request wins
+            let mut action = ResponseAction::Continue;
+            for middleware in self.middleware[..ran].iter().rev() {
+                let next = middleware.after_response(&ctx, &mut result).await;
+                if action == ResponseAction::Continue {
+                    action = next;
+                }
+            }
 
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
+            match action {
+                ResponseAction::Retry { delay, reason } => {
+                    tokio::time::sleep(delay).await;
+                    ctx.next_attempt(reason, delay);
+                }
+                ResponseAction::Continue => break result,
+            }
+        };
+
+        let response = result?;
+        if !response.is_success() {
+            return Err(Self::parse_error_response(&response));
         }
+        Ok(response)
+    }
 
-        let bases_response: BasesResponse = response.json().await?;
-        Ok(bases_response.bases)
+    /// Download a file such as an attachment from its (pre-signed) URL
+    ///
+    /// Bypasses the middleware chain and sends no `Authorization` header, so the
+    /// API key never reaches the attachment host.
+    pub(crate) async fn download(&self, url: &str) -> Result<Vec<u8>> {
+        let mut request = HttpRequest::get(url).header("User-Agent", "rsairtable/0.1.0");
+        request.timeout = Some(Duration::from_secs(self.config.timeout_seconds));
+
+        let response = self.transport.send(request).await?;
+        if !response.is_success() {
+            return Err(Error::api(
+                response.status,
+                format!("Failed to download {}", url),
+            ));
+        }
+        Ok(response.body)
     }
 
     /// Parse error response from API
-    async fn parse_error_response(&self, response: reqwest::Response) -> Error {
-        let status = response.status().as_u16();
+    pub(crate) fn parse_error_response(response: &HttpResponse) -> Error {
+        let status = response.status;
 
         // Try to parse structured error response
-        if let Ok(error_response) = response.json::<ErrorResponse>().await {
+        if let Ok(error_response) = response.parse_json::<ErrorResponse>() {
             Error::api(status, error_response.error.message)
         } else {
             // Fallback to generic error message
@@ -119,6 +337,42 @@ impl Client {
     }
 }
 
+/// Iterator for paginated base listing
+#[derive(Debug)]
+pub struct BaseIterator {
+    client: Client,
+    offset: Option<String>,
+    finished: bool,
+}
+
+impl BaseIterator {
+    /// Get the next page of bases
+    pub async fn next(&mut self) -> Option<Result<Vec<BaseInfo>>> {
+        if self.finished {
+            return None;
+        }
+
+        match self.client.bases_page(self.offset.as_deref()).await {
+            Ok(page) => {
+                self.offset = page.offset.filter(|offset| !offset.is_empty());
+                if self.offset.is_none() {
+                    self.finished = true;
+                }
+                Some(Ok(page.bases))
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Get the `fields` object of a batch update/upsert record (`{"id": ..., "fields": ...}`)
+fn record_fields(record: &serde_json::Value) -> &serde_json::Value {
+    record.get("fields").unwrap_or(record)
+}
+
 fn parse_records(json: &serde_json::Value) -> Vec<Record> {
     json.get("records")
         .and_then(|v| v.as_array())
@@ -130,6 +384,24 @@ fn parse_records(json: &serde_json::Value) -> Vec<Record> {
         .unwrap_or_default()
 }
 
+/// Body for a metadata update of a name and/or description
+fn name_and_description(name: Option<&str>, description: Option<&str>) -> serde_json::Value {
+    let mut body = serde_json::Map::new();
+    if let Some(name) = name {
+        body.insert("name".to_string(), name.into());
+    }
+    if let Some(description) = description {
+        body.insert("description".to_string(), description.into());
+    }
+    serde_json::Value::Object(body)
+}
+
+/// URL path without the query string (for logging)
+#[cfg(feature = "tracing")]
+fn request_path(url: &str) -> &str {
+    url.split('?').next().unwrap_or(url)
+}
+
 /// Handle for operations on a specific base
 #[derive(Debug, Clone)]
 pub struct BaseHandle {
@@ -143,29 +415,164 @@ impl BaseHandle {
         TableHandle {
             base: self.clone(),
             table_name: table_name.to_string(),
+            validator: None,
         }
     }
 
     /// Get base schema information
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "base.schema",
+            skip_all,
+            fields(base_id = %self.base_id)
+        )
+    )]
     pub async fn schema(&self) -> Result<BaseSchema> {
         let url = format!(
             "{}/meta/bases/{}/tables",
             self.client.config.endpoint_url, self.base_id
         );
-        let response = self.client.http_client.get(&url).send().await?;
+        let response = self.client.send(HttpRequest::get(&url)).await?;
 
-        if !response.status().is_success() {
-            return Err(self.client.parse_error_response(response).await);
-        }
+        let schema: BaseSchema = response.parse_json()?;
+        Ok(schema)
+    }
+
+    /// Create a table in the base
+    ///
+    /// The first field becomes the primary field. Link fields can only point at
+    /// tables that already exist.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "base.create_table",
+            skip_all,
+            fields(base_id = %self.base_id, table = %table.name)
+        )
+    )]
+    pub async fn create_table(&self, table: TableDefinition) -> Result<TableSchema> {
+        let url = format!(
+            "{}/meta/bases/{}/tables",
+            self.client.config.endpoint_url, self.base_id
+        );
+        let response = self
+            .client
+            .send(HttpRequest::post(&url).json(serde_json::to_value(&table)?))
+            .await?;
+
+        let schema: TableSchema = response.parse_json()?;
+        Ok(schema)
+    }
 
-        let schema: BaseSchema = response.json().await?;
+    /// Change a table's name and/or description
+    ///
+    /// `table` is a table ID or name. Fields left as `None` are unchanged.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "base.update_table",
+            skip_all,
+            fields(base_id = %self.base_id, table = %table)
+        )
+    )]
+    pub async fn update_table(
+        &self,
+        table: &str,
+        name: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<TableSchema> {
+        let url = format!(
+            "{}/meta/bases/{}/tables/{}",
+            self.client.config.endpoint_url,
+            self.base_id,
+            urlencoding::encode(table)
+        );
+        let response = self
+            .client
+            .send(HttpRequest::patch(&url).json(name_and_description(name, description)))
+            .await?;
+
+        let schema: TableSchema = response.parse_json()?;
         Ok(schema)
     }
 
+    /// Get base collaborators, invite links and interfaces
+    ///
+    /// **Note**: This uses the Enterprise metadata endpoint and requires a token
+    /// belonging to an Enterprise plan admin with the `workspacesAndBases:read` scope.
+    /// Other tokens receive an [`Error::Auth`] explaining the requirement, while an
+    /// unknown base is still reported as a 404 API error.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "base.collaborators",
+            skip_all,
+            fields(base_id = %self.base_id)
+        )
+    )]
+    pub async fn collaborators(&self) -> Result<BaseCollaborators> {
+        self.metadata(&["collaborators", "inviteLinks", "interfaces"])
+            .await
+    }
+
+    /// Get invite links (shares) for the base and its workspace
+    ///
+    /// **Note**: Like [`BaseHandle::collaborators`], this requires an Enterprise plan token.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "base.shares",
+            skip_all,
+            fields(base_id = %self.base_id)
+        )
+    )]
+    pub async fn shares(&self) -> Result<InviteLinks> {
+        let metadata = self.metadata(&["inviteLinks"]).await?;
+        Ok(metadata.invite_links)
+    }
+
+    /// Fetch base metadata with the requested `include` sections
+    async fn metadata(&self, include: &[&str]) -> Result<BaseCollaborators> {
+        let mut url = Url::parse(&format!(
+            "{}/meta/bases/{}",
+            self.client.config.endpoint_url, self.base_id
+        ))?;
+        {
+            let mut query_pairs = url.query_pairs_mut();
+            for section in include {
+                query_pairs.append_pair("include", section);
+            }
+        }
+
+        let response = self
+            .client
+            .send(HttpRequest::get(url.as_str()))
+            .await
+            .map_err(|e| match e {
+                Error::Api { status, message } if matches!(status, 401 | 403) => {
+                    Error::auth(format!(
+                        "base collaborators, shares and interfaces require an Enterprise plan token \
+                         with the workspacesAndBases:read scope (API error {}: {})",
+                        status, message
+                    ))
+                }
+                other => other,
+            })?;
+
+        let metadata: BaseCollaborators = response.parse_json()?;
+        Ok(metadata)
+    }
+
     /// Get base ID
     pub fn id(&self) -> &str {
         &self.base_id
     }
+
+    /// Client this handle sends requests through
+    pub(crate) fn client(&self) -> &Client {
+        &self.client
+    }
 }
 
 /// Handle for operations on a specific table
@@ -173,9 +580,50 @@ impl BaseHandle {
 pub struct TableHandle {
     base: BaseHandle,
     table_name: String,
+    /// Optional schema validator applied to writes before sending
+    validator: Option<Arc<WriteValidator>>,
 }
 
 impl TableHandle {
+    /// Validate record writes with the given validator before sending them
+    pub fn with_validator(mut self, validator: WriteValidator) -> Self {
+        self.validator = Some(Arc::new(validator));
+        self
+    }
+
+    /// Fetch this table's schema and validate record writes against it
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.validated",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name)
+        )
+    )]
+    pub async fn validated(self) -> Result<Self> {
+        let schema = self.schema().await?;
+        Ok(self.with_validator(WriteValidator::new(&schema)))
+    }
+
+    /// Record how many records an operation read or wrote (span field and metrics)
+    fn count_records(&self, access: RecordAccess, count: usize) {
+        record_field!("record_count", count);
+        if let Some(ref metrics) = self.base.client.metrics {
+            metrics.record_records(&self.base.base_id, &self.table_name, access, count);
+        }
+    }
+
+    /// Check record fields against the attached validator (if any)
+    fn check_writes<'a, I>(&self, records: I, typecast: bool) -> Result<()>
+    where
+        I: IntoIterator<Item = &'a serde_json::Value>,
+    {
+        match self.validator {
+            Some(ref validator) => validator.validate_batch(records, typecast),
+            None => Ok(()),
+        }
+    }
+
     /// Create a query builder for listing records
     pub fn list(&self) -> ListRecordsQuery {
         ListRecordsQuery {
@@ -210,7 +658,25 @@ impl TableHandle {
         &self.table_name
     }
 
+    /// Get the ID of the base this table belongs to
+    pub fn base_id(&self) -> &str {
+        &self.base.base_id
+    }
+
+    /// Handle of the base this table belongs to
+    pub(crate) fn base(&self) -> &BaseHandle {
+        &self.base
+    }
+
     /// Get all records from the table (convenience method)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.all",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name, record_count)
+        )
+    )]
     pub async fn all(&self) -> Result<Vec<Record>> {
         let mut all_records = Vec::new();
         let mut offset = None;
@@ -222,19 +688,30 @@ impl TableHandle {
             }
             offset = next_offset;
         }
+        record_field!("record_count", all_records.len());
         Ok(all_records)
     }
 
     /// Get a single record by ID
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.get",
+            skip_all,
+            fields(
+                base_id = %self.base.base_id,
+                table = %self.table_name,
+                record_count,
+                record_id = %record_id
+            )
+        )
+    )]
     pub async fn get(&self, record_id: &str) -> Result<Record> {
         let url = self.build_url(record_id);
-        let response = self.base.client.http_client.get(&url).send().await?;
+        let response = self.base.client.send(HttpRequest::get(&url)).await?;
 
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
-
-        let record: Record = response.json().await?;
+        let record: Record = response.parse_json()?;
+        self.count_records(RecordAccess::Read, 1);
         Ok(record)
     }
 
@@ -249,7 +726,17 @@ impl TableHandle {
     }
 
     /// Create a single record
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.create",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name, record_count)
+        )
+    )]
     pub async fn create(&self, fields: serde_json::Value) -> Result<Record> {
+        self.check_writes([&fields], false)?;
+
         let request_body = json!({
             "fields": fields,
             "typecast": false
@@ -259,26 +746,30 @@ impl TableHandle {
         let response = self
             .base
             .client
-            .http_client
-            .post(&url)
-            .json(&request_body)
-            .send()
+            .send(HttpRequest::post(&url).json(request_body))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
-
-        let record: Record = response.json().await?;
+        let record: Record = response.parse_json()?;
+        self.count_records(RecordAccess::Written, 1);
         Ok(record)
     }
 
     /// Create a single record with typecast option
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.create",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name, record_count)
+        )
+    )]
     pub async fn create_with_typecast(
         &self,
         fields: serde_json::Value,
         typecast: bool,
     ) -> Result<Record> {
+        self.check_writes([&fields], typecast)?;
+
         let request_body = json!({
             "fields": fields,
             "typecast": typecast
@@ -288,21 +779,23 @@ impl TableHandle {
         let response = self
             .base
             .client
-            .http_client
-            .post(&url)
-            .json(&request_body)
-            .send()
+            .send(HttpRequest::post(&url).json(request_body))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
-
-        let record: Record = response.json().await?;
+        let record: Record = response.parse_json()?;
+        self.count_records(RecordAccess::Written, 1);
         Ok(record)
     }
 
     /// Batch create multiple records
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.batch_create",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name, record_count)
+        )
+    )]
     pub async fn batch_create(&self, records_data: Vec<serde_json::Value>) -> Result<Vec<Record>> {
         if records_data.is_empty() {
             return Err(Error::Api {
@@ -318,6 +811,8 @@ impl TableHandle {
             });
         }
 
+        self.check_writes(&records_data, false)?;
+
         let records: Vec<serde_json::Value> = records_data
             .into_iter()
             .map(|fields| json!({"fields": fields}))
@@ -332,21 +827,23 @@ impl TableHandle {
         let response = self
             .base
             .client
-            .http_client
-            .post(&url)
-            .json(&request_body)
-            .send()
+            .send(HttpRequest::post(&url).json(request_body))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
-
-        let response_data: ListRecordsResponse = response.json().await?;
+        let response_data: ListRecordsResponse = response.parse_json()?;
+        self.count_records(RecordAccess::Written, response_data.records.len());
         Ok(response_data.records)
     }
 
     /// Batch create multiple records with options
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.batch_create",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name, record_count)
+        )
+    )]
     pub async fn batch_create_with_options(
         &self,
         records_data: Vec<serde_json::Value>,
@@ -367,6 +864,8 @@ impl TableHandle {
             });
         }
 
+        self.check_writes(&records_data, typecast)?;
+
         let records: Vec<serde_json::Value> = records_data
             .into_iter()
             .map(|fields| json!({"fields": fields}))
@@ -384,22 +883,31 @@ impl TableHandle {
         let response = self
             .base
             .client
-            .http_client
-            .post(&url)
-            .json(&request_body)
-            .send()
+            .send(HttpRequest::post(&url).json(request_body))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
-
-        let response_data: ListRecordsResponse = response.json().await?;
+        let response_data: ListRecordsResponse = response.parse_json()?;
+        self.count_records(RecordAccess::Written, response_data.records.len());
         Ok(response_data.records)
     }
 
     /// Update a single record
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.update",
+            skip_all,
+            fields(
+                base_id = %self.base.base_id,
+                table = %self.table_name,
+                record_count,
+                record_id = %record_id
+            )
+        )
+    )]
     pub async fn update(&self, record_id: &str, fields: serde_json::Value) -> Result<Record> {
+        self.check_writes([&fields], false)?;
+
         let request_body = json!({
             "fields": fields,
             "typecast": false
@@ -409,27 +917,36 @@ impl TableHandle {
         let response = self
             .base
             .client
-            .http_client
-            .patch(&url)
-            .json(&request_body)
-            .send()
+            .send(HttpRequest::patch(&url).json(request_body))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
-
-        let record: Record = response.json().await?;
+        let record: Record = response.parse_json()?;
+        self.count_records(RecordAccess::Written, 1);
         Ok(record)
     }
 
     /// Update a single record with typecast option
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.update",
+            skip_all,
+            fields(
+                base_id = %self.base.base_id,
+                table = %self.table_name,
+                record_count,
+                record_id = %record_id
+            )
+        )
+    )]
     pub async fn update_with_typecast(
         &self,
         record_id: &str,
         fields: serde_json::Value,
         typecast: bool,
     ) -> Result<Record> {
+        self.check_writes([&fields], typecast)?;
+
         let request_body = json!({
             "fields": fields,
             "typecast": typecast
@@ -439,21 +956,23 @@ impl TableHandle {
         let response = self
             .base
             .client
-            .http_client
-            .patch(&url)
-            .json(&request_body)
-            .send()
+            .send(HttpRequest::patch(&url).json(request_body))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
-
-        let record: Record = response.json().await?;
+        let record: Record = response.parse_json()?;
+        self.count_records(RecordAccess::Written, 1);
         Ok(record)
     }
 
     /// Batch update multiple records
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.batch_update",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name, record_count)
+        )
+    )]
     pub async fn batch_update(&self, records_data: Vec<serde_json::Value>) -> Result<Vec<Record>> {
         if records_data.is_empty() {
             return Err(Error::Api {
@@ -469,6 +988,8 @@ impl TableHandle {
             });
         }
 
+        self.check_writes(records_data.iter().map(record_fields), false)?;
+
         let request_body = json!({
             "records": records_data,
             "typecast": false
@@ -478,17 +999,11 @@ impl TableHandle {
         let response = self
             .base
             .client
-            .http_client
-            .patch(&url)
-            .json(&request_body)
-            .send()
+            .send(HttpRequest::patch(&url).json(request_body))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
-
-        let response_data: ListRecordsResponse = response.json().await?;
+        let response_data: ListRecordsResponse = response.parse_json()?;
+        self.count_records(RecordAccess::Written, response_data.records.len());
         Ok(response_data.records)
     }
 
@@ -498,6 +1013,26 @@ impl TableHandle {
         records_data: Vec<serde_json::Value>,
         fields_to_merge_on: &[&str],
     ) -> Result<Vec<Record>> {
+        let response = self
+            .batch_upsert_detailed(records_data, fields_to_merge_on)
+            .await?;
+        Ok(response.records)
+    }
+
+    /// Batch upsert, also reporting which records were created and which updated
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.batch_upsert",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name, record_count)
+        )
+    )]
+    pub async fn batch_upsert_detailed(
+        &self,
+        records_data: Vec<serde_json::Value>,
+        fields_to_merge_on: &[&str],
+    ) -> Result<UpsertRecordsResponse> {
         if records_data.is_empty() {
             return Err(Error::Api {
                 status: 400,
@@ -512,6 +1047,8 @@ impl TableHandle {
             });
         }
 
+        self.check_writes(records_data.iter().map(record_fields), false)?;
+
         let request_body = json!({
             "records": records_data,
             "performUpsert": {
@@ -524,33 +1061,45 @@ impl TableHandle {
         let response = self
             .base
             .client
-            .http_client
-            .patch(&url)
-            .json(&request_body)
-            .send()
+            .send(HttpRequest::patch(&url).json(request_body))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
-
-        let response_data: ListRecordsResponse = response.json().await?;
-        Ok(response_data.records)
+        let response_data: UpsertRecordsResponse = response.parse_json()?;
+        self.count_records(RecordAccess::Written, response_data.records.len());
+        Ok(response_data)
     }
 
     /// Delete a single record
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.delete",
+            skip_all,
+            fields(
+                base_id = %self.base.base_id,
+                table = %self.table_name,
+                record_count,
+                record_id = %record_id
+            )
+        )
+    )]
     pub async fn delete(&self, record_id: &str) -> Result<()> {
         let url = self.build_url(record_id);
-        let response = self.base.client.http_client.delete(&url).send().await?;
-
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
+        self.base.client.send(HttpRequest::delete(&url)).await?;
 
+        self.count_records(RecordAccess::Written, 1);
         Ok(())
     }
 
     /// Batch delete multiple records
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.batch_delete",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name, record_count)
+        )
+    )]
     pub async fn batch_delete(&self, record_ids: &[String]) -> Result<()> {
         if record_ids.is_empty() {
             return Err(Error::Api {
@@ -574,18 +1123,12 @@ impl TableHandle {
             }
         }
 
-        let response = self
-            .base
+        self.base
             .client
-            .http_client
-            .delete(url.as_str())
-            .send()
+            .send(HttpRequest::delete(url.as_str()))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
-
+        self.count_records(RecordAccess::Written, record_ids.len());
         Ok(())
     }
 
@@ -611,6 +1154,14 @@ impl TableHandle {
     }
 
     /// Get table schema information
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.schema",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name)
+        )
+    )]
     pub async fn schema(&self) -> Result<TableSchema> {
         // Get the base schema and extract this table's schema
         let base_schema = self.base.schema().await?;
@@ -618,7 +1169,7 @@ impl TableHandle {
         let table_schema = base_schema
             .tables
             .into_iter()
-            .find(|t| t.name == self.table_name)
+            .find(|t| t.name == self.table_name || t.id == self.table_name)
             .ok_or_else(|| Error::Api {
                 status: 404,
                 message: format!("Table '{}' not found in base", self.table_name),
@@ -637,6 +1188,14 @@ impl TableHandle {
     /// for field creation does not exist or is not accessible.
     ///
     /// **Recommendation**: Use the `schema()` method to inspect existing fields.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.create_field",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name)
+        )
+    )]
     pub async fn create_field(&self, field_definition: serde_json::Value) -> Result<FieldSchema> {
         let url = format!(
             "{}/meta/bases/{}/tables/{}/fields",
@@ -648,17 +1207,10 @@ impl TableHandle {
         let response = self
             .base
             .client
-            .http_client
-            .post(&url)
-            .json(&field_definition)
-            .send()
+            .send(HttpRequest::post(&url).json(field_definition))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
-
-        let field: FieldSchema = response.json().await?;
+        let field: FieldSchema = response.parse_json()?;
         Ok(field)
     }
 
@@ -672,6 +1224,14 @@ impl TableHandle {
     /// for field deletion does not exist or is not accessible.
     ///
     /// **Recommendation**: Use the `schema()` method to inspect existing fields.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.delete_field",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name)
+        )
+    )]
     pub async fn delete_field(&self, field_id: &str) -> Result<()> {
         let url = format!(
             "{}/meta/bases/{}/tables/{}/fields/{}",
@@ -681,16 +1241,56 @@ impl TableHandle {
             field_id
         );
 
-        let response = self.base.client.http_client.delete(&url).send().await?;
-
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
+        self.base.client.send(HttpRequest::delete(&url)).await?;
 
         Ok(())
     }
 
+    /// Change a field's name and/or description
+    ///
+    /// The metadata API cannot change a field's type or options. Fields left
+    /// as `None` are unchanged.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.update_field",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name)
+        )
+    )]
+    pub async fn update_field(
+        &self,
+        field_id: &str,
+        name: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<FieldSchema> {
+        let url = format!(
+            "{}/meta/bases/{}/tables/{}/fields/{}",
+            self.base.client.config.endpoint_url,
+            self.base.base_id,
+            urlencoding::encode(&self.table_name),
+            field_id
+        );
+
+        let response = self
+            .base
+            .client
+            .send(HttpRequest::patch(&url).json(name_and_description(name, description)))
+            .await?;
+
+        let field: FieldSchema = response.parse_json()?;
+        Ok(field)
+    }
+
     /// Get comments for a record
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.comments",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name, record_id = %record_id)
+        )
+    )]
     pub async fn comments(&self, record_id: &str) -> Result<Vec<Comment>> {
         let url = format!(
             "{}/{}/{}/{}/comments",
@@ -700,13 +1300,9 @@ impl TableHandle {
             record_id
         );
 
-        let response = self.base.client.http_client.get(&url).send().await?;
-
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
+        let response = self.base.client.send(HttpRequest::get(&url)).await?;
 
-        let comments_response: serde_json::Value = response.json().await?;
+        let comments_response: serde_json::Value = response.parse_json()?;
         let comments: Vec<Comment> = serde_json::from_value(
             comments_response
                 .get("comments")
@@ -717,6 +1313,14 @@ impl TableHandle {
     }
 
     /// Add a comment to a record
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.add_comment",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name, record_id = %record_id)
+        )
+    )]
     pub async fn add_comment(&self, record_id: &str, text: &str) -> Result<Comment> {
         let url = format!(
             "{}/{}/{}/{}/comments",
@@ -733,17 +1337,10 @@ impl TableHandle {
         let response = self
             .base
             .client
-            .http_client
-            .post(&url)
-            .json(&request_body)
-            .send()
+            .send(HttpRequest::post(&url).json(request_body))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.base.client.parse_error_response(response).await);
-        }
-
-        let comment: Comment = response.json().await?;
+        let comment: Comment = response.parse_json()?;
         Ok(comment)
     }
 
@@ -770,6 +1367,14 @@ impl TableHandle {
     ///
     /// **Important**: Attachment URLs must be publicly accessible (no auth required)
     /// and will expire after approximately 2 hours for security reasons.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.upload_attachment",
+            skip_all,
+            fields(base_id = %self.base.base_id, table = %self.table_name, record_id = %record_id)
+        )
+    )]
     pub async fn upload_attachment(
         &self,
         record_id: &str,
@@ -788,30 +1393,20 @@ impl TableHandle {
         );
 
         // Create multipart form data
-        let part = reqwest::multipart::Part::bytes(file_content.to_vec())
-            .file_name(filename.to_string())
-            .mime_str(content_type)
-            .map_err(|e| Error::Api {
-                status: 400,
-                message: format!("Invalid content type: {}", e),
-            })?;
-
-        let form = reqwest::multipart::Form::new().part("file", part);
+        let form = MultipartFile {
+            field_name: "file".to_string(),
+            filename: filename.to_string(),
+            content_type: content_type.to_string(),
+            bytes: file_content.to_vec(),
+        };
 
         let upload_response = self
             .base
             .client
-            .http_client
-            .post(&upload_url)
-            .multipart(form)
-            .send()
+            .send(HttpRequest::post(&upload_url).multipart(form))
             .await?;
 
-        if !upload_response.status().is_success() {
-            return Err(self.base.client.parse_error_response(upload_response).await);
-        }
-
-        let upload_result: serde_json::Value = upload_response.json().await?;
+        let upload_result: serde_json::Value = upload_response.parse_json()?;
         let attachment_data = upload_result.get("attachment").ok_or_else(|| Error::Api {
             status: 500,
             message: "No attachment data in upload response".to_string(),
@@ -823,8 +1418,8 @@ impl TableHandle {
             .fields
             .get(field_name)
             .and_then(|v| v.as_array())
-            .map(|arr| arr.clone())
-            .unwrap_or_else(Vec::new);
+            .cloned()
+            .unwrap_or_default();
 
         current_attachments.push(attachment_data.clone());
 
@@ -906,7 +1501,7 @@ impl ListRecordsQuery {
         self
     }
 
-    /// Set sort order
+    /// Set sort order, one `"Field"`, `"Field asc"` or `"Field desc"` per key
     pub fn sort(mut self, sort: Vec<String>) -> Self {
         self.sort = Some(sort);
         self
@@ -919,58 +1514,68 @@ impl ListRecordsQuery {
     }
 
     /// Execute the query and return all matching records
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.list",
+            skip_all,
+            fields(base_id = %self.table.base.base_id, table = %self.table.table_name, record_count)
+        )
+    )]
     pub async fn execute(self) -> Result<(Vec<Record>, Option<String>)> {
         let mut url = Url::parse(&self.table.build_url(""))?;
-        let mut query_pairs = url.query_pairs_mut();
+        // Scoped so the non-Send serializer is gone before the request is awaited
+        {
+            let mut query_pairs = url.query_pairs_mut();
 
-        if let Some(max_records) = self.max_records {
-            query_pairs.append_pair("maxRecords", &max_records.to_string());
-        }
+            if let Some(max_records) = self.max_records {
+                query_pairs.append_pair("maxRecords", &max_records.to_string());
+            }
 
-        if let Some(page_size) = self.page_size {
-            query_pairs.append_pair("pageSize", &page_size.to_string());
-        }
+            if let Some(page_size) = self.page_size {
+                query_pairs.append_pair("pageSize", &page_size.to_string());
+            }
 
-        if let Some(ref fields) = self.fields {
-            for field in fields {
-                query_pairs.append_pair("fields[]", field);
+            if let Some(ref fields) = self.fields {
+                for field in fields {
+                    query_pairs.append_pair("fields[]", field);
+                }
             }
-        }
 
-        if let Some(ref formula) = self.filter_by_formula {
-            query_pairs.append_pair("filterByFormula", formula);
-        }
+            if let Some(ref formula) = self.filter_by_formula {
+                query_pairs.append_pair("filterByFormula", formula);
+            }
 
-        if let Some(ref view) = self.view {
-            query_pairs.append_pair("view", view);
-        }
+            if let Some(ref view) = self.view {
+                query_pairs.append_pair("view", view);
+            }
 
-        if let Some(ref offset_val) = self.offset {
-            query_pairs.append_pair("offset", offset_val);
-        }
+            for (index, spec) in self.sort.iter().flatten().enumerate() {
+                let (field, direction) = parse_sort(spec);
+                query_pairs.append_pair(&format!("sort[{}][field]", index), field);
+                query_pairs.append_pair(&format!("sort[{}][direction]", index), direction);
+            }
 
-        drop(query_pairs);
+            if let Some(ref offset_val) = self.offset {
+                query_pairs.append_pair("offset", offset_val);
+            }
+        }
 
         // Make the request
         let response = self
             .table
             .base
             .client
-            .http_client
-            .get(url.as_str())
-            .send()
+            .send(HttpRequest::get(url.as_str()))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.table.base.client.parse_error_response(response).await);
-        }
-
-        let response_json: serde_json::Value = response.json().await?;
+        let response_json: serde_json::Value = response.parse_json()?;
         let records = parse_records(&response_json); // Use your existing record parsing logic
         let next_offset = response_json
             .get("offset")
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
+        self.table.count_records(RecordAccess::Read, records.len());
         Ok((records, next_offset))
     }
 
@@ -980,6 +1585,20 @@ impl ListRecordsQuery {
     }
 }
 
+/// Split a sort spec `"Field"`, `"Field asc"` or `"Field desc"` into field and direction
+fn parse_sort(spec: &str) -> (&str, &str) {
+    let spec = spec.trim();
+    match spec.rsplit_once(' ') {
+        Some((field, direction)) if direction.eq_ignore_ascii_case("desc") => {
+            (field.trim_end(), "desc")
+        }
+        Some((field, direction)) if direction.eq_ignore_ascii_case("asc") => {
+            (field.trim_end(), "asc")
+        }
+        _ => (spec, "asc"),
+    }
+}
+
 // Add urlencoding dependency to Cargo.toml when we test
 /// Query builder for getting the first record with optional filters
 #[derive(Debug, Clone)]
@@ -1010,6 +1629,14 @@ impl FirstRecordQuery {
     }
 
     /// Execute and return the first matching record
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.first",
+            skip_all,
+            fields(base_id = %self.table.base.base_id, table = %self.table.table_name)
+        )
+    )]
     pub async fn execute(self) -> Result<Record> {
         let mut query = self.table.list().max_records(1);
 
@@ -1071,6 +1698,12 @@ impl RecordIteratorBuilder {
         self
     }
 
+    /// Set sort order, one `"Field"`, `"Field asc"` or `"Field desc"` per key
+    pub fn sort(mut self, sort: Vec<String>) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
     /// Build the iterator
     pub async fn build(self) -> Result<RecordIterator> {
         Ok(RecordIterator {
@@ -1120,6 +1753,10 @@ impl RecordIterator {
             query = query.view(view);
         }
 
+        if let Some(ref sort) = self.sort {
+            query = query.sort(sort.clone());
+        }
+
         if let Some(ref offset) = self.offset {
             query = query.offset(Some(offset.clone()));
         }
@@ -1190,6 +1827,14 @@ impl SelectQueryBuilder {
     }
 
     /// Execute the query
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.select",
+            skip_all,
+            fields(base_id = %self.table.base.base_id, table = %self.table.table_name, record_count)
+        )
+    )]
     pub async fn execute(self) -> Result<Vec<Record>> {
         let mut query = self.table.list();
 
@@ -1205,11 +1850,20 @@ impl SelectQueryBuilder {
             query = query.view(&view);
         }
 
+        if let Some(sort) = self.sort {
+            query = query.sort(
+                sort.iter()
+                    .map(|(field, direction)| format!("{} {}", field, direction))
+                    .collect(),
+            );
+        }
+
         if let Some(max) = self.max_records {
             query = query.max_records(max);
         }
 
         let (records, _) = query.execute().await?;
+        record_field!("record_count", records.len());
         Ok(records)
     }
 }
@@ -1243,12 +1897,23 @@ impl CreateRecordQuery {
     }
 
     /// Execute the record creation
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.create",
+            skip_all,
+            fields(base_id = %self.table.base.base_id, table = %self.table.table_name, record_count)
+        )
+    )]
     pub async fn execute(self) -> Result<Record> {
         let fields = self.fields.ok_or_else(|| Error::Api {
             status: 400,
             message: "Missing fields: fields must be specified for record creation".to_string(),
         })?;
 
+        self.table
+            .check_writes([&fields], self.typecast.unwrap_or(false))?;
+
         let request_body = json!({
             "fields": fields,
             "typecast": self.typecast.unwrap_or(false)
@@ -1262,17 +1927,11 @@ impl CreateRecordQuery {
             .table
             .base
             .client
-            .http_client
-            .post(&url)
-            .json(&request_body)
-            .send()
+            .send(HttpRequest::post(&url).json(request_body))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.table.base.client.parse_error_response(response).await);
-        }
-
-        let record: Record = response.json().await?;
+        let record: Record = response.parse_json()?;
+        self.table.count_records(RecordAccess::Written, 1);
         Ok(record)
     }
 }
@@ -1307,12 +1966,28 @@ impl UpdateRecordQuery {
     }
 
     /// Execute the record update
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "table.update",
+            skip_all,
+            fields(
+                base_id = %self.table.base.base_id,
+                table = %self.table.table_name,
+                record_count,
+                record_id = %self.record_id
+            )
+        )
+    )]
     pub async fn execute(self) -> Result<Record> {
         let fields = self.fields.ok_or_else(|| Error::Api {
             status: 400,
             message: "Missing fields: fields must be specified for record update".to_string(),
         })?;
 
+        self.table
+            .check_writes([&fields], self.typecast.unwrap_or(false))?;
+
         let request_body = json!({
             "fields": fields,
             "typecast": self.typecast.unwrap_or(false)
@@ -1326,17 +2001,11 @@ impl UpdateRecordQuery {
             .table
             .base
             .client
-            .http_client
-            .patch(&url)
-            .json(&request_body)
-            .send()
+            .send(HttpRequest::patch(&url).json(request_body))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(self.table.base.client.parse_error_response(response).await);
-        }
-
-        let record: Record = response.json().await?;
+        let record: Record = response.parse_json()?;
+        self.table.count_records(RecordAccess::Written, 1);
         Ok(record)
     }
 }
@@ -1364,4 +2033,18 @@ mod tests {
         let table = client.base("appTestBase123").table("TestTable");
         assert_eq!(table.name(), "TestTable");
     }
+
+    #[test]
+    fn test_from_config_rejects_invalid_api_key() {
+        let config = Config::new("bad\nkey".to_string());
+        assert!(matches!(Client::from_config(config), Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn test_parse_sort() {
+        assert_eq!(parse_sort("Name"), ("Name", "asc"));
+        assert_eq!(parse_sort(" Filed On  DESC "), ("Filed On", "desc"));
+        assert_eq!(parse_sort("Case asc"), ("Case", "asc"));
+        assert_eq!(parse_sort("Description"), ("Description", "asc"));
+    }
 }
@@ -0,0 +1,396 @@
+//! Names for shell completion, served from a local schema cache
+//!
+//! [`SchemaCache`] keeps the base list and base schemas as JSON files so that
+//! shell completion can offer base, table, field and view names without calling
+//! the API. The CLI fills the cache with `rsairtable completions refresh` and
+//! whenever `bases` or `base schema` run; completion only ever reads it. Each
+//! profile has its own cache, as its token may reach different bases.
+//!
+//! [`dynamic_hook`] returns the shell code that the CLI appends to the static
+//! completion scripts generated by `clap_complete`. It asks
+//! `rsairtable completions names` for names after `base`, `table`, `--base`,
+//! `--table`, `-F`/`--field`, `-S`/`--sort` and `-u`/`--view`, and falls back
+//! to the static completions otherwise.
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::models::{BaseInfo, BaseSchema, TableSchema};
+use crate::output::write_atomic;
+use crate::resolve::unique_match;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// File holding the cached base list
+const BASES_FILE: &str = "bases.json";
+
+/// Cache directory used without a profile
+const NO_PROFILE_DIR: &str = "default";
+
+/// Kind of name to complete
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameKind {
+    /// Base IDs and names
+    Bases,
+    /// Table names of a base
+    Tables,
+    /// Field names of a table
+    Fields,
+    /// View names of a table
+    Views,
+}
+
+impl NameKind {
+    /// Accepted spellings, for help text
+    pub const NAMES: [&'static str; 4] = ["bases", "tables", "fields", "views"];
+}
+
+impl FromStr for NameKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "bases" => Ok(NameKind::Bases),
+            "tables" => Ok(NameKind::Tables),
+            "fields" => Ok(NameKind::Fields),
+            "views" => Ok(NameKind::Views),
+            other => Err(Error::config(format!(
+                "Unknown name kind '{}'. Expected one of: {}",
+                other,
+                Self::NAMES.join(", ")
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for NameKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            NameKind::Bases => "bases",
+            NameKind::Tables => "tables",
+            NameKind::Fields => "fields",
+            NameKind::Views => "views",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Base list and schemas cached on disk, one JSON file each
+#[derive(Debug, Clone)]
+pub struct SchemaCache {
+    dir: PathBuf,
+}
+
+impl SchemaCache {
+    /// Use `dir` for the cache files (created on the first write)
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `$XDG_CACHE_HOME/rsairtable/schemas/PROFILE`, or `~/.cache/rsairtable/schemas/PROFILE`
+    ///
+    /// Without a profile, `PROFILE` is `default`.
+    pub fn default_dir(profile: Option<&str>) -> Option<PathBuf> {
+        let cache_dir = env::var_os("XDG_CACHE_HOME")
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| crate::profile::home_dir().map(|home| home.join(".cache")))?;
+        Some(
+            cache_dir
+                .join("rsairtable")
+                .join("schemas")
+                .join(profile.unwrap_or(NO_PROFILE_DIR)),
+        )
+    }
+
+    /// The cache of `profile` in [`SchemaCache::default_dir`], if a home directory can be found
+    pub fn open_default(profile: Option<&str>) -> Option<Self> {
+        Self::default_dir(profile).map(Self::new)
+    }
+
+    /// Directory holding the cache files
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Replace the cached base list
+    pub fn store_bases(&self, bases: &[BaseInfo]) -> Result<()> {
+        self.write(BASES_FILE, &serde_json::to_vec_pretty(bases)?)
+    }
+
+    /// Replace the cached schema of `base_id`
+    pub fn store_schema(&self, base_id: &str, schema: &BaseSchema) -> Result<()> {
+        self.write(
+            &format!("{}.json", base_id),
+            &serde_json::to_vec_pretty(schema)?,
+        )
+    }
+
+    /// Cached base list (empty if nothing is cached)
+    pub fn bases(&self) -> Vec<BaseInfo> {
+        self.read(BASES_FILE).unwrap_or_default()
+    }
+
+    /// Cached schema of a base, by ID or unique base name
+    pub fn schema(&self, base: &str) -> Option<BaseSchema> {
+        let base_id = if self.dir.join(format!("{}.json", base)).is_file() {
+            base.to_string()
+        } else {
+            let bases = self.bases();
+            unique_match(&bases, base, |b| &b.name).ok()??.id.clone()
+        };
+        self.read(&format!("{}.json", base_id))
+    }
+
+    /// Cached ID of the table named `table` (exactly, or else uniquely ignoring case)
+    pub fn table_id(&self, base: &str, table: &str) -> Option<String> {
+        let schema = self.schema(base)?;
+        let found = unique_match(&schema.tables, table, |t| &t.name).ok()??;
+        Some(found.id.clone())
+    }
+
+    /// Cached names of `kind`
+    ///
+    /// Tables need `base` (an ID or name); fields and views also need `table`
+    /// (a name or ID). Missing cache entries give no names.
+    pub fn names(&self, kind: NameKind, base: Option<&str>, table: Option<&str>) -> Vec<String> {
+        if kind == NameKind::Bases {
+            return self
+                .bases()
+                .into_iter()
+                .flat_map(|base| [base.id, base.name])
+                .collect();
+        }
+        let Some(schema) = base.and_then(|base| self.schema(base)) else {
+            return Vec::new();
+        };
+        if kind == NameKind::Tables {
+            return schema.tables.into_iter().map(|t| t.name).collect();
+        }
+        let Some(table) = table.and_then(|table| find_table(&schema, table)) else {
+            return Vec::new();
+        };
+        match kind {
+            NameKind::Fields => table.fields.iter().map(|f| f.name.clone()).collect(),
+            _ => table.views.iter().map(|v| v.name.clone()).collect(),
+        }
+    }
+
+    /// Fetch the base list and every base schema into the cache
+    ///
+    /// With `only` non-empty, just those base IDs are fetched. Returns the
+    /// number of schemas stored.
+    pub async fn refresh(&self, client: &Client, only: &[String]) -> Result<usize> {
+        let bases = client.bases().await?;
+        self.store_bases(&bases)?;
+        let mut stored = 0;
+        for base in bases
+            .iter()
+            .filter(|b| only.is_empty() || only.contains(&b.id))
+        {
+            let schema = client.base(&base.id).schema().await?;
+            self.store_schema(&base.id, &schema)?;
+            stored += 1;
+        }
+        Ok(stored)
+    }
+
+    fn write(&self, file: &str, contents: &[u8]) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        write_atomic(self.dir.join(file), contents)
+    }
+
+    fn read<T: serde::de::DeserializeOwned>(&self, file: &str) -> Option<T> {
+        let contents = std::fs::read(self.dir.join(file)).ok()?;
+        serde_json::from_slice(&contents).ok()
+    }
+}
+
+/// A table by ID, or by a name matching exactly one table
+fn find_table<'a>(schema: &'a BaseSchema, name: &str) -> Option<&'a TableSchema> {
+    match schema.tables.iter().find(|t| t.id == name) {
+        Some(table) => Some(table),
+        None => unique_match(&schema.tables, name, |t| &t.name).ok()?,
+    }
+}
+
+/// Shell code adding cached names to the static completions of `shell`
+///
+/// Supported shells are `bash`, `zsh` and `fish`.
+pub fn dynamic_hook(shell: &str) -> Option<&'static str> {
+    match shell {
+        "bash" => Some(BASH_HOOK),
+        "zsh" => Some(ZSH_HOOK),
+        "fish" => Some(FISH_HOOK),
+        _ => None,
+    }
+}
+
+const BASH_HOOK: &str = r#"
+# Names of bases, tables, fields and views from the local schema cache
+_rsairtable_names() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}"
+    local kind="" base="" table="" i
+    case "$prev" in
+        base|--base) kind=bases ;;
+        table|--table) kind=tables ;;
+        -F|--field|-S|--sort|-x|--expand) kind=fields ;;
+        -u|--view) kind=views ;;
+    esac
+    if [[ -n "$kind" ]]; then
+        for ((i = 1; i < COMP_CWORD - 1; i++)); do
+            case "${COMP_WORDS[i]}" in
+                base|--base) base="${COMP_WORDS[i+1]}" ;;
+                table|--table) table="${COMP_WORDS[i+1]}" ;;
+            esac
+        done
+        local IFS=$'\n'
+        COMPREPLY=($(compgen -W "$(rsairtable completions names "$kind" --base "$base" --table "$table" 2>/dev/null)" -- "$cur"))
+        COMPREPLY=("${COMPREPLY[@]// /\\ }")
+        [[ ${#COMPREPLY[@]} -gt 0 ]] && return 0
+    fi
+    _rsairtable "$@"
+}
+complete -F _rsairtable_names -o bashdefault -o default rsairtable
+"#;
+
+const ZSH_HOOK: &str = r#"
+# Names of bases, tables, fields and views from the local schema cache
+_rsairtable_names() {
+    local kind="" base="" table="" i
+    case "${words[CURRENT-1]}" in
+        base|--base) kind=bases ;;
+        table|--table) kind=tables ;;
+        -F|--field|-S|--sort|-x|--expand) kind=fields ;;
+        -u|--view) kind=views ;;
+    esac
+    if [[ -n "$kind" ]]; then
+        for ((i = 2; i < CURRENT - 1; i++)); do
+            case "${words[i]}" in
+                base|--base) base="${words[i+1]}" ;;
+                table|--table) table="${words[i+1]}" ;;
+            esac
+        done
+        local -a names
+        names=("${(@f)$(rsairtable completions names "$kind" --base "$base" --table "$table" 2>/dev/null)}")
+        if [[ -n "${names[1]}" ]]; then
+            compadd -a names
+            return
+        fi
+    fi
+    _rsairtable "$@"
+}
+compdef _rsairtable_names rsairtable
+"#;
+
+const FISH_HOOK: &str = r#"
+# Names of bases, tables, fields and views from the local schema cache
+function __rsairtable_names
+    set -l words (commandline -opc)
+    set -l base ''
+    set -l table ''
+    for i in (seq 2 (math (count $words) - 1))
+        switch $words[$i]
+            case base --base
+                set base $words[(math $i + 1)]
+            case table --table
+                set table $words[(math $i + 1)]
+        end
+    end
+    rsairtable completions names $argv[1] --base "$base" --table "$table" 2>/dev/null
+end
+complete -c rsairtable -n '__fish_prev_arg_in base --base' -f -a '(__rsairtable_names bases)'
+complete -c rsairtable -n '__fish_prev_arg_in table --table' -f -a '(__rsairtable_names tables)'
+complete -c rsairtable -n '__fish_prev_arg_in -F --field -S --sort -x --expand' -f -a '(__rsairtable_names fields)'
+complete -c rsairtable -n '__fish_prev_arg_in -u --view' -f -a '(__rsairtable_names views)'
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_from_cached_schema() {
+        let dir = std::env::temp_dir().join(format!("rsairtable-names-{}", std::process::id()));
+        let cache = SchemaCache::new(&dir);
+        assert!(cache.names(NameKind::Bases, None, None).is_empty());
+
+        cache
+            .store_bases(&[BaseInfo {
+                id: "appAAAAAAAAAAAAAA".to_string(),
+                name: "Law Firm".to_string(),
+                permission_level: "create".to_string(),
+            }])
+            .unwrap();
+        let schema: BaseSchema = serde_json::from_value(serde_json::json!({
+            "tables": [{
+                "id": "tblAAAAAAAAAAAAAA",
+                "name": "Cases",
+                "primaryFieldId": "fld1",
+                "fields": [
+                    { "id": "fld1", "name": "Case Number", "type": "singleLineText" },
+                    { "id": "fld2", "name": "Status", "type": "singleSelect" }
+                ],
+                "views": [{ "id": "viw1", "name": "Open cases", "type": "grid" }]
+            }]
+        }))
+        .unwrap();
+        cache.store_schema("appAAAAAAAAAAAAAA", &schema).unwrap();
+
+        assert_eq!(
+            cache.names(NameKind::Bases, None, None),
+            ["appAAAAAAAAAAAAAA", "Law Firm"]
+        );
+        assert_eq!(
+            cache.names(NameKind::Tables, Some("law firm"), None),
+            ["Cases"]
+        );
+        assert_eq!(
+            cache.names(NameKind::Fields, Some("appAAAAAAAAAAAAAA"), Some("cases")),
+            ["Case Number", "Status"]
+        );
+        assert_eq!(
+            cache.names(NameKind::Views, Some("Law Firm"), Some("tblAAAAAAAAAAAAAA")),
+            ["Open cases"]
+        );
+        assert!(cache
+            .names(NameKind::Fields, Some("Law Firm"), None)
+            .is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_ambiguous_table_names_give_no_names() {
+        let dir = std::env::temp_dir().join(format!("rsairtable-ambiguous-{}", std::process::id()));
+        let cache = SchemaCache::new(&dir);
+        let table = |id: &str, name: &str| {
+            serde_json::json!({
+                "id": id,
+                "name": name,
+                "primaryFieldId": "fld1",
+                "fields": [{ "id": "fld1", "name": id, "type": "singleLineText" }],
+                "views": []
+            })
+        };
+        let schema: BaseSchema = serde_json::from_value(serde_json::json!({
+            "tables": [
+                table("tblAAAAAAAAAAAAAA", "Cases"),
+                table("tblBBBBBBBBBBBBBB", "CASES"),
+            ]
+        }))
+        .unwrap();
+        cache.store_schema("appAAAAAAAAAAAAAA", &schema).unwrap();
+
+        let fields =
+            |table: &str| cache.names(NameKind::Fields, Some("appAAAAAAAAAAAAAA"), Some(table));
+        assert_eq!(fields("CASES"), ["tblBBBBBBBBBBBBBB"]);
+        assert!(fields("cases").is_empty());
+        assert_eq!(
+            cache.table_id("appAAAAAAAAAAAAAA", "Cases").as_deref(),
+            Some("tblAAAAAAAAAAAAAA")
+        );
+        assert_eq!(cache.table_id("appAAAAAAAAAAAAAA", "cases"), None);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -7,11 +7,17 @@
 //!
 //! This matches the configuration strategy used by pyairtable.
 
+use crate::cassette::CassetteMode;
 use crate::error::{Error, Result};
+use std::collections::BTreeMap;
 use std::env;
+use std::fmt;
+use std::path::PathBuf;
 
 /// Configuration for RSAirtable client
-#[derive(Debug, Clone)]
+///
+/// The `Debug` output redacts the API key.
+#[derive(Clone)]
 pub struct Config {
     /// Personal Access Token or API Key for Airtable
     pub api_key: String,
@@ -23,6 +29,36 @@ pub struct Config {
     pub max_retries: u32,
     /// Enable verbose logging
     pub verbose: bool,
+    /// Cassette file for recording or replaying HTTP interactions
+    pub cassette_path: Option<PathBuf>,
+    /// Whether the cassette records or replays
+    pub cassette_mode: CassetteMode,
+    /// Base used when none is given (set by profiles)
+    pub default_base: Option<String>,
+    /// Table aliases: alias → table name or ID in the default base
+    pub table_aliases: BTreeMap<String, String>,
+    /// Aliases: alias → `base` or `base/table` (IDs or names), see [`Client::resolve`](crate::Client::resolve)
+    pub aliases: BTreeMap<String, String>,
+    /// Profile the configuration was loaded from, if any
+    pub profile: Option<String>,
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("api_key", &crate::transport::REDACTED)
+            .field("endpoint_url", &self.endpoint_url)
+            .field("timeout_seconds", &self.timeout_seconds)
+            .field("max_retries", &self.max_retries)
+            .field("verbose", &self.verbose)
+            .field("cassette_path", &self.cassette_path)
+            .field("cassette_mode", &self.cassette_mode)
+            .field("default_base", &self.default_base)
+            .field("table_aliases", &self.table_aliases)
+            .field("aliases", &self.aliases)
+            .field("profile", &self.profile)
+            .finish()
+    }
 }
 
 impl Config {
@@ -34,9 +70,38 @@ impl Config {
             timeout_seconds: 30,
             max_retries: 3,
             verbose: false,
+            cassette_path: None,
+            cassette_mode: CassetteMode::Replay,
+            default_base: None,
+            table_aliases: BTreeMap::new(),
+            aliases: BTreeMap::new(),
+            profile: None,
         }
     }
 
+    /// Load a named profile from the config file
+    ///
+    /// `None` selects the file's `default_profile`. The file's shared aliases
+    /// are included, overridden by the profile's own. See [`crate::profile`]
+    /// for the file format and location.
+    pub fn from_profile(name: Option<&str>) -> Result<Self> {
+        let path = crate::profile::default_path()
+            .ok_or_else(|| Error::config("Cannot locate the config file: HOME is not set"))?;
+        let file = crate::profile::ProfileFile::load(&path)?;
+        if file.profiles.is_empty() {
+            return Err(Error::config(format!("No profiles defined in {}", path.display())));
+        }
+        let (name, profile) = file.profile(name)?;
+        let mut config = profile
+            .to_config()
+            .map_err(|e| Error::config(format!("Profile '{}': {}", name, crate::profile::message(e))))?;
+        for (alias, target) in &file.aliases {
+            config.aliases.entry(alias.clone()).or_insert_with(|| target.clone());
+        }
+        config.profile = Some(name.to_string());
+        Ok(config)
+    }
+
     /// Load configuration from environment variables and .env files
     /// 
     /// This follows the pyairtable pattern of checking multiple possible env var names:
@@ -94,6 +159,15 @@ impl Config {
             config.verbose = verbose.to_lowercase() == "true" || verbose == "1";
         }
 
+        if let Ok(path) = env::var("AIRTABLE_CASSETTE") {
+            config.cassette_path = Some(PathBuf::from(path));
+        }
+
+        // A typo here would silently replay instead of recording, so it is an error
+        if let Ok(mode) = env::var("AIRTABLE_CASSETTE_MODE") {
+            config.cassette_mode = mode.parse::<CassetteMode>()?;
+        }
+
         Ok(config)
     }
 
@@ -175,4 +249,23 @@ impl Config {
         self.max_retries = max_retries;
         self
     }
+
+    /// Set the base used when none is given
+    pub fn with_default_base<S: Into<String>>(mut self, base_id: S) -> Self {
+        self.default_base = Some(base_id.into());
+        self
+    }
+
+    /// Add an alias for a base (`appXXX` or a base name) or a table (`appXXX/tblYYY`)
+    pub fn with_alias<A: Into<String>, T: Into<String>>(mut self, alias: A, target: T) -> Self {
+        self.aliases.insert(alias.into(), target.into());
+        self
+    }
+
+    /// Record HTTP interactions to, or replay them from, a cassette file
+    pub fn with_cassette<P: Into<PathBuf>>(mut self, path: P, mode: CassetteMode) -> Self {
+        self.cassette_path = Some(path.into());
+        self.cassette_mode = mode;
+        self
+    }
 }
\ No newline at end of file
@@ -0,0 +1,146 @@
+//! Minimal CSV reading and writing
+//!
+//! Implements RFC 4180: fields are separated by a delimiter, quoted fields may
+//! contain delimiters, newlines and doubled quotes, and rows end with `\n` or
+//! `\r\n`. A leading UTF-8 byte order mark is ignored.
+
+use crate::error::{Error, Result};
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+/// A parsed CSV row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRow {
+    /// 1-based line number the row starts on
+    pub line: usize,
+    /// Field values
+    pub fields: Vec<String>,
+}
+
+/// Parse CSV text into rows, skipping blank lines
+pub fn parse(input: &str, delimiter: char) -> Result<Vec<CsvRow>> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut rows = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut quoted = false;
+    let mut chars = input.chars().peekable();
+
+    let mut finish_row = |fields: &mut Vec<String>, row_line: usize| {
+        if !(fields.len() == 1 && fields[0].is_empty()) {
+            rows.push(CsvRow {
+                line: row_line,
+                fields: std::mem::take(fields),
+            });
+        }
+        fields.clear();
+    };
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => quoted = true,
+            c if c == delimiter => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                finish_row(&mut fields, row_line);
+                line += 1;
+                row_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(Error::Other(format!(
+            "Unterminated quoted field starting on line {}",
+            row_line
+        )));
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        finish_row(&mut fields, row_line);
+    }
+    Ok(rows)
+}
+
+/// Quote a field if it contains the delimiter, quotes or line breaks
+pub fn escape(field: &str, delimiter: char) -> Cow<'_, str> {
+    if field.contains([delimiter, '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Write one row terminated by `\n`
+pub fn write_row<W, S>(writer: &mut W, fields: &[S], delimiter: char) -> io::Result<()>
+where
+    W: Write,
+    S: AsRef<str>,
+{
+    let mut separator = [0; 4];
+    let separator = delimiter.encode_utf8(&mut separator);
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            writer.write_all(separator.as_bytes())?;
+        }
+        writer.write_all(escape(field.as_ref(), delimiter).as_bytes())?;
+    }
+    writer.write_all(b"\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quotes_and_line_numbers() {
+        let rows = parse(
+            "\u{feff}Name,Notes\r\nAda,\"Said \"\"hi\"\"\"\n\nBob,\"two\nlines\"\nCy,",
+            ',',
+        )
+        .unwrap();
+
+        let fields: Vec<&[String]> = rows.iter().map(|r| r.fields.as_slice()).collect();
+        assert_eq!(fields[0], ["Name", "Notes"]);
+        assert_eq!(fields[1], ["Ada", "Said \"hi\""]);
+        assert_eq!(fields[2], ["Bob", "two\nlines"]);
+        assert_eq!(fields[3], ["Cy", ""]);
+        assert_eq!(
+            rows.iter().map(|r| r.line).collect::<Vec<_>>(),
+            [1, 2, 4, 6]
+        );
+        assert!(parse("a,\"open", ',').is_err());
+    }
+
+    #[test]
+    fn test_write_row_round_trips() {
+        let mut out = Vec::new();
+        write_row(&mut out, &["plain", "a,b", "say \"x\""], ',').unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, "plain,\"a,b\",\"say \"\"x\"\"\"\n");
+        assert_eq!(
+            parse(&text, ',').unwrap()[0].fields,
+            ["plain", "a,b", "say \"x\""]
+        );
+    }
+}
@@ -0,0 +1,366 @@
+//! Enterprise account operations for RSAirtable
+//!
+//! This module wraps Airtable's Enterprise endpoints: account information,
+//! audit log events, user lookup, admin access grants and user claims.
+//! All of them require a token belonging to an Enterprise plan admin.
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::models::*;
+use crate::transport::HttpRequest;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::json;
+use url::Url;
+
+/// Handle for operations on a specific enterprise account
+#[derive(Debug, Clone)]
+pub struct EnterpriseHandle {
+    client: Client,
+    account_id: String,
+}
+
+impl EnterpriseHandle {
+    /// Create a new enterprise handle (use [`Client::enterprise`])
+    pub(crate) fn new(client: Client, account_id: &str) -> Self {
+        Self {
+            client,
+            account_id: account_id.to_string(),
+        }
+    }
+
+    /// Get enterprise account ID
+    pub fn id(&self) -> &str {
+        &self.account_id
+    }
+
+    /// Build URL for enterprise account operations
+    fn build_url(&self, path: &str) -> String {
+        if path.is_empty() {
+            format!(
+                "{}/meta/enterpriseAccounts/{}",
+                self.client.config.endpoint_url, self.account_id
+            )
+        } else {
+            format!(
+                "{}/meta/enterpriseAccounts/{}/{}",
+                self.client.config.endpoint_url, self.account_id, path
+            )
+        }
+    }
+
+    /// Get enterprise account information
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "enterprise.info", skip_all, fields(account_id = %self.account_id))
+    )]
+    pub async fn info(&self) -> Result<EnterpriseInfo> {
+        let url = self.build_url("");
+        let response = self.client.send(HttpRequest::get(&url)).await?;
+
+        let info: EnterpriseInfo = response.parse_json()?;
+        Ok(info)
+    }
+
+    /// Create a query builder for audit log events
+    pub fn audit_log(&self) -> AuditLogQuery {
+        AuditLogQuery {
+            enterprise: self.clone(),
+            start_time: None,
+            end_time: None,
+            event_types: Vec::new(),
+            originating_user_id: None,
+            model_id: None,
+            page_size: None,
+            sort_order: None,
+            next: None,
+        }
+    }
+
+    /// Look up enterprise users by ID and/or email address
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "enterprise.users", skip_all, fields(account_id = %self.account_id))
+    )]
+    pub async fn users(&self, user_ids: &[&str], emails: &[&str]) -> Result<Vec<EnterpriseUser>> {
+        if user_ids.is_empty() && emails.is_empty() {
+            return Err(Error::config(
+                "At least one user ID or email is required to look up enterprise users",
+            ));
+        }
+
+        let mut url = Url::parse(&self.build_url("users"))?;
+        {
+            let mut query_pairs = url.query_pairs_mut();
+            for user_id in user_ids {
+                query_pairs.append_pair("id", user_id);
+            }
+            for email in emails {
+                query_pairs.append_pair("email", email);
+            }
+        }
+
+        let response = self.client.send(HttpRequest::get(url.as_str())).await?;
+
+        let users_response: EnterpriseUsersResponse = response.parse_json()?;
+        Ok(users_response.users)
+    }
+
+    /// Get a single enterprise user by ID or email address
+    pub async fn user(&self, id_or_email: &str) -> Result<EnterpriseUser> {
+        let users = if is_email(id_or_email) {
+            self.users(&[], &[id_or_email]).await?
+        } else {
+            self.users(&[id_or_email], &[]).await?
+        };
+
+        users.into_iter().next().ok_or_else(|| Error::Api {
+            status: 404,
+            message: format!("Enterprise user '{}' not found", id_or_email),
+        })
+    }
+
+    /// Grant enterprise admin access to users (by ID or email address)
+    pub async fn grant_admin(&self, users: &[&str]) -> Result<ManageUsersResponse> {
+        let users: Vec<serde_json::Value> = users.iter().map(|user| user_ref(user)).collect();
+        self.manage_users("users/grantAdminAccess", json!({ "users": users }))
+            .await
+    }
+
+    /// Revoke enterprise admin access from users (by ID or email address)
+    pub async fn revoke_admin(&self, users: &[&str]) -> Result<ManageUsersResponse> {
+        let users: Vec<serde_json::Value> = users.iter().map(|user| user_ref(user)).collect();
+        self.manage_users("users/revokeAdminAccess", json!({ "users": users }))
+            .await
+    }
+
+    /// Claim users into (or release them from) enterprise management
+    ///
+    /// Each entry pairs a user ID or email address with the desired membership state.
+    pub async fn claim_users(&self, claims: &[(&str, ClaimState)]) -> Result<ManageUsersResponse> {
+        let users: Vec<serde_json::Value> = claims
+            .iter()
+            .map(|(user, state)| {
+                let mut user = user_ref(user);
+                user["state"] = json!(state);
+                user
+            })
+            .collect();
+        self.manage_users("claim/users", json!({ "users": users }))
+            .await
+    }
+
+    /// Send a user management request and parse the per-user results
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "enterprise.manage_users", skip_all, fields(account_id = %self.account_id))
+    )]
+    async fn manage_users(
+        &self,
+        path: &str,
+        request_body: serde_json::Value,
+    ) -> Result<ManageUsersResponse> {
+        if request_body["users"]
+            .as_array()
+            .is_none_or(|users| users.is_empty())
+        {
+            return Err(Error::Api {
+                status: 400,
+                message: "Cannot manage empty user list: at least one user is required".to_string(),
+            });
+        }
+
+        let url = self.build_url(path);
+        let response = self
+            .client
+            .send(HttpRequest::post(&url).json(request_body))
+            .await?;
+
+        let manage_response: ManageUsersResponse = response.parse_json()?;
+        Ok(manage_response)
+    }
+}
+
+/// Whether a user reference is an email address rather than a user ID
+fn is_email(user: &str) -> bool {
+    user.contains('@')
+}
+
+/// Build a `{"id": ...}` or `{"email": ...}` user reference
+fn user_ref(user: &str) -> serde_json::Value {
+    if is_email(user) {
+        json!({ "email": user })
+    } else {
+        json!({ "id": user })
+    }
+}
+
+/// Query builder for enterprise audit log events
+#[derive(Debug, Clone)]
+pub struct AuditLogQuery {
+    enterprise: EnterpriseHandle,
+    start_time: Option<DateTime<Utc>>,
+    end_time: Option<DateTime<Utc>>,
+    event_types: Vec<String>,
+    originating_user_id: Option<String>,
+    model_id: Option<String>,
+    page_size: Option<u32>,
+    sort_order: Option<String>,
+    next: Option<String>,
+}
+
+impl AuditLogQuery {
+    /// Only return events at or after this time
+    pub fn start_time(mut self, start_time: DateTime<Utc>) -> Self {
+        self.start_time = Some(start_time);
+        self
+    }
+
+    /// Only return events before this time
+    pub fn end_time(mut self, end_time: DateTime<Utc>) -> Self {
+        self.end_time = Some(end_time);
+        self
+    }
+
+    /// Only return events of this type (may be called multiple times)
+    pub fn event_type<S: Into<String>>(mut self, event_type: S) -> Self {
+        self.event_types.push(event_type.into());
+        self
+    }
+
+    /// Only return events performed by this user
+    pub fn originating_user_id<S: Into<String>>(mut self, user_id: S) -> Self {
+        self.originating_user_id = Some(user_id.into());
+        self
+    }
+
+    /// Only return events affecting this model (base, workspace, etc.)
+    pub fn model_id<S: Into<String>>(mut self, model_id: S) -> Self {
+        self.model_id = Some(model_id.into());
+        self
+    }
+
+    /// Set page size for pagination (Airtable allows up to 1000)
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Set sort order ("ascending" or "descending")
+    pub fn sort_order<S: Into<String>>(mut self, sort_order: S) -> Self {
+        self.sort_order = Some(sort_order.into());
+        self
+    }
+
+    /// Continue from the given pagination cursor
+    pub fn next(mut self, cursor: Option<String>) -> Self {
+        self.next = cursor;
+        self
+    }
+
+    /// Execute the query and return one page of events with the next cursor
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "enterprise.audit_log",
+            skip_all,
+            fields(account_id = %self.enterprise.account_id, record_count)
+        )
+    )]
+    pub async fn execute(&self) -> Result<(Vec<AuditLogEvent>, Option<String>)> {
+        let mut url = Url::parse(&self.enterprise.build_url("auditLogEvents"))?;
+        let mut query_pairs = url.query_pairs_mut();
+
+        if let Some(ref start_time) = self.start_time {
+            query_pairs.append_pair(
+                "startTime",
+                &start_time.to_rfc3339_opts(SecondsFormat::Millis, true),
+            );
+        }
+
+        if let Some(ref end_time) = self.end_time {
+            query_pairs.append_pair(
+                "endTime",
+                &end_time.to_rfc3339_opts(SecondsFormat::Millis, true),
+            );
+        }
+
+        for event_type in &self.event_types {
+            query_pairs.append_pair("eventType", event_type);
+        }
+
+        if let Some(ref user_id) = self.originating_user_id {
+            query_pairs.append_pair("originatingUserId", user_id);
+        }
+
+        if let Some(ref model_id) = self.model_id {
+            query_pairs.append_pair("modelId", model_id);
+        }
+
+        if let Some(page_size) = self.page_size {
+            query_pairs.append_pair("pageSize", &page_size.to_string());
+        }
+
+        if let Some(ref sort_order) = self.sort_order {
+            query_pairs.append_pair("sortOrder", sort_order);
+        }
+
+        if let Some(ref next) = self.next {
+            query_pairs.append_pair("next", next);
+        }
+
+        drop(query_pairs);
+
+        let client = &self.enterprise.client;
+        let response = client.send(HttpRequest::get(url.as_str())).await?;
+
+        let audit_log: AuditLogResponse = response.parse_json()?;
+        let next = audit_log
+            .pagination
+            .next
+            .filter(|cursor| !cursor.is_empty());
+        record_field!("record_count", audit_log.events.len());
+        Ok((audit_log.events, next))
+    }
+
+    /// Get all matching events by following pagination cursors
+    pub async fn all(self) -> Result<Vec<AuditLogEvent>> {
+        let mut all_events = Vec::new();
+        let mut query = self;
+        loop {
+            let (events, next) = query.execute().await?;
+            let page_was_empty = events.is_empty();
+            all_events.extend(events);
+            // Airtable keeps handing out cursors at the end of the log; stop on an empty page
+            if next.is_none() || page_was_empty {
+                break;
+            }
+            query = query.next(next);
+        }
+        Ok(all_events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_ref_distinguishes_ids_and_emails() {
+        assert_eq!(user_ref("usrABC123"), json!({ "id": "usrABC123" }));
+        assert_eq!(
+            user_ref("jane@example.com"),
+            json!({ "email": "jane@example.com" })
+        );
+    }
+
+    #[test]
+    fn test_enterprise_handle() {
+        let client = Client::new("test_key".to_string());
+        let enterprise = client.enterprise("entTestAccount1");
+        assert_eq!(enterprise.id(), "entTestAccount1");
+        assert_eq!(
+            enterprise.build_url("auditLogEvents"),
+            "https://api.airtable.com/v0/meta/enterpriseAccounts/entTestAccount1/auditLogEvents"
+        );
+    }
+}
@@ -4,6 +4,7 @@
 //! The error types are designed to provide detailed information about what went wrong
 //! and are compatible with the error patterns used in pyairtable.
 
+use crate::validation::ValidationIssue;
 use thiserror::Error;
 
 /// Result type alias for RSAirtable operations
@@ -60,6 +61,14 @@ pub enum Error {
     #[error("Authentication failed: {message}")]
     Auth { message: String },
 
+    /// Record writes failed schema validation before being sent
+    #[error("Write validation failed with {} problem(s): {}", issues.len(), format_issues(issues))]
+    Validation { issues: Vec<ValidationIssue> },
+
+    /// Local SQLite mirror error (a `rusqlite::Error` with the `sync` feature)
+    #[error("Database error: {0}")]
+    Database(#[source] Box<dyn std::error::Error + Send + Sync>),
+
     /// Generic error for other cases
     #[error("Error: {0}")]
     Other(String),
@@ -109,3 +118,18 @@ impl Error {
         }
     }
 }
+
+/// Summarize validation issues for display, listing the first few
+fn format_issues(issues: &[ValidationIssue]) -> String {
+    const SHOWN: usize = 10;
+    let mut summary = issues
+        .iter()
+        .take(SHOWN)
+        .map(|issue| issue.to_string())
+        .collect::<Vec<_>>()
+        .join("; ");
+    if issues.len() > SHOWN {
+        summary.push_str(&format!("; and {} more", issues.len() - SHOWN));
+    }
+    summary
+}
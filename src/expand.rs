@@ -0,0 +1,277 @@
+//! Expanding linked records
+//!
+//! [`Record::expand_links`] replaces the record IDs in `multipleRecordLinks`
+//! fields with the linked records themselves, fetched from the table named by
+//! the field's `linkedTableId` option. With a depth above 1, the link fields of
+//! the linked records are expanded as well.
+//!
+//! [`LinkExpander`] keeps the base schema and every record it has fetched, so
+//! expanding many records fetches each linked record only once. Linked records
+//! are fetched with one `RECORD_ID()` formula per linked table and level rather
+//! than one request per record.
+
+use crate::client::BaseHandle;
+use crate::error::{Error, Result};
+use crate::models::{BaseSchema, FieldSchema, Record, TableSchema};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+
+/// Most record IDs put in one `filterByFormula`, keeping the URL well under
+/// Airtable's 16k limit
+const MAX_IDS_PER_REQUEST: usize = 100;
+
+/// Expands link fields, caching linked records between calls
+#[derive(Debug)]
+pub struct LinkExpander {
+    base: BaseHandle,
+    schema: BaseSchema,
+    cache: HashMap<String, Record>,
+    fetched: usize,
+}
+
+impl LinkExpander {
+    /// Create an expander for `base`, fetching its schema
+    pub async fn new(base: &BaseHandle) -> Result<Self> {
+        Ok(Self {
+            base: base.clone(),
+            schema: base.schema().await?,
+            cache: HashMap::new(),
+            fetched: 0,
+        })
+    }
+
+    /// Number of records fetched so far
+    pub fn fetched(&self) -> usize {
+        self.fetched
+    }
+
+    /// Expand link fields of `record`, which belongs to `table` (a name or ID)
+    ///
+    /// `fields` names the link fields to expand; when empty or `["*"]`, every
+    /// link field is expanded. Nested levels always expand every link field. A
+    /// depth of 0 leaves the record unchanged.
+    pub async fn expand(
+        &mut self,
+        record: &mut Record,
+        table: &str,
+        fields: &[&str],
+        depth: usize,
+    ) -> Result<()> {
+        self.expand_all(std::slice::from_mut(record), table, fields, depth)
+            .await
+    }
+
+    /// Expand link fields of several records of `table`, as [`expand`](Self::expand)
+    pub async fn expand_all(
+        &mut self,
+        records: &mut [Record],
+        table: &str,
+        fields: &[&str],
+        depth: usize,
+    ) -> Result<()> {
+        let fields: Vec<String> = fields.iter().map(|f| f.to_string()).collect();
+        self.expand_boxed(records, table.to_string(), fields, depth)
+            .await
+    }
+
+    /// Boxed so expansion can recurse into linked records
+    fn expand_boxed<'a>(
+        &'a mut self,
+        records: &'a mut [Record],
+        table: String,
+        fields: Vec<String>,
+        depth: usize,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            if depth == 0 {
+                return Ok(());
+            }
+            let links = link_fields(self.table_schema(&table)?, &fields)?;
+
+            for (field, linked_table) in links {
+                // Fetch and expand every record linked from this field once
+                let mut ids = Vec::new();
+                let mut seen = HashSet::new();
+                for record in records.iter() {
+                    for id in linked_ids(record, &field) {
+                        if seen.insert(id.clone()) {
+                            ids.push(id);
+                        }
+                    }
+                }
+                if ids.is_empty() {
+                    continue;
+                }
+                self.fetch(&linked_table, &ids).await?;
+                let mut linked: Vec<Record> = ids
+                    .iter()
+                    .filter_map(|id| self.cache.get(id).cloned())
+                    .collect();
+                self.expand_boxed(&mut linked, linked_table, Vec::new(), depth - 1)
+                    .await?;
+                let mut expanded = HashMap::new();
+                for record in linked {
+                    expanded.insert(record.id.clone(), serde_json::to_value(record)?);
+                }
+
+                // IDs of records that no longer exist are left as they are
+                for record in records.iter_mut() {
+                    if let Some(Value::Array(items)) = record.fields.get_mut(&field) {
+                        for item in items {
+                            if let Some(value) = item.as_str().and_then(|id| expanded.get(id)) {
+                                *item = value.clone();
+                            }
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn table_schema(&self, table: &str) -> Result<&TableSchema> {
+        self.schema
+            .tables
+            .iter()
+            .find(|t| t.id == table || t.name == table)
+            .ok_or_else(|| Error::table_not_found(table))
+    }
+
+    /// Table a record belongs to: the only table with all of its fields and the
+    /// requested link fields
+    fn table_of(&self, record: &Record, fields: &[&str]) -> Result<String> {
+        let has_field = |table: &TableSchema, name: &str| {
+            table.fields.iter().any(|f| f.name == name || f.id == name)
+        };
+        let candidates: Vec<&TableSchema> = self
+            .schema
+            .tables
+            .iter()
+            .filter(|table| record.fields.keys().all(|name| has_field(table, name)))
+            .filter(|table| {
+                fields
+                    .iter()
+                    .all(|name| *name == "*" || has_field(table, name))
+            })
+            .collect();
+        match candidates.as_slice() {
+            [table] => Ok(table.id.clone()),
+            [] => Err(Error::config(format!(
+                "record {} does not match any table in {}",
+                record.id,
+                self.base.id()
+            ))),
+            _ => Err(Error::config(format!(
+                "record {} matches {} tables in {}; use LinkExpander::expand with the table",
+                record.id,
+                candidates.len(),
+                self.base.id()
+            ))),
+        }
+    }
+
+    /// Fetch the records of `table_id` in `ids` that are not cached yet
+    async fn fetch(&mut self, table_id: &str, ids: &[String]) -> Result<()> {
+        let missing: Vec<&String> = ids
+            .iter()
+            .filter(|id| !self.cache.contains_key(*id))
+            .collect();
+        for chunk in missing.chunks(MAX_IDS_PER_REQUEST) {
+            let clauses: Vec<String> = chunk
+                .iter()
+                .map(|id| format!("RECORD_ID()='{}'", id))
+                .collect();
+            let formula = format!("OR({})", clauses.join(","));
+
+            let table = self.base.table(table_id);
+            let mut offset = None;
+            loop {
+                let (records, next) = table
+                    .list()
+                    .filter_by_formula(&formula)
+                    .offset(offset)
+                    .execute()
+                    .await?;
+                self.fetched += records.len();
+                for record in records {
+                    self.cache.insert(record.id.clone(), record);
+                }
+                if next.is_none() {
+                    break;
+                }
+                offset = next;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Record IDs in a link field of `record`
+fn linked_ids(record: &Record, field: &str) -> Vec<String> {
+    match record.fields.get(field) {
+        Some(Value::Array(ids)) => ids
+            .iter()
+            .filter_map(|id| id.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// `(field name, linked table ID)` for the requested link fields of `table`
+///
+/// No fields, or just `*`, means every link field; `*` cannot be combined with
+/// field names.
+fn link_fields(table: &TableSchema, requested: &[String]) -> Result<Vec<(String, String)>> {
+    let links = |field: &FieldSchema| {
+        field
+            .linked_table_id()
+            .map(|linked| (field.name.clone(), linked.to_string()))
+    };
+
+    if requested.len() > 1 && requested.iter().any(|name| name == "*") {
+        return Err(Error::config(
+            "'*' expands every link field and cannot be combined with field names",
+        ));
+    }
+    if requested.is_empty() || requested[0] == "*" {
+        return Ok(table.fields.iter().filter_map(links).collect());
+    }
+    requested
+        .iter()
+        .map(|name| {
+            table
+                .fields
+                .iter()
+                .find(|f| &f.name == name || &f.id == name)
+                .and_then(links)
+                .ok_or_else(|| {
+                    Error::config(format!(
+                        "'{}' is not a linked record field in {}",
+                        name, table.name
+                    ))
+                })
+        })
+        .collect()
+}
+
+impl Record {
+    /// Replace linked record IDs in `fields` with the linked records, `depth` levels deep
+    ///
+    /// The record's table is found in the schema of `base` from the record's
+    /// fields and `fields`; when more than one table fits, use
+    /// [`LinkExpander::expand`] with the table. When `fields` is empty, every
+    /// link field is expanded. Use a [`LinkExpander`] to share fetched records
+    /// when expanding many records.
+    pub async fn expand_links(
+        &mut self,
+        base: &BaseHandle,
+        fields: &[&str],
+        depth: usize,
+    ) -> Result<()> {
+        let mut expander = LinkExpander::new(base).await?;
+        let table = expander.table_of(self, fields)?;
+        expander.expand(self, &table, fields, depth).await
+    }
+}
@@ -0,0 +1,504 @@
+//! CSV import with schema-aware type coercion
+//!
+//! [`TableHandle::import_csv`] maps CSV headers to the table's fields and turns
+//! each cell into the value its field type expects before creating (or
+//! upserting) the records in batches of 10. Headers are matched through an
+//! explicit mapping first, then by exact field name, then case-insensitively.
+//!
+//! | Field type | Accepted cell values |
+//! |------------|----------------------|
+//! | number, currency, percent, rating, duration | `1234`, `1,234.5`, `$12`, `15%` (percent fields read `15` and `15%` as `0.15`) |
+//! | checkbox | `true/false`, `yes/no`, `y/n`, `1/0`, `x`, `checked`, `on/off` |
+//! | date, dateTime | ISO 8601, or the format given with [`ImportOptions::with_date_format`] |
+//! | multipleSelects, multipleAttachments, collaborators | values split on the list delimiter |
+//! | multipleRecordLinks | record IDs or primary field values of the linked table |
+//!
+//! Empty cells are left out of the record. Rows that cannot be coerced, or
+//! whose batch is rejected by Airtable, are collected in the [`ImportReport`]
+//! and can be written out with [`ImportReport::write_rejected`].
+
+use crate::client::TableHandle;
+use crate::csv::{self, CsvRow};
+use crate::error::{Error, Result};
+use crate::models::{FieldSchema, TableSchema};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde_json::{json, Map, Number, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Write};
+
+/// Maximum number of records per batch request
+const BATCH_SIZE: usize = 10;
+
+/// How CSV rows are mapped and written
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    mapping: HashMap<String, String>,
+    date_format: Option<String>,
+    delimiter: char,
+    list_delimiter: char,
+    upsert_keys: Vec<String>,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            mapping: HashMap::new(),
+            date_format: None,
+            delimiter: ',',
+            list_delimiter: ',',
+            upsert_keys: Vec::new(),
+        }
+    }
+}
+
+impl ImportOptions {
+    /// Map CSV headers to field names or IDs, overriding name matching
+    pub fn with_mapping(mut self, mapping: HashMap<String, String>) -> Self {
+        self.mapping = mapping;
+        self
+    }
+
+    /// Parse date and dateTime cells with a `chrono` format such as `%m/%d/%Y`
+    pub fn with_date_format<S: Into<String>>(mut self, format: S) -> Self {
+        self.date_format = Some(format.into());
+        self
+    }
+
+    /// Separator between cells (default `,`; use `\t` for TSV)
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Separator between values of multi-value cells (default `,`)
+    pub fn with_list_delimiter(mut self, delimiter: char) -> Self {
+        self.list_delimiter = delimiter;
+        self
+    }
+
+    /// Upsert on these fields instead of always creating records
+    pub fn with_upsert_keys(mut self, keys: Vec<String>) -> Self {
+        self.upsert_keys = keys;
+        self
+    }
+}
+
+/// A row that was not imported
+#[derive(Debug, Clone)]
+pub struct RejectedRow {
+    /// Line the row starts on in the CSV input
+    pub line: usize,
+    /// The row's original cells
+    pub values: Vec<String>,
+    /// Why the row was rejected
+    pub reason: String,
+}
+
+/// Outcome of [`TableHandle::import_csv`]
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// CSV header row
+    pub headers: Vec<String>,
+    /// Header to field name mapping that was used
+    pub columns: Vec<(String, String)>,
+    /// Headers that did not match a writable field
+    pub ignored_columns: Vec<String>,
+    /// Records written (created, or created/updated when upserting)
+    pub imported: usize,
+    /// Rows that were not imported
+    pub rejected: Vec<RejectedRow>,
+}
+
+impl ImportReport {
+    /// Write the rejected rows as CSV with an extra `error` column
+    pub fn write_rejected<W: Write>(&self, writer: &mut W, delimiter: char) -> io::Result<()> {
+        let mut header = self.headers.clone();
+        header.push("error".to_string());
+        csv::write_row(writer, &header, delimiter)?;
+        for row in &self.rejected {
+            let mut values = row.values.clone();
+            values.resize(self.headers.len(), String::new());
+            values.push(format!("line {}: {}", row.line, row.reason));
+            csv::write_row(writer, &values, delimiter)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ImportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Imported {} records, rejected {} rows",
+            self.imported,
+            self.rejected.len()
+        )?;
+        if !self.ignored_columns.is_empty() {
+            write!(f, " (ignored columns: {})", self.ignored_columns.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl TableHandle {
+    /// Import CSV text into this table
+    ///
+    /// The first row must be a header row. Fails without writing anything if the
+    /// CSV cannot be parsed, an upsert key is not a mapped column or the mapping
+    /// names an unknown field; otherwise bad rows are reported, not fatal.
+    pub async fn import_csv(&self, input: &str, options: &ImportOptions) -> Result<ImportReport> {
+        let mut rows = csv::parse(input, options.delimiter)?.into_iter();
+        let headers = rows
+            .next()
+            .ok_or_else(|| Error::Other("CSV input has no header row".to_string()))?
+            .fields;
+
+        let base_schema = self.base().schema().await?;
+        let schema = base_schema
+            .tables
+            .iter()
+            .find(|t| t.name == self.name() || t.id == self.name())
+            .ok_or_else(|| Error::table_not_found(self.name()))?;
+
+        let mut report = ImportReport {
+            headers: headers.clone(),
+            ..Default::default()
+        };
+        let columns = map_columns(&headers, schema, options, &mut report)?;
+        for key in &options.upsert_keys {
+            if !columns.iter().any(|(_, field)| &field.name == key) {
+                return Err(Error::Other(format!(
+                    "Upsert key '{}' is not mapped to a CSV column",
+                    key
+                )));
+            }
+        }
+
+        // Look up linked records by primary field value
+        let mut links: HashMap<String, LinkIndex> = HashMap::new();
+        for (_, field) in &columns {
+            let Some(linked_id) = field.linked_table_id() else {
+                continue;
+            };
+            if let Some(linked) = base_schema.tables.iter().find(|t| t.id == linked_id) {
+                links.insert(field.id.clone(), LinkIndex::load(self, linked).await?);
+            }
+        }
+
+        let coercer = Coercer { options, links };
+        let mut batch: Vec<(CsvRow, Value)> = Vec::new();
+        for row in rows {
+            match coercer.record(&row, &columns, &options.upsert_keys) {
+                Ok(fields) => batch.push((row, fields)),
+                Err(reason) => report.rejected.push(RejectedRow {
+                    line: row.line,
+                    values: row.fields,
+                    reason,
+                }),
+            }
+            if batch.len() == BATCH_SIZE {
+                self.write_batch(std::mem::take(&mut batch), options, &mut report)
+                    .await;
+            }
+        }
+        if !batch.is_empty() {
+            self.write_batch(batch, options, &mut report).await;
+        }
+
+        report.columns = columns
+            .into_iter()
+            .map(|(index, field)| (headers[index].clone(), field.name.clone()))
+            .collect();
+        Ok(report)
+    }
+
+    /// Create or upsert one batch, rejecting all of its rows if Airtable refuses it
+    async fn write_batch(
+        &self,
+        batch: Vec<(CsvRow, Value)>,
+        options: &ImportOptions,
+        report: &mut ImportReport,
+    ) {
+        let (rows, fields): (Vec<CsvRow>, Vec<Value>) = batch.into_iter().unzip();
+        let result = if options.upsert_keys.is_empty() {
+            self.batch_create(fields).await
+        } else {
+            let records = fields.into_iter().map(|f| json!({ "fields": f })).collect();
+            let keys: Vec<&str> = options.upsert_keys.iter().map(String::as_str).collect();
+            self.batch_upsert(records, &keys).await
+        };
+
+        match result {
+            Ok(records) => report.imported += records.len(),
+            Err(e) => report
+                .rejected
+                .extend(rows.into_iter().map(|row| RejectedRow {
+                    line: row.line,
+                    values: row.fields,
+                    reason: e.to_string(),
+                })),
+        }
+    }
+}
+
+/// Match headers to writable fields, returning `(column index, field)` pairs
+fn map_columns<'a>(
+    headers: &[String],
+    schema: &'a TableSchema,
+    options: &ImportOptions,
+    report: &mut ImportReport,
+) -> Result<Vec<(usize, &'a FieldSchema)>> {
+    let mut columns = Vec::new();
+    for (index, header) in headers.iter().enumerate() {
+        let header = header.trim();
+        let field = match options.mapping.get(header) {
+            Some(target) => Some(
+                schema
+                    .fields
+                    .iter()
+                    .find(|f| &f.name == target || &f.id == target)
+                    .ok_or_else(|| {
+                        Error::Other(format!(
+                            "Mapping for column '{}' names unknown field '{}'",
+                            header, target
+                        ))
+                    })?,
+            ),
+            None => schema.fields.iter().find(|f| f.name == header).or_else(|| {
+                schema
+                    .fields
+                    .iter()
+                    .find(|f| f.name.eq_ignore_ascii_case(header))
+            }),
+        };
+
+        match field {
+            Some(field) if !field.is_computed() => columns.push((index, field)),
+            _ => report.ignored_columns.push(header.to_string()),
+        }
+    }
+    Ok(columns)
+}
+
+/// Primary field values of a linked table, for resolving link cells
+struct LinkIndex {
+    table: String,
+    ids: HashMap<String, Vec<String>>,
+}
+
+impl LinkIndex {
+    async fn load(table: &TableHandle, linked: &TableSchema) -> Result<Self> {
+        let primary = linked
+            .fields
+            .iter()
+            .find(|f| f.id == linked.primary_field_id)
+            .map(|f| f.name.as_str())
+            .unwrap_or_default();
+        let handle = table.base().table(&linked.id);
+
+        let mut ids: HashMap<String, Vec<String>> = HashMap::new();
+        let mut offset = None;
+        loop {
+            let (records, next) = handle
+                .list()
+                .fields(&[primary])
+                .offset(offset)
+                .execute()
+                .await?;
+            for record in records {
+                if let Some(value) = record.fields.get(primary) {
+                    ids.entry(cell_text(value)).or_default().push(record.id);
+                }
+            }
+            if next.is_none() {
+                break;
+            }
+            offset = next;
+        }
+
+        Ok(Self {
+            table: linked.name.clone(),
+            ids,
+        })
+    }
+
+    fn resolve(&self, value: &str) -> std::result::Result<String, String> {
+        if value.starts_with("rec") && value.len() == 17 {
+            return Ok(value.to_string());
+        }
+        match self.ids.get(value).map(Vec::as_slice) {
+            Some([id]) => Ok(id.clone()),
+            Some(_) => Err(format!(
+                "'{}' matches several records in {}",
+                value, self.table
+            )),
+            None => Err(format!("no record in {} named '{}'", self.table, value)),
+        }
+    }
+}
+
+/// Text of a primary field value as it would appear in a CSV cell
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Turns CSV cells into field values
+struct Coercer<'a> {
+    options: &'a ImportOptions,
+    links: HashMap<String, LinkIndex>,
+}
+
+impl Coercer<'_> {
+    fn record(
+        &self,
+        row: &CsvRow,
+        columns: &[(usize, &FieldSchema)],
+        upsert_keys: &[String],
+    ) -> std::result::Result<Value, String> {
+        let mut fields = Map::new();
+        for (index, field) in columns {
+            let raw = row.fields.get(*index).map(|s| s.trim()).unwrap_or_default();
+            if raw.is_empty() {
+                continue;
+            }
+            let value = self
+                .value(field, raw)
+                .map_err(|e| format!("{}: {}", field.name, e))?;
+            fields.insert(field.name.clone(), value);
+        }
+        if let Some(key) = upsert_keys.iter().find(|k| !fields.contains_key(*k)) {
+            return Err(format!("missing upsert key {}", key));
+        }
+        if fields.is_empty() {
+            return Err("row has no values".to_string());
+        }
+        Ok(Value::Object(fields))
+    }
+
+    fn value(&self, field: &FieldSchema, raw: &str) -> std::result::Result<Value, String> {
+        let list = || {
+            raw.split(self.options.list_delimiter)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+        };
+        match field.field_type.as_str() {
+            "number" | "currency" | "rating" | "duration" => parse_number(raw, false),
+            "percent" => parse_number(raw, true),
+            "checkbox" => parse_checkbox(raw),
+            "date" => parse_date(raw, self.options.date_format.as_deref())
+                .map(|d| Value::String(d.format("%Y-%m-%d").to_string())),
+            "dateTime" => parse_datetime(raw, self.options.date_format.as_deref())
+                .map(|d| Value::String(d.to_rfc3339())),
+            "multipleSelects" => Ok(json!(list().collect::<Vec<_>>())),
+            "multipleAttachments" => Ok(Value::Array(
+                list().map(|url| json!({ "url": url })).collect(),
+            )),
+            "singleCollaborator" => Ok(json!({ "email": raw })),
+            "multipleCollaborators" => Ok(Value::Array(
+                list().map(|email| json!({ "email": email })).collect(),
+            )),
+            "barcode" => Ok(json!({ "text": raw })),
+            "multipleRecordLinks" => {
+                let index = self
+                    .links
+                    .get(&field.id)
+                    .ok_or_else(|| "linked table not found".to_string())?;
+                let ids = list()
+                    .map(|value| index.resolve(value))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(json!(ids))
+            }
+            _ => Ok(Value::String(raw.to_string())),
+        }
+    }
+}
+
+fn parse_number(raw: &str, percent: bool) -> std::result::Result<Value, String> {
+    let text = raw.strip_suffix('%').unwrap_or(raw);
+    let cleaned: String = text
+        .trim()
+        .trim_start_matches(['$', '€', '£', '¥'])
+        .chars()
+        .filter(|c| *c != ',' && *c != '_' && !c.is_whitespace())
+        .collect();
+
+    if !percent {
+        if let Ok(n) = cleaned.parse::<i64>() {
+            return Ok(Value::Number(n.into()));
+        }
+    }
+    let n: f64 = cleaned
+        .parse()
+        .map_err(|_| format!("'{}' is not a number", raw))?;
+    let n = if percent { n / 100.0 } else { n };
+    Number::from_f64(n)
+        .map(Value::Number)
+        .ok_or_else(|| format!("'{}' is not a finite number", raw))
+}
+
+fn parse_checkbox(raw: &str) -> std::result::Result<Value, String> {
+    match raw.to_ascii_lowercase().as_str() {
+        "true" | "t" | "yes" | "y" | "1" | "x" | "checked" | "on" => Ok(Value::Bool(true)),
+        "false" | "f" | "no" | "n" | "0" | "unchecked" | "off" => Ok(Value::Bool(false)),
+        _ => Err(format!("'{}' is not a checkbox value", raw)),
+    }
+}
+
+fn parse_date(raw: &str, format: Option<&str>) -> std::result::Result<NaiveDate, String> {
+    if let Some(format) = format {
+        return NaiveDate::parse_from_str(raw, format)
+            .or_else(|_| NaiveDateTime::parse_from_str(raw, format).map(|d| d.date()))
+            .map_err(|_| format!("'{}' does not match date format '{}'", raw, format));
+    }
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .or_else(|_| DateTime::parse_from_rfc3339(raw).map(|d| d.date_naive()))
+        .map_err(|_| format!("'{}' is not a YYYY-MM-DD date", raw))
+}
+
+fn parse_datetime(raw: &str, format: Option<&str>) -> std::result::Result<DateTime<Utc>, String> {
+    let midnight = |date: NaiveDate| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+    if let Some(format) = format {
+        return NaiveDateTime::parse_from_str(raw, format)
+            .map(|d| d.and_utc())
+            .or_else(|_| NaiveDate::parse_from_str(raw, format).map(midnight))
+            .map_err(|_| format!("'{}' does not match date format '{}'", raw, format));
+    }
+    DateTime::parse_from_rfc3339(raw)
+        .map(|d| d.with_timezone(&Utc))
+        .or_else(|_| NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S").map(|d| d.and_utc()))
+        .or_else(|_| NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S").map(|d| d.and_utc()))
+        .or_else(|_| NaiveDate::parse_from_str(raw, "%Y-%m-%d").map(midnight))
+        .map_err(|_| format!("'{}' is not an ISO 8601 date and time", raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar_coercions() {
+        assert_eq!(parse_number("1,234", false), Ok(json!(1234)));
+        assert_eq!(parse_number("$12.50", false), Ok(json!(12.5)));
+        assert_eq!(parse_number("15%", true), Ok(json!(0.15)));
+        assert_eq!(parse_number("15", true), Ok(json!(0.15)));
+        assert_eq!(parse_number("15%", false), Ok(json!(15)));
+        assert!(parse_number("twelve", false).is_err());
+
+        assert_eq!(parse_checkbox("Yes"), Ok(json!(true)));
+        assert_eq!(parse_checkbox("0"), Ok(json!(false)));
+        assert!(parse_checkbox("maybe").is_err());
+
+        assert_eq!(
+            parse_date("03/14/2024", Some("%m/%d/%Y")),
+            NaiveDate::from_ymd_opt(2024, 3, 14).ok_or_else(String::new)
+        );
+        assert_eq!(
+            parse_datetime("2024-03-14 09:30:00", None).map(|d| d.to_rfc3339()),
+            Ok("2024-03-14T09:30:00+00:00".to_string())
+        );
+    }
+}
@@ -36,13 +36,68 @@
 //! }
 //! ```
 
+/// Record a value on the current tracing span (no-op without the `tracing` feature)
+macro_rules! record_field {
+    ($name:literal, $value:expr) => {{
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record($name, $value);
+        #[cfg(not(feature = "tracing"))]
+        let _ = &$value;
+    }};
+}
+
+pub mod backup;
+pub mod bulk;
+pub mod cache;
+pub mod cassette;
 pub mod client;
+pub mod completion;
 pub mod config;
+pub mod csv;
+pub mod enterprise;
 pub mod error;
+pub mod expand;
+pub mod import;
+pub mod metrics;
+pub mod middleware;
+pub mod migrate;
 pub mod models;
+pub mod output;
+pub mod profile;
+pub mod resolve;
+pub mod schema_diff;
+#[cfg(feature = "shell")]
+pub mod shell;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "sync")]
+pub mod sync;
+pub mod transport;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod validation;
 pub mod views;
 
+pub use backup::{BackupManifest, BackupOptions, RestoreReport};
+pub use bulk::{BulkFailure, BulkOperation, BulkOutcome, BulkSummary};
+pub use cache::{CacheEndpoint, CacheStore, DiskCache, MemoryCache, ResponseCache};
+pub use cassette::{CassetteMode, CassetteTransport};
 pub use client::Client;
+pub use completion::{NameKind, SchemaCache};
 pub use config::Config;
+pub use enterprise::EnterpriseHandle;
 pub use error::{Error, Result};
+pub use expand::LinkExpander;
+pub use import::{ImportOptions, ImportReport};
+pub use metrics::{InMemoryMetrics, Metrics, MetricsSnapshot};
+pub use middleware::{Middleware, RateLimitMiddleware, RequestContext, ResponseAction, RetryMiddleware};
+pub use migrate::{MigrationFailure, MigrationPlan, MigrationReport, MigrationStep, SchemaSpec};
 pub use models::*;
+pub use output::OutputFormat;
+pub use profile::{DefaultExport, Profile, ProfileFile, TokenSource};
+pub use resolve::Target;
+pub use schema_diff::{SchemaChange, SchemaDiff};
+pub use transport::{
+    HttpRequest, HttpResponse, Method, MultipartFile, RequestBody, ReqwestTransport, Transport,
+};
+pub use validation::WriteValidator;
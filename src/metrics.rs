@@ -0,0 +1,598 @@
+//! Client metrics for RSAirtable
+//!
+//! Implement [`Metrics`] and attach it with [`Client::with_metrics`](crate::Client::with_metrics)
+//! to observe every HTTP attempt, retry and record read or written. The built-in
+//! [`InMemoryMetrics`] aggregates them into a [`MetricsSnapshot`] that can be
+//! printed as a summary or exported in the Prometheus text format.
+//!
+//! Airtable limits each base to 5 requests per second, so the snapshot also
+//! counts requests per base.
+
+use crate::error::Result;
+use crate::middleware::{Middleware, RequestContext, ResponseAction};
+use crate::transport::{HttpResponse, Method};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Upper bounds (in seconds) of the latency histogram buckets
+pub const LATENCY_BUCKETS: [f64; 9] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/// A completed HTTP attempt (retries produce one event per attempt)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestEvent {
+    /// HTTP method
+    pub method: Method,
+    /// Endpoint template with IDs and table names replaced, e.g. `/{baseId}/{table}/{recordId}`
+    pub endpoint: String,
+    /// Base the request targeted (if any)
+    pub base_id: Option<String>,
+    /// HTTP status, or `None` if no response was received
+    pub status: Option<u16>,
+    /// Time taken by the attempt
+    pub latency: Duration,
+}
+
+/// Why a request was retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RetryReason {
+    /// Airtable returned 429 (the wait counts as a throttle wait)
+    RateLimited,
+    /// Airtable returned a 5xx status
+    ServerError,
+    /// The request could not be sent or the response could not be read
+    Network,
+}
+
+impl RetryReason {
+    /// Label used in exported metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RetryReason::RateLimited => "rate_limited",
+            RetryReason::ServerError => "server_error",
+            RetryReason::Network => "network",
+        }
+    }
+}
+
+/// A retry about to be made after waiting `delay`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryEvent {
+    /// HTTP method
+    pub method: Method,
+    /// Endpoint template (see [`RequestEvent::endpoint`])
+    pub endpoint: String,
+    /// Why the request is retried
+    pub reason: RetryReason,
+    /// Time waited before retrying
+    pub delay: Duration,
+}
+
+/// Whether records were read or written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RecordAccess {
+    /// Records returned by list/get operations
+    Read,
+    /// Records created, updated or deleted
+    Written,
+}
+
+/// Receives client metrics events
+///
+/// All methods have empty default implementations, so implementors only need
+/// to override the events they care about. Methods are called inline on the
+/// request path and should return quickly.
+pub trait Metrics: fmt::Debug + Send + Sync {
+    /// Called after every HTTP attempt
+    fn record_request(&self, _event: &RequestEvent) {}
+
+    /// Called before waiting to retry a request
+    fn record_retry(&self, _event: &RetryEvent) {}
+
+    /// Called when a table operation reads or writes records
+    fn record_records(&self, _base_id: &str, _table: &str, _access: RecordAccess, _count: usize) {}
+}
+
+/// Cumulative latency histogram
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Cumulative counts for each bound in [`LATENCY_BUCKETS`]
+    pub buckets: Vec<u64>,
+    /// Number of observations
+    pub count: u64,
+    /// Sum of observations
+    pub sum: Duration,
+    /// Largest observation
+    pub max: Duration,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            buckets: vec![0; LATENCY_BUCKETS.len()],
+            count: 0,
+            sum: Duration::ZERO,
+            max: Duration::ZERO,
+        }
+    }
+}
+
+impl Histogram {
+    /// Add an observation
+    pub fn observe(&mut self, value: Duration) {
+        let seconds = value.as_secs_f64();
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += value;
+        self.max = self.max.max(value);
+    }
+
+    /// Mean observation (zero when empty)
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            Duration::ZERO
+        } else {
+            self.sum / self.count as u32
+        }
+    }
+}
+
+/// Request counter key
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RequestKey {
+    /// HTTP method
+    pub method: String,
+    /// Endpoint template
+    pub endpoint: String,
+    /// HTTP status, or `None` for network errors
+    pub status: Option<u16>,
+}
+
+/// Records read and written for one table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecordCounts {
+    /// Records read
+    pub read: u64,
+    /// Records written (created, updated or deleted)
+    pub written: u64,
+}
+
+/// Point-in-time copy of aggregated metrics
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    /// Request counts per method, endpoint and status
+    pub requests: BTreeMap<RequestKey, u64>,
+    /// Request counts per base
+    pub requests_per_base: BTreeMap<String, u64>,
+    /// Latency histograms per `(method, endpoint)`
+    pub latency: BTreeMap<(String, String), Histogram>,
+    /// Retries per reason
+    pub retries: BTreeMap<RetryReason, u64>,
+    /// Total time spent waiting after 429 responses
+    pub throttle_wait: Duration,
+    /// Records read and written per `(base ID, table)`
+    pub records: BTreeMap<(String, String), RecordCounts>,
+}
+
+impl MetricsSnapshot {
+    /// Total number of HTTP attempts
+    pub fn total_requests(&self) -> u64 {
+        self.requests.values().sum()
+    }
+
+    /// Total number of retries
+    pub fn total_retries(&self) -> u64 {
+        self.retries.values().sum()
+    }
+
+    /// Number of waits caused by rate limiting
+    pub fn throttle_waits(&self) -> u64 {
+        self.retries
+            .get(&RetryReason::RateLimited)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Render the snapshot in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str(
+            "# HELP airtable_requests_total Airtable HTTP requests (each attempt counts)\n",
+        );
+        out.push_str("# TYPE airtable_requests_total counter\n");
+        for (key, count) in &self.requests {
+            let status = key
+                .status
+                .map_or_else(|| "error".to_string(), |s| s.to_string());
+            out.push_str(&format!(
+                "airtable_requests_total{{method=\"{}\",endpoint=\"{}\",status=\"{}\"}} {}\n",
+                key.method,
+                escape(&key.endpoint),
+                status,
+                count
+            ));
+        }
+
+        out.push_str("# HELP airtable_base_requests_total Airtable HTTP requests per base\n");
+        out.push_str("# TYPE airtable_base_requests_total counter\n");
+        for (base_id, count) in &self.requests_per_base {
+            out.push_str(&format!(
+                "airtable_base_requests_total{{base=\"{}\"}} {}\n",
+                escape(base_id),
+                count
+            ));
+        }
+
+        out.push_str("# HELP airtable_request_duration_seconds Airtable HTTP request latency\n");
+        out.push_str("# TYPE airtable_request_duration_seconds histogram\n");
+        for ((method, endpoint), histogram) in &self.latency {
+            let labels = format!("method=\"{}\",endpoint=\"{}\"", method, escape(endpoint));
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(&histogram.buckets) {
+                out.push_str(&format!(
+                    "airtable_request_duration_seconds_bucket{{{},le=\"{}\"}} {}\n",
+                    labels, bound, count
+                ));
+            }
+            out.push_str(&format!(
+                "airtable_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}\n",
+                labels, histogram.count
+            ));
+            out.push_str(&format!(
+                "airtable_request_duration_seconds_sum{{{}}} {}\n",
+                labels,
+                histogram.sum.as_secs_f64()
+            ));
+            out.push_str(&format!(
+                "airtable_request_duration_seconds_count{{{}}} {}\n",
+                labels, histogram.count
+            ));
+        }
+
+        out.push_str("# HELP airtable_retries_total Airtable request retries\n");
+        out.push_str("# TYPE airtable_retries_total counter\n");
+        for (reason, count) in &self.retries {
+            out.push_str(&format!(
+                "airtable_retries_total{{reason=\"{}\"}} {}\n",
+                reason.as_str(),
+                count
+            ));
+        }
+
+        out.push_str(
+            "# HELP airtable_throttle_wait_seconds_total Time spent waiting after rate limiting\n",
+        );
+        out.push_str("# TYPE airtable_throttle_wait_seconds_total counter\n");
+        out.push_str(&format!(
+            "airtable_throttle_wait_seconds_total {}\n",
+            self.throttle_wait.as_secs_f64()
+        ));
+
+        out.push_str("# HELP airtable_records_total Records read or written per table\n");
+        out.push_str("# TYPE airtable_records_total counter\n");
+        for ((base_id, table), counts) in &self.records {
+            for (access, count) in [("read", counts.read), ("written", counts.written)] {
+                out.push_str(&format!(
+                    "airtable_records_total{{base=\"{}\",table=\"{}\",access=\"{}\"}} {}\n",
+                    escape(base_id),
+                    escape(table),
+                    access,
+                    count
+                ));
+            }
+        }
+
+        out
+    }
+}
+
+impl fmt::Display for MetricsSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Airtable API usage")?;
+        writeln!(
+            f,
+            "  Requests: {} (retries: {}, throttle waits: {} totalling {:.1}s)",
+            self.total_requests(),
+            self.total_retries(),
+            self.throttle_waits(),
+            self.throttle_wait.as_secs_f64()
+        )?;
+
+        for (key, count) in &self.requests {
+            let status = key
+                .status
+                .map_or_else(|| "error".to_string(), |s| s.to_string());
+            let histogram = self
+                .latency
+                .get(&(key.method.clone(), key.endpoint.clone()));
+            write!(
+                f,
+                "  {:<6} {:<40} {:>5} x{}",
+                key.method, key.endpoint, status, count
+            )?;
+            if let Some(histogram) = histogram {
+                write!(
+                    f,
+                    "  (avg {}ms, max {}ms)",
+                    histogram.mean().as_millis(),
+                    histogram.max.as_millis()
+                )?;
+            }
+            writeln!(f)?;
+        }
+
+        if !self.requests_per_base.is_empty() {
+            writeln!(f, "  Requests per base:")?;
+            for (base_id, count) in &self.requests_per_base {
+                writeln!(f, "    {}: {}", base_id, count)?;
+            }
+        }
+
+        if !self.records.is_empty() {
+            writeln!(f, "  Records:")?;
+            for ((base_id, table), counts) in &self.records {
+                writeln!(
+                    f,
+                    "    {}/{}: {} read, {} written",
+                    base_id, table, counts.read, counts.written
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Escape a Prometheus label value
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Metrics collected in memory
+#[derive(Debug, Default)]
+pub struct InMemoryMetrics {
+    snapshot: Mutex<MetricsSnapshot>,
+}
+
+impl InMemoryMetrics {
+    /// Create an empty collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy of the metrics collected so far
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.snapshot.lock().unwrap().clone()
+    }
+
+    /// Clear all collected metrics
+    pub fn reset(&self) {
+        *self.snapshot.lock().unwrap() = MetricsSnapshot::default();
+    }
+}
+
+impl Metrics for InMemoryMetrics {
+    fn record_request(&self, event: &RequestEvent) {
+        let mut snapshot = self.snapshot.lock().unwrap();
+        let method = event.method.to_string();
+
+        *snapshot
+            .requests
+            .entry(RequestKey {
+                method: method.clone(),
+                endpoint: event.endpoint.clone(),
+                status: event.status,
+            })
+            .or_default() += 1;
+
+        if let Some(ref base_id) = event.base_id {
+            *snapshot
+                .requests_per_base
+                .entry(base_id.clone())
+                .or_default() += 1;
+        }
+
+        snapshot
+            .latency
+            .entry((method, event.endpoint.clone()))
+            .or_default()
+            .observe(event.latency);
+    }
+
+    fn record_retry(&self, event: &RetryEvent) {
+        let mut snapshot = self.snapshot.lock().unwrap();
+        *snapshot.retries.entry(event.reason).or_default() += 1;
+        if event.reason == RetryReason::RateLimited {
+            snapshot.throttle_wait += event.delay;
+        }
+    }
+
+    fn record_records(&self, base_id: &str, table: &str, access: RecordAccess, count: usize) {
+        let mut snapshot = self.snapshot.lock().unwrap();
+        let counts = snapshot
+            .records
+            .entry((base_id.to_string(), table.to_string()))
+            .or_default();
+        match access {
+            RecordAccess::Read => counts.read += count as u64,
+            RecordAccess::Written => counts.written += count as u64,
+        }
+    }
+}
+
+/// Middleware reporting every HTTP attempt and retry to a [`Metrics`] hook
+///
+/// Installed by [`Client::with_metrics`](crate::Client::with_metrics).
+#[derive(Debug, Clone)]
+pub struct MetricsMiddleware {
+    metrics: Arc<dyn Metrics>,
+}
+
+impl MetricsMiddleware {
+    /// Report to the given metrics hook
+    pub fn new(metrics: Arc<dyn Metrics>) -> Self {
+        Self { metrics }
+    }
+}
+
+#[async_trait]
+impl Middleware for MetricsMiddleware {
+    async fn before_request(&self, ctx: &mut RequestContext) -> Result<Option<HttpResponse>> {
+        if let Some((reason, delay)) = ctx.retry {
+            self.metrics.record_retry(&RetryEvent {
+                method: ctx.request.method,
+                endpoint: ctx.endpoint().0,
+                reason,
+                delay,
+            });
+        }
+        Ok(None)
+    }
+
+    async fn after_response(
+        &self,
+        ctx: &RequestContext,
+        result: &mut Result<HttpResponse>,
+    ) -> ResponseAction {
+        let (endpoint, base_id) = ctx.endpoint();
+        self.metrics.record_request(&RequestEvent {
+            method: ctx.request.method,
+            endpoint,
+            base_id,
+            status: result.as_ref().ok().map(|response| response.status),
+            latency: ctx.attempt_elapsed(),
+        });
+        ResponseAction::Continue
+    }
+}
+
+/// Turn a request URL into an endpoint template and the base it targets
+///
+/// IDs are replaced by placeholders (`{baseId}`, `{recordId}`, ...) and the table
+/// segment of record URLs by `{table}`, so requests group by endpoint.
+pub(crate) fn endpoint_template(endpoint_url: &str, url: &str) -> (String, Option<String>) {
+    let path = url
+        .strip_prefix(endpoint_url.trim_end_matches('/'))
+        .unwrap_or(url);
+    let path = path.split('?').next().unwrap_or(path).trim_matches('/');
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    let is_meta = segments.first() == Some(&"meta");
+    let mut base_id = None;
+    let template: Vec<String> = segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            if let Some(placeholder) = id_placeholder(segment) {
+                if placeholder == "{baseId}" {
+                    base_id = Some(segment.to_string());
+                }
+                placeholder.to_string()
+            } else if !is_meta && index == 1 {
+                "{table}".to_string()
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect();
+
+    (format!("/{}", template.join("/")), base_id)
+}
+
+/// Placeholder for an Airtable ID segment (three-letter prefix and 14 characters)
+fn id_placeholder(segment: &str) -> Option<&'static str> {
+    if segment.len() != 17 || !segment.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    match &segment[..3] {
+        "app" => Some("{baseId}"),
+        "tbl" => Some("{tableId}"),
+        "rec" => Some("{recordId}"),
+        "fld" => Some("{fieldId}"),
+        "usr" => Some("{userId}"),
+        "ent" => Some("{enterpriseAccountId}"),
+        "wsp" => Some("{workspaceId}"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_template() {
+        let endpoint = "https://api.airtable.com/v0";
+        assert_eq!(
+            endpoint_template(
+                endpoint,
+                "https://api.airtable.com/v0/appABCDEFGHIJKLMN/Test%20Cases/recABCDEFGHIJKLMN?fields%5B%5D=Name"
+            ),
+            (
+                "/{baseId}/{table}/{recordId}".to_string(),
+                Some("appABCDEFGHIJKLMN".to_string())
+            )
+        );
+        assert_eq!(
+            endpoint_template(
+                endpoint,
+                "https://api.airtable.com/v0/meta/bases/appABCDEFGHIJKLMN/tables"
+            )
+            .0,
+            "/meta/bases/{baseId}/tables"
+        );
+        assert_eq!(
+            endpoint_template(endpoint, "https://api.airtable.com/v0/meta/whoami"),
+            ("/meta/whoami".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn test_in_memory_metrics_snapshot() {
+        let metrics = InMemoryMetrics::new();
+        for (status, millis) in [(Some(200), 40), (Some(200), 300), (Some(429), 10)] {
+            metrics.record_request(&RequestEvent {
+                method: Method::Get,
+                endpoint: "/{baseId}/{table}".to_string(),
+                base_id: Some("appABCDEFGHIJKLMN".to_string()),
+                status,
+                latency: Duration::from_millis(millis),
+            });
+        }
+        metrics.record_retry(&RetryEvent {
+            method: Method::Get,
+            endpoint: "/{baseId}/{table}".to_string(),
+            reason: RetryReason::RateLimited,
+            delay: Duration::from_secs(2),
+        });
+        metrics.record_records("appABCDEFGHIJKLMN", "Cases", RecordAccess::Read, 100);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.total_requests(), 3);
+        assert_eq!(snapshot.requests_per_base["appABCDEFGHIJKLMN"], 3);
+        assert_eq!(snapshot.throttle_waits(), 1);
+        assert_eq!(snapshot.throttle_wait, Duration::from_secs(2));
+
+        let histogram = &snapshot.latency[&("GET".to_string(), "/{baseId}/{table}".to_string())];
+        assert_eq!(histogram.count, 3);
+        assert_eq!(histogram.buckets[0], 2, "two observations within 50ms");
+
+        let prometheus = snapshot.to_prometheus();
+        assert!(prometheus.contains(
+            "airtable_requests_total{method=\"GET\",endpoint=\"/{baseId}/{table}\",status=\"429\"} 1"
+        ));
+        assert!(prometheus.contains(
+            "airtable_records_total{base=\"appABCDEFGHIJKLMN\",table=\"Cases\",access=\"read\"} 100"
+        ));
+        assert!(prometheus.contains("airtable_throttle_wait_seconds_total 2"));
+    }
+}
@@ -0,0 +1,269 @@
+//! Request/response middleware for RSAirtable
+//!
+//! Every request sent by [`Client`](crate::Client) passes through an ordered
+//! chain of [`Middleware`]. `before_request` hooks run in the order the
+//! middleware was added and may modify the request or short-circuit it with a
+//! response of their own (for example from a cache). `after_response` hooks run
+//! in reverse order and may inspect or rewrite the result, or ask for the
+//! request to be retried.
+//!
+//! Retries, client-side rate limiting, tracing and metrics are all provided as
+//! middleware. Clients start with [`TracingMiddleware`] (with the `tracing`
+//! feature) and a [`RetryMiddleware`] configured from
+//! [`Config::max_retries`](crate::Config::max_retries).
+
+use crate::error::{Error, Result};
+use crate::metrics::{endpoint_template, RetryReason};
+use crate::transport::{HttpRequest, HttpResponse, Method};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A request travelling through the middleware chain
+#[derive(Debug)]
+pub struct RequestContext {
+    /// Request about to be sent (authentication headers already set)
+    pub request: HttpRequest,
+    /// Number of retries made so far (0 for the first attempt)
+    pub attempt: u32,
+    /// Why and after how long this attempt is a retry (`None` for the first attempt)
+    pub retry: Option<(RetryReason, Duration)>,
+    /// API endpoint URL from the client configuration
+    pub(crate) endpoint_url: String,
+    started: Instant,
+    attempt_started: Instant,
+}
+
+impl RequestContext {
+    pub(crate) fn new(request: HttpRequest, endpoint_url: &str) -> Self {
+        let now = Instant::now();
+        Self {
+            request,
+            attempt: 0,
+            retry: None,
+            endpoint_url: endpoint_url.to_string(),
+            started: now,
+            attempt_started: now,
+        }
+    }
+
+    /// Time since the first attempt started
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Time since the current attempt started
+    pub fn attempt_elapsed(&self) -> Duration {
+        self.attempt_started.elapsed()
+    }
+
+    /// Endpoint template and base ID of the request (see [`crate::metrics::RequestEvent`])
+    pub fn endpoint(&self) -> (String, Option<String>) {
+        endpoint_template(&self.endpoint_url, &self.request.url)
+    }
+
+    pub(crate) fn next_attempt(&mut self, reason: RetryReason, delay: Duration) {
+        self.attempt += 1;
+        self.retry = Some((reason, delay));
+        self.attempt_started = Instant::now();
+    }
+}
+
+/// What the chain should do once a middleware has seen a response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseAction {
+    /// Hand the result to the next middleware
+    Continue,
+    /// Send the request again after waiting `delay`
+    Retry {
+        /// Time to wait before retrying
+        delay: Duration,
+        /// Why the request is retried
+        reason: RetryReason,
+    },
+}
+
+/// Hooks run around every HTTP request
+///
+/// Both hooks have pass-through default implementations.
+#[async_trait]
+pub trait Middleware: fmt::Debug + Send + Sync {
+    /// Inspect or modify a request before it is sent
+    ///
+    /// Returning a response skips the transport and the `before_request` hooks of
+    /// later middleware. Returning an error aborts the request.
+    async fn before_request(&self, _ctx: &mut RequestContext) -> Result<Option<HttpResponse>> {
+        Ok(None)
+    }
+
+    /// Inspect or rewrite the result of a request
+    ///
+    /// Non-2xx responses arrive as `Ok` and are turned into [`Error::Api`] after the
+    /// whole chain has run, so middleware can retry or rewrite them.
+    async fn after_response(
+        &self,
+        _ctx: &RequestContext,
+        _result: &mut Result<HttpResponse>,
+    ) -> ResponseAction {
+        ResponseAction::Continue
+    }
+}
+
+/// Retries rate-limited requests, server errors and network failures
+///
+/// 429 responses are retried for every method; 5xx responses and network
+/// failures only for methods other than POST, which is not idempotent. Backoff
+/// is exponential from 500ms, capped at 30 seconds, and honours `Retry-After`.
+#[derive(Debug, Clone)]
+pub struct RetryMiddleware {
+    max_retries: u32,
+}
+
+impl RetryMiddleware {
+    /// Retry each request at most `max_retries` times
+    pub fn new(max_retries: u32) -> Self {
+        Self { max_retries }
+    }
+}
+
+#[async_trait]
+impl Middleware for RetryMiddleware {
+    async fn after_response(
+        &self,
+        ctx: &RequestContext,
+        result: &mut Result<HttpResponse>,
+    ) -> ResponseAction {
+        match retry_delay(&ctx.request, result, ctx.attempt, self.max_retries) {
+            Some((delay, reason)) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    retry = ctx.attempt + 1,
+                    delay_ms = delay.as_millis() as u64,
+                    reason = reason.as_str(),
+                    "retrying Airtable request"
+                );
+                ResponseAction::Retry { delay, reason }
+            }
+            None => ResponseAction::Continue,
+        }
+    }
+}
+
+/// Delay and reason for retrying a request, or `None` if it should not be retried
+fn retry_delay(
+    request: &HttpRequest,
+    result: &Result<HttpResponse>,
+    retries: u32,
+    max_retries: u32,
+) -> Option<(Duration, RetryReason)> {
+    if retries >= max_retries {
+        return None;
+    }
+
+    let idempotent = request.method != Method::Post;
+    let (retry_after, reason) = match result {
+        Ok(response) if response.status == 429 => (
+            response
+                .header_value("retry-after")
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .map(Duration::from_secs),
+            RetryReason::RateLimited,
+        ),
+        Ok(response) if response.status >= 500 && idempotent => (None, RetryReason::ServerError),
+        Err(Error::Http(_)) if idempotent => (None, RetryReason::Network),
+        _ => return None,
+    };
+
+    // Exponential backoff from 500ms, capped at Airtable's 30 second rate limit penalty
+    let backoff = Duration::from_millis(500u64.saturating_mul(1 << retries.min(6)));
+    Some((
+        retry_after.unwrap_or(backoff).min(Duration::from_secs(30)),
+        reason,
+    ))
+}
+
+/// Spaces out requests so no base receives more than a given number per second
+///
+/// Airtable allows 5 requests per second per base; staying under the limit
+/// avoids the 30 second penalty that follows a 429. Requests that do not target
+/// a base (such as `whoami`) share one budget.
+#[derive(Debug)]
+pub struct RateLimitMiddleware {
+    interval: Duration,
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl RateLimitMiddleware {
+    /// Allow at most `requests_per_second` requests per base
+    pub fn new(requests_per_second: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / requests_per_second.max(1),
+            next_slot: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl Middleware for RateLimitMiddleware {
+    async fn before_request(&self, ctx: &mut RequestContext) -> Result<Option<HttpResponse>> {
+        let base_id = ctx.endpoint().1.unwrap_or_default();
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            let now = Instant::now();
+            let slot = next_slot.get(&base_id).map_or(now, |slot| (*slot).max(now));
+            next_slot.insert(base_id, slot + self.interval);
+            slot
+        };
+        tokio::time::sleep_until(slot.into()).await;
+        Ok(None)
+    }
+}
+
+/// Records status, latency and retries on the current `airtable.request` span
+#[cfg(feature = "tracing")]
+#[derive(Debug, Clone, Default)]
+pub struct TracingMiddleware;
+
+#[cfg(feature = "tracing")]
+#[async_trait]
+impl Middleware for TracingMiddleware {
+    async fn after_response(
+        &self,
+        ctx: &RequestContext,
+        result: &mut Result<HttpResponse>,
+    ) -> ResponseAction {
+        let span = tracing::Span::current();
+        span.record("retries", ctx.attempt);
+        span.record("latency_ms", ctx.elapsed().as_millis() as u64);
+        if let Ok(response) = result {
+            span.record("status", response.status);
+        }
+        ResponseAction::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_policy() {
+        let get = HttpRequest::get("https://api.airtable.com/v0/appX/T");
+        let post = HttpRequest::post("https://api.airtable.com/v0/appX/T");
+        let limited = Ok(HttpResponse::new(429, Vec::new()).with_header("Retry-After", "2"));
+        let failed = Ok(HttpResponse::new(503, Vec::new()));
+
+        assert_eq!(
+            retry_delay(&post, &limited, 0, 3),
+            Some((Duration::from_secs(2), RetryReason::RateLimited))
+        );
+        assert_eq!(
+            retry_delay(&get, &failed, 2, 3),
+            Some((Duration::from_secs(2), RetryReason::ServerError))
+        );
+        assert_eq!(retry_delay(&post, &failed, 0, 3), None);
+        assert_eq!(retry_delay(&get, &limited, 3, 3), None);
+    }
+}
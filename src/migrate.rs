@@ -0,0 +1,978 @@
+//! Declarative schema migrations
+//!
+//! A [`SchemaSpec`] describes the tables of a base: their names and
+//! descriptions, and each field's name, type, options and description. It is
+//! read from TOML, YAML (with the `yaml` feature) or JSON; the JSON printed by
+//! `base schema` is a valid spec. [`SchemaSpec::plan`] compares a spec with a
+//! base's schema and lists the [`MigrationStep`]s that make the base match,
+//! and [`BaseHandle::apply_migration`] carries them out through the metadata
+//! API.
+//!
+//! Tables and fields are matched by `id` when the spec gives one, then by
+//! name. Tables and fields missing from the spec are planned for removal, so
+//! the spec must list every table, including the inverse link fields Airtable
+//! creates. Descriptions and options are only compared when the spec gives
+//! them, and only the option keys it gives are compared, in nested objects
+//! too. A link field's `linkedTableId` may name a table instead of giving its
+//! ID. When both sides of a new link are listed, the second side is made by
+//! renaming the inverse field Airtable creates for the first.
+//!
+//! The metadata API can create tables and fields and change their names and
+//! descriptions, but cannot remove tables or fields or change field types and
+//! options. Those steps are reported for making in Airtable by hand. Removing
+//! tables, fields or select choices and changing field types lose data, so a
+//! plan containing them is refused unless destructive changes are allowed.
+//! When they are, the other steps are made and the destructive ones, which are
+//! all manual, are left for making in Airtable.
+//!
+//! A step that fails stops the migration. The [`MigrationReport`] still lists
+//! the steps made before it, along with the failure and the steps left.
+
+use crate::client::BaseHandle;
+use crate::error::{Error, Result};
+use crate::models::{BaseSchema, FieldDefinition, FieldSchema, TableDefinition, TableSchema};
+use crate::schema_diff::{pair, Keyed, ID_OPTIONS};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// Desired schema of a base
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaSpec {
+    /// Every table of the base
+    pub tables: Vec<TableSpec>,
+}
+
+/// Desired table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableSpec {
+    /// Table ID, to match a table that is being renamed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Table name
+    pub name: String,
+    /// Table description (left unchanged when not given)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Fields, the first being the primary field of a new table
+    pub fields: Vec<FieldSpec>,
+}
+
+/// Desired field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldSpec {
+    /// Field ID, to match a field that is being renamed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Field name
+    pub name: String,
+    /// Field type (e.g., "singleLineText", "number", "singleSelect")
+    #[serde(rename = "type")]
+    pub field_type: String,
+    /// Field description (left unchanged when not given)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Field options; only the keys given are compared
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<Value>,
+}
+
+impl Keyed for TableSpec {
+    fn key_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+    fn key_name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Keyed for FieldSpec {
+    fn key_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+    fn key_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// One change in a [`MigrationPlan`]
+///
+/// Tables are named as they will be after the migration, except in
+/// [`MigrationStep::UpdateTable`] and [`MigrationStep::RemoveTable`]. Fields are
+/// named as they are now in [`MigrationStep::UpdateField`] and
+/// [`MigrationStep::RemoveField`], and as they will be otherwise.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum MigrationStep {
+    /// Create a table with its non-link fields
+    CreateTable {
+        table: String,
+        definition: TableDefinition,
+    },
+    /// Rename a table and/or change its description
+    UpdateTable {
+        table: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+    /// Create a field
+    CreateField {
+        table: String,
+        definition: FieldDefinition,
+    },
+    /// Rename a field and/or change its description
+    UpdateField {
+        table: String,
+        field: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+    /// Change a field's type
+    ChangeFieldType {
+        table: String,
+        field: String,
+        from: String,
+        to: String,
+    },
+    /// Add or remove select choices
+    ChangeChoices {
+        table: String,
+        field: String,
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    /// Change other field options
+    ChangeFieldOptions {
+        table: String,
+        field: String,
+        from: Value,
+        to: Value,
+    },
+    /// Remove a field
+    RemoveField { table: String, field: String },
+    /// Remove a table
+    RemoveTable { table: String },
+}
+
+impl MigrationStep {
+    /// Whether the step loses data: removing tables, fields or choices, or changing a type
+    pub fn is_destructive(&self) -> bool {
+        match self {
+            MigrationStep::ChangeChoices { removed, .. } => !removed.is_empty(),
+            MigrationStep::ChangeFieldType { .. }
+            | MigrationStep::RemoveField { .. }
+            | MigrationStep::RemoveTable { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the metadata API can make the change; other steps are made in Airtable
+    pub fn is_supported(&self) -> bool {
+        matches!(
+            self,
+            MigrationStep::CreateTable { .. }
+                | MigrationStep::UpdateTable { .. }
+                | MigrationStep::CreateField { .. }
+                | MigrationStep::UpdateField { .. }
+        )
+    }
+}
+
+impl fmt::Display for MigrationStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationStep::CreateTable { table, definition } => write!(
+                f,
+                "+ table {} ({} field(s))",
+                table,
+                definition.fields.len()
+            )?,
+            MigrationStep::UpdateTable {
+                table,
+                name,
+                description,
+            } => write!(
+                f,
+                "~ table {} {}",
+                table,
+                updates(name.as_deref(), description.is_some())
+            )?,
+            MigrationStep::CreateField { table, definition } => write!(
+                f,
+                "+ field {}.{} ({})",
+                table, definition.name, definition.field_type
+            )?,
+            MigrationStep::UpdateField {
+                table,
+                field,
+                name,
+                description,
+            } => write!(
+                f,
+                "~ field {}.{} {}",
+                table,
+                field,
+                updates(name.as_deref(), description.is_some())
+            )?,
+            MigrationStep::ChangeFieldType {
+                table,
+                field,
+                from,
+                to,
+            } => write!(f, "~ field {}.{} type {} → {}", table, field, from, to)?,
+            MigrationStep::ChangeChoices {
+                table,
+                field,
+                added,
+                removed,
+            } => {
+                write!(f, "~ field {}.{} choices", table, field)?;
+                for choice in added {
+                    write!(f, " +{:?}", choice)?;
+                }
+                for choice in removed {
+                    write!(f, " -{:?}", choice)?;
+                }
+            }
+            MigrationStep::ChangeFieldOptions {
+                table,
+                field,
+                from,
+                to,
+            } => write!(f, "~ field {}.{} options {} → {}", table, field, from, to)?,
+            MigrationStep::RemoveField { table, field } => {
+                write!(f, "- field {}.{}", table, field)?
+            }
+            MigrationStep::RemoveTable { table } => write!(f, "- table {}", table)?,
+        }
+
+        match (self.is_destructive(), self.is_supported()) {
+            (true, false) => write!(f, " [destructive, manual]"),
+            (false, false) => write!(f, " [manual]"),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// "renamed to X, description updated"
+fn updates(name: Option<&str>, description: bool) -> String {
+    let mut parts = Vec::new();
+    if let Some(name) = name {
+        parts.push(format!("renamed to {}", name));
+    }
+    if description {
+        parts.push("description updated".to_string());
+    }
+    parts.join(", ")
+}
+
+/// Steps that make a base match a [`SchemaSpec`], see [`SchemaSpec::plan`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MigrationPlan {
+    /// Steps in the order they are applied; removals come last
+    pub steps: Vec<MigrationStep>,
+}
+
+impl MigrationPlan {
+    /// Whether the base already matches the spec
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Steps that lose data
+    pub fn destructive(&self) -> impl Iterator<Item = &MigrationStep> {
+        self.steps.iter().filter(|step| step.is_destructive())
+    }
+
+    /// Steps the metadata API cannot make
+    pub fn manual(&self) -> impl Iterator<Item = &MigrationStep> {
+        self.steps.iter().filter(|step| !step.is_supported())
+    }
+}
+
+impl Serialize for MigrationPlan {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Step<'a> {
+            #[serde(flatten)]
+            step: &'a MigrationStep,
+            destructive: bool,
+            manual: bool,
+        }
+        #[derive(Serialize)]
+        struct Plan<'a> {
+            steps: Vec<Step<'a>>,
+        }
+
+        Plan {
+            steps: self
+                .steps
+                .iter()
+                .map(|step| Step {
+                    step,
+                    destructive: step.is_destructive(),
+                    manual: !step.is_supported(),
+                })
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl fmt::Display for MigrationPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.steps.is_empty() {
+            return writeln!(f, "No changes");
+        }
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+        writeln!(
+            f,
+            "{} change(s), {} destructive, {} to make in Airtable",
+            self.steps.len(),
+            self.destructive().count(),
+            self.manual().count()
+        )
+    }
+}
+
+/// A step the metadata API rejected, see [`MigrationReport::failed`]
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationFailure {
+    /// The step being made
+    pub step: MigrationStep,
+    /// What went wrong
+    pub error: String,
+}
+
+/// Outcome of [`BaseHandle::apply_migration`]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationReport {
+    /// Steps made through the metadata API
+    pub applied: Vec<MigrationStep>,
+    /// Steps left to make in Airtable
+    pub manual: Vec<MigrationStep>,
+    /// The step that stopped the migration, if one failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed: Option<MigrationFailure>,
+    /// Steps not attempted because an earlier step failed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<MigrationStep>,
+}
+
+impl fmt::Display for MigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Applied {} change(s)", self.applied.len())?;
+        for step in &self.applied {
+            writeln!(f, "  {}", step)?;
+        }
+        if let Some(failed) = &self.failed {
+            writeln!(f, "Failed: {}: {}", failed.step, failed.error)?;
+        }
+        if !self.skipped.is_empty() {
+            writeln!(f, "{} change(s) not attempted:", self.skipped.len())?;
+            for step in &self.skipped {
+                writeln!(f, "  {}", step)?;
+            }
+        }
+        if !self.manual.is_empty() {
+            writeln!(
+                f,
+                "{} change(s) must be made in Airtable:",
+                self.manual.len()
+            )?;
+            for step in &self.manual {
+                writeln!(f, "  {}", step)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SchemaSpec {
+    /// Read a spec from a `.toml`, `.yaml`/`.yml` (with the `yaml` feature) or JSON file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            Error::config(format!("Cannot read schema file {}: {}", path.display(), e))
+        })?;
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let parsed = match extension {
+            "toml" => toml::from_str(&text).map_err(|e| e.to_string()),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => serde_norway::from_str(&text).map_err(|e| e.to_string()),
+            #[cfg(not(feature = "yaml"))]
+            "yaml" | "yml" => Err(
+                "YAML schema files require rsairtable to be built with the `yaml` feature"
+                    .to_string(),
+            ),
+            _ => serde_json::from_str(&text).map_err(|e| e.to_string()),
+        };
+        parsed.map_err(|e| Error::config(format!("Invalid schema file {}: {}", path.display(), e)))
+    }
+
+    /// Steps that turn `live` into this spec
+    pub fn plan(&self, live: &BaseSchema) -> MigrationPlan {
+        let (tables, removed) = pair(&live.tables, &self.tables);
+        let linked = LinkTargets::new(live, &tables);
+        let mut steps = Vec::new();
+
+        for (live_table, spec) in &tables {
+            let Some(live_table) = live_table else {
+                let fields = spec.fields.iter().filter(|f| !f.is_link());
+                steps.push(MigrationStep::CreateTable {
+                    table: spec.name.clone(),
+                    definition: TableDefinition {
+                        name: spec.name.clone(),
+                        description: spec.description.clone(),
+                        fields: fields.map(|f| f.definition(&linked)).collect(),
+                    },
+                });
+                continue;
+            };
+            let name = (live_table.name != spec.name).then(|| spec.name.clone());
+            let description = changed(&live_table.description, &spec.description);
+            if name.is_some() || description.is_some() {
+                steps.push(MigrationStep::UpdateTable {
+                    table: live_table.name.clone(),
+                    name,
+                    description,
+                });
+            }
+        }
+
+        for (live_table, spec) in &tables {
+            match live_table {
+                Some(live_table) => plan_fields(live_table, spec, &linked, &mut steps),
+                None => steps.extend(spec.fields.iter().filter(|f| f.is_link()).map(|field| {
+                    MigrationStep::CreateField {
+                        table: spec.name.clone(),
+                        definition: field.definition(&linked),
+                    }
+                })),
+            }
+        }
+
+        steps.extend(removed.into_iter().map(|table| MigrationStep::RemoveTable {
+            table: table.name.clone(),
+        }));
+        MigrationPlan { steps }
+    }
+}
+
+impl FieldSpec {
+    fn is_link(&self) -> bool {
+        self.field_type == "multipleRecordLinks"
+    }
+
+    /// Definition for creating the field, with the linked table's ID where it exists
+    fn definition(&self, linked: &LinkTargets) -> FieldDefinition {
+        let mut options = self.options.clone();
+        if let Some(Value::Object(map)) = &mut options {
+            map.retain(|key, _| !ID_OPTIONS.contains(&key.as_str()));
+            if let Some(Value::String(table)) = map.get_mut("linkedTableId") {
+                *table = linked.resolve(table);
+            }
+        }
+        FieldDefinition {
+            name: self.name.clone(),
+            field_type: self.field_type.clone(),
+            description: self.description.clone(),
+            options,
+        }
+    }
+}
+
+/// Table IDs for link options that name a table
+struct LinkTargets {
+    /// Spec table names and IDs, and live table IDs, mapped to live table IDs
+    ids: HashMap<String, String>,
+}
+
+impl LinkTargets {
+    fn new(live: &BaseSchema, tables: &[(Option<&TableSchema>, &TableSpec)]) -> Self {
+        let mut ids: HashMap<String, String> = live
+            .tables
+            .iter()
+            .map(|t| (t.id.clone(), t.id.clone()))
+            .collect();
+        for (live_table, spec) in tables {
+            if let Some(live_table) = live_table {
+                ids.insert(spec.name.clone(), live_table.id.clone());
+                if let Some(id) = &spec.id {
+                    ids.insert(id.clone(), live_table.id.clone());
+                }
+            }
+        }
+        Self { ids }
+    }
+
+    /// The live ID of a table, or the name of a table the migration creates
+    fn resolve(&self, table: &str) -> String {
+        self.ids
+            .get(table)
+            .cloned()
+            .unwrap_or_else(|| table.to_string())
+    }
+}
+
+/// The spec's value when it gives one that differs
+fn changed(live: &Option<String>, spec: &Option<String>) -> Option<String> {
+    spec.as_ref()
+        .filter(|spec| live.as_ref() != Some(*spec))
+        .cloned()
+}
+
+fn plan_fields(
+    live: &TableSchema,
+    spec: &TableSpec,
+    linked: &LinkTargets,
+    steps: &mut Vec<MigrationStep>,
+) {
+    let table = &spec.name;
+    let (fields, removed) = pair(&live.fields, &spec.fields);
+
+    for (live_field, field) in fields {
+        let Some(live_field) = live_field else {
+            steps.push(MigrationStep::CreateField {
+                table: table.clone(),
+                definition: field.definition(linked),
+            });
+            continue;
+        };
+        let name = (live_field.name != field.name).then(|| field.name.clone());
+        let description = changed(&live_field.description, &field.description);
+        if name.is_some() || description.is_some() {
+            steps.push(MigrationStep::UpdateField {
+                table: table.clone(),
+                field: live_field.name.clone(),
+                name,
+                description,
+            });
+        }
+        if live_field.field_type != field.field_type {
+            steps.push(MigrationStep::ChangeFieldType {
+                table: table.clone(),
+                field: field.name.clone(),
+                from: live_field.field_type.clone(),
+                to: field.field_type.clone(),
+            });
+        }
+        if let Some(Value::Object(options)) = &field.options {
+            plan_options(table, live_field, field, options, linked, steps);
+        }
+    }
+
+    steps.extend(removed.into_iter().map(|field| MigrationStep::RemoveField {
+        table: table.clone(),
+        field: field.name.clone(),
+    }));
+}
+
+/// Compare the option keys the spec gives with the live field's options
+fn plan_options(
+    table: &str,
+    live: &FieldSchema,
+    field: &FieldSpec,
+    options: &Map<String, Value>,
+    linked: &LinkTargets,
+    steps: &mut Vec<MigrationStep>,
+) {
+    let live_options = live.options.as_ref();
+
+    if let Some(choices) = options.get("choices") {
+        let wanted = choice_names(Some(choices));
+        let current = choice_names(live_options.and_then(|o| o.get("choices")));
+        let added: Vec<String> = wanted
+            .iter()
+            .filter(|c| !current.contains(c))
+            .cloned()
+            .collect();
+        let removed: Vec<String> = current
+            .iter()
+            .filter(|c| !wanted.contains(c))
+            .cloned()
+            .collect();
+        if !added.is_empty() || !removed.is_empty() {
+            steps.push(MigrationStep::ChangeChoices {
+                table: table.to_string(),
+                field: field.name.clone(),
+                added,
+                removed,
+            });
+        }
+    }
+
+    let (mut from, mut to) = (Map::new(), Map::new());
+    for (key, value) in options {
+        if key == "choices" || ID_OPTIONS.contains(&key.as_str()) {
+            continue;
+        }
+        let wanted = match (key.as_str(), value) {
+            ("linkedTableId", Value::String(table)) => Value::from(linked.resolve(table)),
+            _ => value.clone(),
+        };
+        let current = live_options
+            .and_then(|o| o.get(key))
+            .cloned()
+            .unwrap_or(Value::Null);
+        if !has_options(&current, &wanted) {
+            from.insert(key.clone(), current);
+            to.insert(key.clone(), value.clone());
+        }
+    }
+    if !to.is_empty() {
+        steps.push(MigrationStep::ChangeFieldOptions {
+            table: table.to_string(),
+            field: field.name.clone(),
+            from: Value::Object(from),
+            to: Value::Object(to),
+        });
+    }
+}
+
+/// Whether `live` matches `wanted`, where objects only need the keys `wanted` gives
+fn has_options(live: &Value, wanted: &Value) -> bool {
+    match (live, wanted) {
+        (Value::Object(live), Value::Object(wanted)) => wanted
+            .iter()
+            .all(|(key, value)| has_options(live.get(key).unwrap_or(&Value::Null), value)),
+        _ => live == wanted,
+    }
+}
+
+fn choice_names(choices: Option<&Value>) -> Vec<String> {
+    choices
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|choice| choice.get("name").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect()
+}
+
+impl BaseHandle {
+    /// Make this base match `spec` through the metadata API
+    ///
+    /// The plan is computed from the base's current schema and applied with
+    /// [`BaseHandle::apply_plan`].
+    pub async fn apply_migration(
+        &self,
+        spec: &SchemaSpec,
+        allow_destructive: bool,
+    ) -> Result<MigrationReport> {
+        let live = self.schema().await?;
+        let plan = spec.plan(&live);
+        self.apply_plan(&live, plan, allow_destructive).await
+    }
+
+    /// Make the steps of `plan`, computed from the `live` schema of this base
+    ///
+    /// A plan with destructive steps is refused, with nothing applied, unless
+    /// `allow_destructive` is set. Steps the metadata API cannot make are
+    /// returned in [`MigrationReport::manual`]. A failing step stops the
+    /// migration: check [`MigrationReport::failed`].
+    pub async fn apply_plan(
+        &self,
+        live: &BaseSchema,
+        plan: MigrationPlan,
+        allow_destructive: bool,
+    ) -> Result<MigrationReport> {
+        let destructive: Vec<String> = plan.destructive().map(|s| s.to_string()).collect();
+        if !destructive.is_empty() && !allow_destructive {
+            return Err(Error::config(format!(
+                "Refusing to apply a plan with {} destructive change(s) without allowing them:\n  {}",
+                destructive.len(),
+                destructive.join("\n  ")
+            )));
+        }
+
+        let mut ids = AppliedIds {
+            tables: live
+                .tables
+                .iter()
+                .map(|t| (t.name.clone(), t.id.clone()))
+                .collect(),
+            inverses: HashMap::new(),
+        };
+        let mut report = MigrationReport::default();
+
+        let mut steps = plan.steps.into_iter();
+        while let Some(step) = steps.next() {
+            if !step.is_supported() {
+                report.manual.push(step);
+                continue;
+            }
+            if let Err(e) = self.apply_step(live, &mut ids, &step).await {
+                report.failed = Some(MigrationFailure {
+                    step,
+                    error: e.to_string(),
+                });
+                for step in steps.by_ref() {
+                    if step.is_supported() {
+                        report.skipped.push(step);
+                    } else {
+                        report.manual.push(step);
+                    }
+                }
+                break;
+            }
+            report.applied.push(step);
+        }
+        Ok(report)
+    }
+
+    async fn apply_step(
+        &self,
+        live: &BaseSchema,
+        ids: &mut AppliedIds,
+        step: &MigrationStep,
+    ) -> Result<()> {
+        match step {
+            MigrationStep::CreateTable { table, definition } => {
+                let created = self.create_table(definition.clone()).await?;
+                ids.tables.insert(table.clone(), created.id);
+            }
+            MigrationStep::UpdateTable {
+                table,
+                name,
+                description,
+            } => {
+                let id = table_id(&ids.tables, table)?;
+                self.update_table(&id, name.as_deref(), description.as_deref())
+                    .await?;
+                ids.tables
+                    .insert(name.clone().unwrap_or_else(|| table.clone()), id);
+            }
+            MigrationStep::CreateField { table, definition } => {
+                let mut definition = definition.clone();
+                if let Some(Value::String(linked)) = definition
+                    .options
+                    .as_mut()
+                    .and_then(|o| o.get_mut("linkedTableId"))
+                {
+                    if let Some(id) = ids.tables.get(linked.as_str()) {
+                        *linked = id.clone();
+                    }
+                }
+                let id = table_id(&ids.tables, table)?;
+                let linked = definition
+                    .options
+                    .as_ref()
+                    .and_then(|o| o.get("linkedTableId"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+
+                // The other side of a link created earlier already exists
+                if let Some(inverse) = linked
+                    .as_ref()
+                    .and_then(|linked| ids.inverses.remove(&(id.clone(), linked.clone())))
+                {
+                    self.table(&id)
+                        .update_field(
+                            &inverse,
+                            Some(&definition.name),
+                            definition.description.as_deref(),
+                        )
+                        .await?;
+                    return Ok(());
+                }
+
+                let created = self
+                    .table(&id)
+                    .create_field(serde_json::to_value(&definition)?)
+                    .await?;
+                let inverse = created
+                    .options
+                    .as_ref()
+                    .and_then(|o| o.get("inverseLinkFieldId"))
+                    .and_then(Value::as_str);
+                if let (Some(linked), Some(inverse)) = (linked, inverse) {
+                    ids.inverses.insert((linked, id), inverse.to_string());
+                }
+            }
+            MigrationStep::UpdateField {
+                table,
+                field,
+                name,
+                description,
+            } => {
+                let id = table_id(&ids.tables, table)?;
+                let field_id = live
+                    .tables
+                    .iter()
+                    .find(|t| t.id == id)
+                    .and_then(|t| t.fields.iter().find(|f| &f.name == field))
+                    .map(|f| f.id.clone())
+                    .ok_or_else(|| Error::config(format!("Unknown field {}.{}", table, field)))?;
+                self.table(&id)
+                    .update_field(&field_id, name.as_deref(), description.as_deref())
+                    .await?;
+            }
+            _ => return Err(Error::config(format!("{} must be made in Airtable", step))),
+        }
+        Ok(())
+    }
+}
+
+/// IDs learned while applying a plan
+struct AppliedIds {
+    /// Table IDs by the name each table has after the migration
+    tables: HashMap<String, String>,
+    /// Inverse link field IDs by (table holding the inverse, linked table)
+    inverses: HashMap<(String, String), String>,
+}
+
+fn table_id(table_ids: &HashMap<String, String>, table: &str) -> Result<String> {
+    table_ids
+        .get(table)
+        .cloned()
+        .ok_or_else(|| Error::table_not_found(table))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_plan_orders_steps_and_flags_destructive_changes() {
+        let live: BaseSchema = serde_json::from_value(json!({ "tables": [
+            { "id": "tbl1", "name": "Cases", "primaryFieldId": "fld1",
+              "fields": [
+                { "id": "fld1", "name": "Name", "type": "singleLineText" },
+                { "id": "fld2", "name": "Status", "type": "singleSelect",
+                  "options": { "choices": [{ "id": "sel1", "name": "Open", "color": "blueLight2" },
+                                           { "id": "sel2", "name": "Closed" }] } },
+                { "id": "fld3", "name": "Hours", "type": "number", "options": { "precision": 1 } },
+                { "id": "fld4", "name": "Old", "type": "singleLineText" }
+              ],
+              "views": [] },
+            { "id": "tbl2", "name": "Archive", "primaryFieldId": "fld9",
+              "fields": [{ "id": "fld9", "name": "Name", "type": "singleLineText" }], "views": [] }
+        ]}))
+        .unwrap();
+        let spec: SchemaSpec = toml::from_str(
+            r#"
+            [[tables]]
+            id = "tbl1"
+            name = "Matters"
+            description = "Open and closed matters"
+
+            [[tables.fields]]
+            name = "Name"
+            type = "singleLineText"
+
+            [[tables.fields]]
+            name = "Status"
+            type = "singleSelect"
+            options = { choices = [{ name = "Open" }, { name = "Appealed" }] }
+
+            [[tables.fields]]
+            name = "Hours"
+            type = "currency"
+            options = { precision = 1 }
+
+            [[tables.fields]]
+            name = "Client"
+            type = "multipleRecordLinks"
+            options = { linkedTableId = "Clients" }
+
+            [[tables]]
+            name = "Clients"
+
+            [[tables.fields]]
+            name = "Name"
+            type = "singleLineText"
+
+            [[tables.fields]]
+            name = "Matters"
+            type = "multipleRecordLinks"
+            options = { linkedTableId = "Matters" }
+            "#,
+        )
+        .unwrap();
+
+        let plan = spec.plan(&live);
+        let lines: Vec<String> = plan.steps.iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            lines,
+            [
+                "~ table Cases renamed to Matters, description updated",
+                "+ table Clients (1 field(s))",
+                "~ field Matters.Status choices +\"Appealed\" -\"Closed\" [destructive, manual]",
+                "~ field Matters.Hours type number → currency [destructive, manual]",
+                "+ field Matters.Client (multipleRecordLinks)",
+                "- field Matters.Old [destructive, manual]",
+                "+ field Clients.Matters (multipleRecordLinks)",
+                "- table Archive [destructive, manual]",
+            ]
+        );
+        // Existing tables are linked by ID; new ones by name until they are created
+        let MigrationStep::CreateField { definition, .. } = &plan.steps[4] else {
+            panic!("expected a field")
+        };
+        assert_eq!(
+            definition.options,
+            Some(json!({ "linkedTableId": "Clients" }))
+        );
+        let MigrationStep::CreateField { definition, .. } = &plan.steps[6] else {
+            panic!("expected a field")
+        };
+        assert_eq!(definition.options, Some(json!({ "linkedTableId": "tbl1" })));
+        assert_eq!(plan.destructive().count(), 4);
+
+        // The base's own schema is a spec with nothing to change
+        let own: SchemaSpec = serde_json::from_value(json!(live)).unwrap();
+        assert!(own.plan(&live).is_empty());
+    }
+
+    #[test]
+    fn test_plan_compares_only_the_nested_option_keys_given() {
+        let live: BaseSchema = serde_json::from_value(json!({ "tables": [
+            { "id": "tbl1", "name": "Cases", "primaryFieldId": "fld1",
+              "fields": [
+                { "id": "fld1", "name": "Opened", "type": "date",
+                  "options": { "dateFormat": { "name": "iso", "format": "YYYY-MM-DD" } } }
+              ],
+              "views": [] }
+        ]}))
+        .unwrap();
+        let spec = |format: &str| -> SchemaSpec {
+            toml::from_str(&format!(
+                r#"
+                [[tables]]
+                name = "Cases"
+
+                [[tables.fields]]
+                name = "Opened"
+                type = "date"
+                options = {{ dateFormat = {{ name = "{}" }} }}
+                "#,
+                format
+            ))
+            .unwrap()
+        };
+
+        assert!(spec("iso").plan(&live).is_empty());
+        let lines: Vec<String> = spec("us")
+            .plan(&live)
+            .steps
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                r#"~ field Cases.Opened options {"dateFormat":{"format":"YYYY-MM-DD","name":"iso"}} → {"dateFormat":{"name":"us"}} [manual]"#
+            ]
+        );
+    }
+}
@@ -33,6 +33,19 @@ pub struct ListRecordsResponse {
     pub offset: Option<String>,
 }
 
+/// Response from an upsert (`performUpsert`) call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpsertRecordsResponse {
+    /// Created and updated records, in request order
+    pub records: Vec<Record>,
+    /// IDs of records that were created
+    #[serde(rename = "createdRecords", default)]
+    pub created_records: Vec<String>,
+    /// IDs of existing records that were updated
+    #[serde(rename = "updatedRecords", default)]
+    pub updated_records: Vec<String>,
+}
+
 /// Request body for creating/updating records
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateRecordRequest {
@@ -98,6 +111,9 @@ pub struct TableSchema {
     pub id: String,
     /// Table name
     pub name: String,
+    /// Table description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
     /// Primary field ID
     #[serde(rename = "primaryFieldId")]
     pub primary_field_id: String,
@@ -125,6 +141,39 @@ pub struct FieldSchema {
     pub description: Option<String>,
 }
 
+/// Field types whose values Airtable computes; they cannot be written or created
+pub(crate) const COMPUTED_FIELD_TYPES: [&str; 14] = [
+    "formula",
+    "rollup",
+    "lookup",
+    "multipleLookupValues",
+    "count",
+    "autoNumber",
+    "autonumber",
+    "createdTime",
+    "lastModifiedTime",
+    "createdBy",
+    "lastModifiedBy",
+    "button",
+    "externalSyncSource",
+    "aiText",
+];
+
+impl FieldSchema {
+    /// Whether Airtable computes this field's value (formulas, rollups, lookups, ...)
+    pub fn is_computed(&self) -> bool {
+        COMPUTED_FIELD_TYPES.contains(&self.field_type.as_str())
+    }
+
+    /// Table a `multipleRecordLinks` field links to
+    pub fn linked_table_id(&self) -> Option<&str> {
+        if self.field_type != "multipleRecordLinks" {
+            return None;
+        }
+        self.options.as_ref()?.get("linkedTableId")?.as_str()
+    }
+}
+
 /// View schema definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ViewSchema {
@@ -227,4 +276,500 @@ pub struct BasesResponse {
     /// Pagination offset (if any)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub offset: Option<String>,
-}
\ No newline at end of file
+}
+
+/// Table definition used when creating a base
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableDefinition {
+    /// Table name
+    pub name: String,
+    /// Table description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Field definitions (the first field becomes the primary field)
+    pub fields: Vec<FieldDefinition>,
+}
+
+impl TableDefinition {
+    /// Create a table definition with the given name and fields
+    pub fn new<S: Into<String>>(name: S, fields: Vec<FieldDefinition>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            fields,
+        }
+    }
+
+    /// Set table description
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// Field definition used when creating a base or table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDefinition {
+    /// Field name
+    pub name: String,
+    /// Field type (e.g., "singleLineText", "number", "singleSelect")
+    #[serde(rename = "type")]
+    pub field_type: String,
+    /// Field description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Field options (required by some field types, e.g. select choices or number precision)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<serde_json::Value>,
+}
+
+impl FieldDefinition {
+    /// Create a field definition with the given name and type
+    pub fn new<S: Into<String>, T: Into<String>>(name: S, field_type: T) -> Self {
+        Self {
+            name: name.into(),
+            field_type: field_type.into(),
+            description: None,
+            options: None,
+        }
+    }
+
+    /// Set field description
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set field options
+    pub fn with_options(mut self, options: serde_json::Value) -> Self {
+        self.options = Some(options);
+        self
+    }
+}
+
+/// Request body for creating a base
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateBaseRequest {
+    /// Base name
+    pub name: String,
+    /// Workspace to create the base in
+    #[serde(rename = "workspaceId")]
+    pub workspace_id: String,
+    /// Tables to create in the base
+    pub tables: Vec<TableDefinition>,
+}
+
+/// Response from base creation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedBase {
+    /// New base ID
+    pub id: String,
+    /// Schemas of the tables that were created
+    pub tables: Vec<TableSchema>,
+}
+
+/// Base metadata with collaborators, invite links and interfaces
+///
+/// Returned by `GET /meta/bases/{baseId}` when the `include` query parameter
+/// requests `collaborators`, `inviteLinks` and `interfaces`. This endpoint is
+/// only available to Enterprise plan tokens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseCollaborators {
+    /// Base ID
+    pub id: String,
+    /// Base name
+    pub name: String,
+    /// Permission level for current user
+    #[serde(rename = "permissionLevel")]
+    pub permission_level: String,
+    /// Workspace containing the base
+    #[serde(rename = "workspaceId", skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<String>,
+    /// Base creation timestamp
+    #[serde(rename = "createdTime", skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<DateTime<Utc>>,
+    /// Sensitivity label applied to the base (if any)
+    #[serde(rename = "sensitivityLabel", skip_serializing_if = "Option::is_none")]
+    pub sensitivity_label: Option<serde_json::Value>,
+    /// Users with access, granted directly on the base or via its workspace
+    #[serde(rename = "individualCollaborators", default)]
+    pub individual_collaborators: CollaboratorGrants<IndividualCollaborator>,
+    /// Groups with access, granted directly on the base or via its workspace
+    #[serde(rename = "groupCollaborators", default)]
+    pub group_collaborators: CollaboratorGrants<GroupCollaborator>,
+    /// Invite links for the base and its workspace
+    #[serde(rename = "inviteLinks", default)]
+    pub invite_links: InviteLinks,
+    /// Interfaces in the base, keyed by interface ID
+    #[serde(default)]
+    pub interfaces: BTreeMap<String, Interface>,
+}
+
+/// Collaborator grants split by where access was granted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+pub struct CollaboratorGrants<T> {
+    /// Collaborators added directly to the base
+    #[serde(rename = "baseCollaborators", default)]
+    pub via_base: Vec<T>,
+    /// Collaborators inherited from the workspace
+    #[serde(rename = "workspaceCollaborators", default)]
+    pub via_workspace: Vec<T>,
+}
+
+impl<T> Default for CollaboratorGrants<T> {
+    fn default() -> Self {
+        Self {
+            via_base: Vec::new(),
+            via_workspace: Vec::new(),
+        }
+    }
+}
+
+/// A user with access to a base, workspace or interface
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndividualCollaborator {
+    /// User ID
+    #[serde(rename = "userId")]
+    pub user_id: String,
+    /// User email address
+    pub email: String,
+    /// Permission level granted to the user
+    #[serde(rename = "permissionLevel")]
+    pub permission_level: String,
+    /// When access was granted
+    #[serde(rename = "createdTime", skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<DateTime<Utc>>,
+    /// User who granted access
+    #[serde(rename = "grantedByUserId", skip_serializing_if = "Option::is_none")]
+    pub granted_by_user_id: Option<String>,
+}
+
+/// A user group with access to a base, workspace or interface
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupCollaborator {
+    /// Group ID
+    #[serde(rename = "groupId")]
+    pub group_id: String,
+    /// Group name
+    pub name: String,
+    /// Permission level granted to the group
+    #[serde(rename = "permissionLevel")]
+    pub permission_level: String,
+    /// When access was granted
+    #[serde(rename = "createdTime", skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<DateTime<Utc>>,
+    /// User who granted access
+    #[serde(rename = "grantedByUserId", skip_serializing_if = "Option::is_none")]
+    pub granted_by_user_id: Option<String>,
+}
+
+/// Invite links split by where they were created
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InviteLinks {
+    /// Invite links created on the base
+    #[serde(rename = "baseInviteLinks", default)]
+    pub via_base: Vec<InviteLink>,
+    /// Invite links created on the workspace
+    #[serde(rename = "workspaceInviteLinks", default)]
+    pub via_workspace: Vec<InviteLink>,
+}
+
+/// Invite link to a base, workspace or interface
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteLink {
+    /// Invite link ID
+    pub id: String,
+    /// Link type (e.g., "multiUse", "singleUse")
+    #[serde(rename = "type")]
+    pub link_type: String,
+    /// Invite link creation timestamp
+    #[serde(rename = "createdTime", skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<DateTime<Utc>>,
+    /// Email address the link was sent to (single-use links only)
+    #[serde(rename = "invitedEmail", skip_serializing_if = "Option::is_none")]
+    pub invited_email: Option<String>,
+    /// User who created the link
+    #[serde(rename = "referredByUserId", skip_serializing_if = "Option::is_none")]
+    pub referred_by_user_id: Option<String>,
+    /// Permission level granted by the link
+    #[serde(rename = "permissionLevel")]
+    pub permission_level: String,
+    /// Email domains allowed to use the link
+    #[serde(rename = "restrictedToEmailDomains", default)]
+    pub restricted_to_email_domains: Vec<String>,
+}
+
+/// Interface (page bundle) within a base and who can access it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interface {
+    /// Interface ID (e.g., "pbdXXXXXXXXXXXXXX")
+    pub id: String,
+    /// Interface name
+    pub name: String,
+    /// Interface creation timestamp
+    #[serde(rename = "createdTime", skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<DateTime<Utc>>,
+    /// When the interface was first published
+    #[serde(rename = "firstPublishTime", skip_serializing_if = "Option::is_none")]
+    pub first_publish_time: Option<DateTime<Utc>>,
+    /// Users with access to the interface
+    #[serde(rename = "individualCollaborators", default)]
+    pub individual_collaborators: Vec<IndividualCollaborator>,
+    /// Groups with access to the interface
+    #[serde(rename = "groupCollaborators", default)]
+    pub group_collaborators: Vec<GroupCollaborator>,
+    /// Invite links to the interface
+    #[serde(rename = "inviteLinks", default)]
+    pub invite_links: Vec<InviteLink>,
+}
+
+/// Enterprise account information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnterpriseInfo {
+    /// Enterprise account ID (e.g., "entXXXXXXXXXXXXXX")
+    pub id: String,
+    /// Enterprise account creation timestamp
+    #[serde(rename = "createdTime", skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<DateTime<Utc>>,
+    /// IDs of user groups in the enterprise
+    #[serde(rename = "groupIds", default)]
+    pub group_ids: Vec<String>,
+    /// IDs of users in the enterprise
+    #[serde(rename = "userIds", default)]
+    pub user_ids: Vec<String>,
+    /// IDs of workspaces in the enterprise
+    #[serde(rename = "workspaceIds", default)]
+    pub workspace_ids: Vec<String>,
+    /// Email domains verified for the enterprise
+    #[serde(rename = "emailDomains", default)]
+    pub email_domains: Vec<EnterpriseEmailDomain>,
+}
+
+/// Verified email domain of an enterprise account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnterpriseEmailDomain {
+    /// Email domain (e.g., "example.com")
+    #[serde(rename = "emailDomain")]
+    pub email_domain: String,
+    /// Whether SSO is required for users of this domain
+    #[serde(rename = "isSsoRequired", default)]
+    pub is_sso_required: bool,
+}
+
+/// A single enterprise audit log event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogEvent {
+    /// Event ID
+    pub id: String,
+    /// When the event happened
+    pub timestamp: DateTime<Utc>,
+    /// Event type (e.g., "createBase", "updatePermission")
+    pub action: String,
+    /// Who performed the action
+    pub actor: AuditLogActor,
+    /// ID of the model the action applied to
+    #[serde(rename = "modelId", skip_serializing_if = "Option::is_none")]
+    pub model_id: Option<String>,
+    /// Type of the model the action applied to (e.g., "base", "workspace")
+    #[serde(rename = "modelType", skip_serializing_if = "Option::is_none")]
+    pub model_type: Option<String>,
+    /// Event-specific details (shape depends on `action`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<serde_json::Value>,
+    /// Version of the payload format
+    #[serde(rename = "payloadVersion", skip_serializing_if = "Option::is_none")]
+    pub payload_version: Option<String>,
+    /// Where in the enterprise the event happened
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<AuditLogContext>,
+    /// Network origin of the request that caused the event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<AuditLogOrigin>,
+}
+
+/// Actor that performed an audited action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogActor {
+    /// Actor type (e.g., "user", "anonymousUser", "automation")
+    #[serde(rename = "type")]
+    pub actor_type: String,
+    /// Acting user, when the actor is a user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<AuditLogUser>,
+}
+
+/// User reference inside an audit log event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogUser {
+    /// User ID
+    pub id: String,
+    /// User email address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// User display name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Location of an audited action within the enterprise
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogContext {
+    /// ID shared by all events caused by the same action
+    #[serde(rename = "actionId", skip_serializing_if = "Option::is_none")]
+    pub action_id: Option<String>,
+    /// Enterprise account ID
+    #[serde(
+        rename = "enterpriseAccountId",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub enterprise_account_id: Option<String>,
+    /// Descendant enterprise account ID (organizations unit)
+    #[serde(
+        rename = "descendantEnterpriseAccountId",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub descendant_enterprise_account_id: Option<String>,
+    /// Workspace ID
+    #[serde(rename = "workspaceId", skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<String>,
+    /// Base ID
+    #[serde(rename = "baseId", skip_serializing_if = "Option::is_none")]
+    pub base_id: Option<String>,
+    /// Interface ID
+    #[serde(rename = "interfaceId", skip_serializing_if = "Option::is_none")]
+    pub interface_id: Option<String>,
+}
+
+/// Network origin of an audited request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogOrigin {
+    /// Client IP address
+    #[serde(rename = "ipAddress", skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    /// Client user agent
+    #[serde(rename = "userAgent", skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Session ID
+    #[serde(rename = "sessionId", skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Response from the audit log events endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogResponse {
+    /// Events in this page
+    pub events: Vec<AuditLogEvent>,
+    /// Cursors for the neighbouring pages
+    #[serde(default)]
+    pub pagination: AuditLogPagination,
+}
+
+/// Cursor pagination for audit log events
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditLogPagination {
+    /// Cursor for the next page (if any)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    /// Cursor for the previous page (if any)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<String>,
+}
+
+/// Enterprise user information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnterpriseUser {
+    /// User ID
+    pub id: String,
+    /// User email address
+    pub email: String,
+    /// User display name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Account state (e.g., "provisioned", "deactivated")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Account creation timestamp
+    #[serde(rename = "createdTime", skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<DateTime<Utc>>,
+    /// Last time the user was active
+    #[serde(rename = "lastActivityTime", skip_serializing_if = "Option::is_none")]
+    pub last_activity_time: Option<DateTime<Utc>>,
+    /// User who invited this user to Airtable
+    #[serde(
+        rename = "invitedToAirtableByUserId",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub invited_to_airtable_by_user_id: Option<String>,
+    /// Whether the user is an enterprise admin
+    #[serde(rename = "isAdmin", default)]
+    pub is_admin: bool,
+    /// Whether the user is managed (claimed) by the enterprise
+    #[serde(rename = "isManaged", default)]
+    pub is_managed: bool,
+    /// Whether SSO is required for the user
+    #[serde(rename = "isSsoRequired", default)]
+    pub is_sso_required: bool,
+    /// Whether the user has two-factor authentication enabled
+    #[serde(rename = "isTwoFactorAuthEnabled", default)]
+    pub is_two_factor_auth_enabled: bool,
+    /// Groups the user belongs to
+    #[serde(default)]
+    pub groups: Vec<serde_json::Value>,
+}
+
+/// Response from the enterprise users endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnterpriseUsersResponse {
+    /// Matching users
+    pub users: Vec<EnterpriseUser>,
+}
+
+/// Membership state used when claiming enterprise users
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClaimState {
+    /// The enterprise manages the user's account
+    Managed,
+    /// The user's account is released from enterprise management
+    Unmanaged,
+}
+
+/// Response from enterprise user management endpoints (admin grants, claims)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManageUsersResponse {
+    /// Users the operation succeeded for
+    #[serde(default)]
+    pub users: Vec<ManagedUser>,
+    /// Per-user failures
+    #[serde(default)]
+    pub errors: Vec<ManageUserError>,
+}
+
+/// User affected by an enterprise user management operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagedUser {
+    /// User ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// User email address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+/// Per-user failure from an enterprise user management operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManageUserError {
+    /// User ID (if the user was referenced by ID)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// User email (if the user was referenced by email)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Error type/code
+    #[serde(rename = "type")]
+    pub error_type: String,
+    /// Human-readable error message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
@@ -0,0 +1,494 @@
+//! Output formatting for records, schemas and other listings
+//!
+//! [`OutputFormat`] covers JSON, newline-delimited JSON, CSV, TSV, an aligned
+//! text table and YAML. The tabular formats flatten items into [`Rows`]:
+//! records get an `id` column followed by one column per field in schema
+//! order, and cells are rendered with [`cell_text`] so arrays, attachments and
+//! collaborators read naturally in a spreadsheet.
+
+use crate::csv;
+use crate::error::{Error, Result};
+use crate::models::{BaseSchema, Record};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Widest cell printed by [`OutputFormat::Table`] before truncation
+const MAX_TABLE_CELL_WIDTH: usize = 48;
+
+/// Format for printing items
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Pretty-printed JSON
+    #[default]
+    Json,
+    /// One compact JSON value per line
+    Ndjson,
+    /// Comma-separated values with a header row
+    Csv,
+    /// Tab-separated values with a header row
+    Tsv,
+    /// Aligned columns for terminals
+    Table,
+    /// YAML document
+    Yaml,
+}
+
+impl OutputFormat {
+    /// Names accepted by [`OutputFormat::from_str`]
+    pub const NAMES: [&'static str; 6] = ["json", "ndjson", "csv", "tsv", "table", "yaml"];
+
+    /// Name of the format
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "ndjson",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Tsv => "tsv",
+            OutputFormat::Table => "table",
+            OutputFormat::Yaml => "yaml",
+        }
+    }
+
+    /// Whether the format prints flattened rows and columns
+    pub fn is_tabular(&self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Csv | OutputFormat::Tsv | OutputFormat::Table
+        )
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            "csv" => Ok(OutputFormat::Csv),
+            "tsv" => Ok(OutputFormat::Tsv),
+            "table" => Ok(OutputFormat::Table),
+            "yaml" => Ok(OutputFormat::Yaml),
+            other => Err(Error::config(format!(
+                "Unknown output format '{}'. Expected one of: {}",
+                other,
+                Self::NAMES.join(", ")
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Items flattened into named columns
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Rows {
+    /// Column headers
+    pub columns: Vec<String>,
+    /// One value per column for each row
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl Rows {
+    /// Flatten records into an `id` column and one column per field
+    ///
+    /// Fields listed in `field_order` (usually the table schema's field names)
+    /// come first, in that order, if any record has them; other fields follow
+    /// alphabetically.
+    pub fn from_records(records: &[Record], field_order: &[String]) -> Self {
+        let mut fields: Vec<String> = field_order
+            .iter()
+            .filter(|name| records.iter().any(|r| r.fields.contains_key(*name)))
+            .cloned()
+            .collect();
+        for record in records {
+            for name in record.fields.keys() {
+                if !fields.contains(name) {
+                    fields.push(name.clone());
+                }
+            }
+        }
+        let ordered = field_order
+            .iter()
+            .filter(|name| fields.contains(name))
+            .count();
+        fields[ordered..].sort();
+
+        let rows = records
+            .iter()
+            .map(|record| {
+                std::iter::once(Value::String(record.id.clone()))
+                    .chain(
+                        fields
+                            .iter()
+                            .map(|name| record.fields.get(name).cloned().unwrap_or(Value::Null)),
+                    )
+                    .collect()
+            })
+            .collect();
+
+        let mut columns = vec!["id".to_string()];
+        columns.extend(fields);
+        Self { columns, rows }
+    }
+
+    /// Flatten JSON objects into one column per key, in order of first appearance
+    ///
+    /// Scalars and arrays that are not objects become a single `value` column.
+    pub fn from_values(items: &[Value]) -> Self {
+        let mut columns: Vec<String> = Vec::new();
+        for item in items {
+            match item {
+                Value::Object(map) => {
+                    for key in map.keys() {
+                        if !columns.contains(key) {
+                            columns.push(key.clone());
+                        }
+                    }
+                }
+                _ if !columns.iter().any(|c| c == "value") => columns.push("value".to_string()),
+                _ => {}
+            }
+        }
+
+        let rows = items
+            .iter()
+            .map(|item| {
+                columns
+                    .iter()
+                    .map(|column| match item {
+                        Value::Object(map) => map.get(column).cloned().unwrap_or(Value::Null),
+                        other if column == "value" => other.clone(),
+                        _ => Value::Null,
+                    })
+                    .collect()
+            })
+            .collect();
+        Self { columns, rows }
+    }
+
+    /// One row per field: table, field ID, field name, type and whether it is primary
+    pub fn from_schema(schema: &BaseSchema) -> Self {
+        let columns = ["table", "field_id", "field", "type", "primary"]
+            .map(String::from)
+            .to_vec();
+        let rows = schema
+            .tables
+            .iter()
+            .flat_map(|table| {
+                table.fields.iter().map(move |field| {
+                    vec![
+                        Value::String(table.name.clone()),
+                        Value::String(field.id.clone()),
+                        Value::String(field.name.clone()),
+                        Value::String(field.field_type.clone()),
+                        Value::Bool(field.id == table.primary_field_id),
+                    ]
+                })
+            })
+            .collect();
+        Self { columns, rows }
+    }
+
+    /// Write the rows as CSV, TSV or an aligned table
+    ///
+    /// Other formats print one JSON object per row keyed by column name.
+    pub fn write<W: Write>(&self, writer: &mut W, format: OutputFormat) -> Result<()> {
+        match format {
+            OutputFormat::Csv | OutputFormat::Tsv => {
+                let delimiter = if format == OutputFormat::Csv {
+                    ','
+                } else {
+                    '\t'
+                };
+                csv::write_row(writer, &self.columns, delimiter)?;
+                for row in &self.rows {
+                    let cells: Vec<String> = row.iter().map(cell_text).collect();
+                    csv::write_row(writer, &cells, delimiter)?;
+                }
+            }
+            OutputFormat::Table => self.write_table(writer)?,
+            _ => {
+                let objects: Vec<Value> = self
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        Value::Object(
+                            self.columns
+                                .iter()
+                                .cloned()
+                                .zip(row.iter().cloned())
+                                .collect(),
+                        )
+                    })
+                    .collect();
+                write_value(writer, format, &Value::Array(objects))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_table<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let cells: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|v| table_cell(&cell_text(v))).collect())
+            .collect();
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                cells
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .chain(std::iter::once(column.chars().count()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let line = |cells: &[String]| {
+            let padded: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| {
+                    format!("{}{}", cell, " ".repeat(width - cell.chars().count()))
+                })
+                .collect();
+            padded.join("  ").trim_end().to_string()
+        };
+
+        writeln!(writer, "{}", line(&self.columns))?;
+        let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        writeln!(writer, "{}", rule.join("  "))?;
+        for row in &cells {
+            writeln!(writer, "{}", line(row))?;
+        }
+        Ok(())
+    }
+}
+
+/// Render a field value as text for a CSV cell or table column
+///
+/// Strings are printed as is, `null` as an empty cell, attachments as their URL,
+/// collaborators as their email (or name) and other objects as JSON. Array items
+/// are joined with `", "`.
+pub fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(cell_text).collect::<Vec<_>>().join(", "),
+        Value::Object(map) => ["url", "email", "name", "text"]
+            .iter()
+            .find_map(|key| map.get(*key).and_then(Value::as_str))
+            .map(str::to_string)
+            .unwrap_or_else(|| value.to_string()),
+        other => other.to_string(),
+    }
+}
+
+/// Single-line, width-limited version of a cell for [`OutputFormat::Table`]
+fn table_cell(text: &str) -> String {
+    let text = text.replace(['\n', '\r', '\t'], " ");
+    if text.chars().count() <= MAX_TABLE_CELL_WIDTH {
+        text
+    } else {
+        let mut cut: String = text.chars().take(MAX_TABLE_CELL_WIDTH - 1).collect();
+        cut.push('…');
+        cut
+    }
+}
+
+/// Write records, flattening them into columns for the tabular formats
+///
+/// `field_order` decides the column order (see [`Rows::from_records`]). JSON
+/// prints an array of records and NDJSON one record per line.
+pub fn write_records<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    records: &[Record],
+    field_order: &[String],
+) -> Result<()> {
+    if format.is_tabular() {
+        Rows::from_records(records, field_order).write(writer, format)
+    } else {
+        write_value(writer, format, &serde_json::to_value(records)?)
+    }
+}
+
+/// Write a base schema; the tabular formats list one row per field
+pub fn write_schema<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    schema: &BaseSchema,
+) -> Result<()> {
+    match format {
+        format if format.is_tabular() => Rows::from_schema(schema).write(writer, format),
+        OutputFormat::Ndjson => write_value(writer, format, &serde_json::to_value(&schema.tables)?),
+        _ => write_value(writer, format, &serde_json::to_value(schema)?),
+    }
+}
+
+/// Write any serializable value
+///
+/// NDJSON prints each array item on its own line. The tabular formats flatten
+/// an array of objects (or a single object) with [`Rows::from_values`].
+pub fn write_value<W: Write, T: Serialize + ?Sized>(
+    writer: &mut W,
+    format: OutputFormat,
+    value: &T,
+) -> Result<()> {
+    let value = serde_json::to_value(value)?;
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *writer, &value)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Ndjson => {
+            let items = match value {
+                Value::Array(items) => items,
+                other => vec![other],
+            };
+            for item in items {
+                serde_json::to_writer(&mut *writer, &item)?;
+                writeln!(writer)?;
+            }
+        }
+        #[cfg(feature = "yaml")]
+        OutputFormat::Yaml => writer.write_all(to_yaml(&value)?.as_bytes())?,
+        #[cfg(not(feature = "yaml"))]
+        OutputFormat::Yaml => {
+            return Err(Error::config(
+                "YAML output requires rsairtable to be built with the `yaml` feature",
+            ))
+        }
+        _ => {
+            let items = match value {
+                Value::Array(items) => items,
+                other => vec![other],
+            };
+            Rows::from_values(&items).write(writer, format)?;
+        }
+    }
+    Ok(())
+}
+
+/// Render a JSON value as a YAML document
+#[cfg(feature = "yaml")]
+pub fn to_yaml(value: &Value) -> Result<String> {
+    serde_norway::to_string(value).map_err(|e| Error::Other(format!("Cannot write YAML: {}", e)))
+}
+
+/// Replace a file by writing a temporary file next to it and renaming it into place
+///
+/// Readers never see a partly written file, and a failed write leaves any
+/// existing file untouched.
+pub fn write_atomic<P: AsRef<Path>>(path: P, contents: &[u8]) -> Result<()> {
+    let path = path.as_ref();
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| Error::config(format!("'{}' is not a file path", path.display())))?;
+    let temp = path.with_file_name(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+
+    let written = std::fs::File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|()| std::fs::rename(&temp, path));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    Ok(written?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        let dir = std::env::temp_dir().join(format!("rsairtable-atomic-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cases.json");
+        std::fs::write(&path, "old").unwrap();
+
+        write_atomic(&path, b"new").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        assert!(write_atomic(dir.join("missing/cases.json"), b"x").is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_records_flatten_in_schema_order() {
+        let records: Vec<Record> = serde_json::from_value(json!([
+            { "id": "rec1", "createdTime": "2024-01-01T00:00:00Z",
+              "fields": { "Name": "Ada", "Tags": ["a", "b"], "Zeta": 1, "Alpha": true } },
+            { "id": "rec2", "createdTime": "2024-01-01T00:00:00Z",
+              "fields": { "Name": "Bob, Jr.", "Files": [{ "url": "https://x/f.pdf", "filename": "f.pdf" }] } }
+        ]))
+        .unwrap();
+        let order = vec![
+            "Name".to_string(),
+            "Files".to_string(),
+            "Missing".to_string(),
+        ];
+
+        let rows = Rows::from_records(&records, &order);
+        assert_eq!(
+            rows.columns,
+            ["id", "Name", "Files", "Alpha", "Tags", "Zeta"]
+        );
+
+        let mut out = Vec::new();
+        rows.write(&mut out, OutputFormat::Csv).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "id,Name,Files,Alpha,Tags,Zeta\nrec1,Ada,,true,\"a, b\",1\nrec2,\"Bob, Jr.\",https://x/f.pdf,,,\n"
+        );
+
+        let mut out = Vec::new();
+        rows.write(&mut out, OutputFormat::Table).unwrap();
+        let table = String::from_utf8(out).unwrap();
+        assert_eq!(
+            table.lines().next().unwrap(),
+            "id    Name      Files            Alpha  Tags  Zeta"
+        );
+        assert_eq!(
+            table.lines().nth(2).unwrap(),
+            "rec1  Ada                        true   a, b  1"
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_reads_back_ambiguous_strings() {
+        let value = json!({
+            "name": "Cases",
+            "count": 2,
+            "flags": ["yes", "12", "a: b", "null"],
+            "nested": [{ "id": "fld1", "options": {} }],
+            "empty": []
+        });
+        let yaml = to_yaml(&value).unwrap();
+        assert!(yaml.starts_with("count: 2\n"), "{}", yaml);
+        let read: Value = serde_norway::from_str(&yaml).unwrap();
+        assert_eq!(read, value);
+    }
+}
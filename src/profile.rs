@@ -0,0 +1,475 @@
+//! Named profiles stored in a TOML config file
+//!
+//! The file lives at `$XDG_CONFIG_HOME/rsairtable/config.toml` (falling back to
+//! `~/.config/rsairtable/config.toml`), or wherever `RSAIRTABLE_CONFIG` points:
+//!
+//! ```toml
+//! default_profile = "firm"
+//!
+//! [profiles.firm]
+//! token_env = "FIRM_PAT"
+//! base = "appXXXXXXXXXXXXXX"
+//! timeout_seconds = 60
+//!
+//! [profiles.firm.tables]
+//! cases = "tblYYYYYYYYYYYYYY"
+//!
+//! [profiles.firm.aliases]
+//! archive = "appZZZZZZZZZZZZZZ/Closed Cases"
+//!
+//! [profiles.firm.default_export]
+//! table = "Cases"
+//! view = "Open"
+//! output = "open-cases.csv"
+//! format = "csv"
+//!
+//! [profiles.staging]
+//! token_file = "~/.airtable/staging"
+//! endpoint_url = "https://airtable-proxy.internal/v0"
+//! max_retries = 5
+//! ```
+//!
+//! Each profile takes its token from exactly one of `token`, `token_file` or
+//! `token_env`. Unset options keep the [`Config::new`] defaults. Aliases
+//! (`name = "base"` or `name = "base/table"`, see [`crate::resolve`]) can be
+//! set per profile or in a top-level `[aliases]` table shared by all profiles.
+
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::output::OutputFormat;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the config file location
+pub const CONFIG_PATH_ENV: &str = "RSAIRTABLE_CONFIG";
+
+/// Default location of the config file, if a home directory can be found
+pub fn default_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CONFIG_PATH_ENV).filter(|p| !p.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".config")))?;
+    Some(config_dir.join("rsairtable").join("config.toml"))
+}
+
+pub(crate) fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Error text without the "Configuration error" prefix
+pub(crate) fn message(error: Error) -> String {
+    match error {
+        Error::Config(message) => message,
+        other => other.to_string(),
+    }
+}
+
+/// Where a profile's API token comes from
+#[derive(Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    /// Token stored in the config file
+    Literal(String),
+    /// File containing the token
+    File(PathBuf),
+    /// Environment variable containing the token
+    Env(String),
+}
+
+impl TokenSource {
+    /// Read the token
+    pub fn resolve(&self) -> Result<String> {
+        let token = match self {
+            TokenSource::Literal(token) => token.clone(),
+            TokenSource::File(path) => {
+                let path = expand_home(path);
+                std::fs::read_to_string(&path)
+                    .map_err(|e| {
+                        Error::config(format!("Cannot read token file {}: {}", path.display(), e))
+                    })?
+                    .trim()
+                    .to_string()
+            }
+            TokenSource::Env(var) => env::var(var)
+                .map_err(|_| Error::config(format!("Environment variable {} is not set", var)))?,
+        };
+        if token.is_empty() {
+            return Err(Error::config(format!("{} is empty", self)));
+        }
+        Ok(token)
+    }
+}
+
+/// Describes the source without revealing a literal token
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenSource::Literal(_) => write!(f, "token {}", crate::transport::REDACTED),
+            TokenSource::File(path) => write!(f, "token file {}", path.display()),
+            TokenSource::Env(var) => write!(f, "token env ${}", var),
+        }
+    }
+}
+
+impl fmt::Debug for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// What `rsairtable` exports when run without a command
+///
+/// Every record of `table` matching `view` and `formula` is written to
+/// `output`. The defaults export the whole `Cases` table to `cases.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DefaultExport {
+    /// Table name, ID or alias
+    pub table: String,
+    /// View to read records from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub view: Option<String>,
+    /// Formula records must match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formula: Option<String>,
+    /// Fields to include (all when empty)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+    /// File to write
+    pub output: PathBuf,
+    /// Output format
+    pub format: OutputFormat,
+}
+
+impl Default for DefaultExport {
+    fn default() -> Self {
+        Self {
+            table: "Cases".to_string(),
+            view: None,
+            formula: None,
+            fields: Vec::new(),
+            output: PathBuf::from("cases.json"),
+            format: OutputFormat::Json,
+        }
+    }
+}
+
+/// One named profile
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Literal API token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// File containing the API token (`~/` is expanded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_file: Option<PathBuf>,
+    /// Environment variable containing the API token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+    /// Base ID, name or alias used when a command does not name one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// Table aliases: alias → table name or ID in the default base
+    ///
+    /// An entry in [`aliases`](Self::aliases) with the same name wins.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tables: BTreeMap<String, String>,
+    /// Aliases: alias → `base` or `base/table`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+    /// API endpoint URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint_url: Option<String>,
+    /// Request timeout in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<u64>,
+    /// Maximum number of retries for failed requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+    /// Export run by `rsairtable` without a command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_export: Option<DefaultExport>,
+}
+
+impl fmt::Debug for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Profile")
+            .field("token", &self.token_source().ok())
+            .field("base", &self.base)
+            .field("tables", &self.tables)
+            .field("aliases", &self.aliases)
+            .field("endpoint_url", &self.endpoint_url)
+            .field("timeout_seconds", &self.timeout_seconds)
+            .field("max_retries", &self.max_retries)
+            .field("default_export", &self.default_export)
+            .finish()
+    }
+}
+
+impl Profile {
+    /// The configured token source; exactly one must be set
+    pub fn token_source(&self) -> Result<TokenSource> {
+        let sources: Vec<TokenSource> = [
+            self.token.clone().map(TokenSource::Literal),
+            self.token_file.clone().map(TokenSource::File),
+            self.token_env.clone().map(TokenSource::Env),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        match sources.len() {
+            1 => Ok(sources.into_iter().next().unwrap()),
+            0 => Err(Error::config(
+                "No token source: set one of token, token_file or token_env",
+            )),
+            _ => Err(Error::config(
+                "Only one of token, token_file or token_env may be set",
+            )),
+        }
+    }
+
+    /// Replace the token source
+    pub fn set_token_source(&mut self, source: TokenSource) {
+        self.token = None;
+        self.token_file = None;
+        self.token_env = None;
+        match source {
+            TokenSource::Literal(token) => self.token = Some(token),
+            TokenSource::File(path) => self.token_file = Some(path),
+            TokenSource::Env(var) => self.token_env = Some(var),
+        }
+    }
+
+    /// Check the profile without contacting Airtable, returning every problem found
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        match self.token_source() {
+            Ok(source) => {
+                if let Err(e) = source.resolve() {
+                    problems.push(message(e));
+                }
+            }
+            Err(e) => problems.push(message(e)),
+        }
+        if let Some(url) = &self.endpoint_url {
+            if url::Url::parse(url).is_err() {
+                problems.push(format!("endpoint_url '{}' is not a valid URL", url));
+            }
+        }
+        for (alias, table) in &self.tables {
+            if table.trim().is_empty() {
+                problems.push(format!("table alias '{}' has no table", alias));
+            }
+        }
+        problems
+    }
+
+    /// Build a client configuration, reading the token
+    pub fn to_config(&self) -> Result<Config> {
+        let mut config = Config::new(self.token_source()?.resolve()?);
+        if let Some(url) = &self.endpoint_url {
+            config.endpoint_url = url.clone();
+        }
+        if let Some(timeout) = self.timeout_seconds {
+            config.timeout_seconds = timeout;
+        }
+        if let Some(retries) = self.max_retries {
+            config.max_retries = retries;
+        }
+        config.default_base = self.base.clone();
+        config.table_aliases = self.tables.clone();
+        config.aliases = self.aliases.clone();
+        Ok(config)
+    }
+}
+
+/// Contents of the config file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileFile {
+    /// Profile used when none is named
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    /// Aliases shared by every profile
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+    /// Profiles by name
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl ProfileFile {
+    /// Parse config file contents
+    pub fn parse(text: &str) -> Result<Self> {
+        let file: Self = toml::from_str(text).map_err(|e| Error::config(e.to_string()))?;
+        let all_aliases = file
+            .aliases
+            .values()
+            .chain(file.profiles.values().flat_map(|p| p.aliases.values()));
+        for target in all_aliases {
+            crate::resolve::parse_alias_target(target)?;
+        }
+        if let Some(name) = &file.default_profile {
+            if !file.profiles.contains_key(name) {
+                return Err(Error::config(format!(
+                    "default_profile '{}' is not defined",
+                    name
+                )));
+            }
+        }
+        Ok(file)
+    }
+
+    /// Load a config file; a missing file has no profiles
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text)
+                .map_err(|e| Error::config(format!("{}: {}", path.display(), message(e)))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Load the config file from [`default_path`]
+    pub fn load_default() -> Result<Self> {
+        match default_path() {
+            Some(path) => Self::load(path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Write the config file, creating its directory
+    ///
+    /// Comments in an existing file are not preserved. On Unix the file is
+    /// readable only by its owner, since it may hold tokens.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let text = toml::to_string_pretty(self).map_err(|e| Error::config(e.to_string()))?;
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        std::io::Write::write_all(&mut options.open(path)?, text.as_bytes())?;
+        Ok(())
+    }
+
+    /// Look up a profile by name, or the default profile when `name` is `None`
+    ///
+    /// With no name and no `default_profile`, a file with a single profile uses it.
+    pub fn profile(&self, name: Option<&str>) -> Result<(&str, &Profile)> {
+        let name = match name.or(self.default_profile.as_deref()) {
+            Some(name) => name,
+            None if self.profiles.len() == 1 => self.profiles.keys().next().unwrap(),
+            None => return Err(Error::config("No profile named and no default_profile set")),
+        };
+        self.profiles
+            .get_key_value(name)
+            .map(|(name, profile)| (name.as_str(), profile))
+            .ok_or_else(|| Error::config(format!("Profile '{}' not found", name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = r#"
+default_profile = "firm"
+
+[profiles.firm]
+token = "patFIRM"
+base = "appFirm"
+timeout_seconds = 60
+
+[profiles.firm.tables]
+cases = "tblCases"
+
+[profiles.firm.default_export]
+view = "Open"
+format = "csv"
+
+[profiles.staging]
+token_env = "RSAIRTABLE_TEST_UNSET_TOKEN"
+endpoint_url = "http://localhost:8080/v0"
+"#;
+
+    #[test]
+    fn test_profile_to_config() {
+        let file = ProfileFile::parse(FILE).unwrap();
+        let (name, profile) = file.profile(None).unwrap();
+        assert_eq!(name, "firm");
+
+        let config = profile.to_config().unwrap();
+        assert_eq!(config.api_key, "patFIRM");
+        assert_eq!(config.timeout_seconds, 60);
+        assert_eq!(config.max_retries, 3);
+        assert_eq!(config.default_base.as_deref(), Some("appFirm"));
+        assert_eq!(config.table_aliases["cases"], "tblCases");
+        assert!(!format!("{:?}", profile).contains("patFIRM"));
+
+        let export = profile.default_export.as_ref().unwrap();
+        assert_eq!(export.table, "Cases");
+        assert_eq!(export.view.as_deref(), Some("Open"));
+        assert_eq!(export.format, OutputFormat::Csv);
+        assert_eq!(export.output, PathBuf::from("cases.json"));
+
+        let (_, staging) = file.profile(Some("staging")).unwrap();
+        assert_eq!(
+            staging.problems(),
+            vec!["Environment variable RSAIRTABLE_TEST_UNSET_TOKEN is not set"]
+        );
+        assert!(file.profile(Some("missing")).is_err());
+
+        let named = ProfileFile::parse("[profiles.a]\ntoken = \"x\"\nbase = \"Law Firm\"").unwrap();
+        assert!(named.profiles["a"].problems().is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_files() {
+        assert!(ProfileFile::parse("default_profile = \"nope\"").is_err());
+        assert!(ProfileFile::parse("[profiles.a]\ntokn = \"x\"").is_err());
+        assert!(ProfileFile::parse("[aliases]\ncases = \"appX/\"").is_err());
+
+        let both = ProfileFile::parse("[profiles.a]\ntoken = \"x\"\ntoken_env = \"Y\"").unwrap();
+        assert!(both.profiles["a"].token_source().is_err());
+    }
+
+    #[test]
+    fn test_save_round_trips() {
+        let mut file = ProfileFile::parse(FILE).unwrap();
+        let mut profile = Profile::default();
+        profile.set_token_source(TokenSource::File("~/.airtable_key".into()));
+        file.profiles.insert("new".to_string(), profile);
+
+        let path = std::env::temp_dir().join(format!(
+            "rsairtable-profile-test-{}.toml",
+            std::process::id()
+        ));
+        file.save(&path).unwrap();
+        assert_eq!(ProfileFile::load(&path).unwrap(), file);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,175 @@
+//! Resolving aliases and names to base and table IDs
+//!
+//! [`Client::resolve`] turns a friendly name into a [`Target`]. Names are tried
+//! in order as:
+//!
+//! 1. an alias from [`Config::aliases`](crate::Config::aliases), such as
+//!    `cases = "appXXX/tblYYY"` (a leading `@` is ignored);
+//! 2. a table alias of the profile's default base;
+//! 3. a base ID;
+//! 4. a base name from [`Client::bases`], exactly and then ignoring case.
+//!
+//! [`BaseHandle::resolve_table`] does the same for tables against the base
+//! schema. A name that matches more than one base or table is an error.
+
+use crate::client::{BaseHandle, Client};
+use crate::error::{Error, Result};
+use std::fmt;
+
+/// A base, and optionally a table in it, named by an alias or name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    /// Base ID
+    pub base_id: String,
+    /// Table name or ID, when the alias names a table
+    pub table: Option<String>,
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.table {
+            Some(table) => write!(f, "{}/{}", self.base_id, table),
+            None => write!(f, "{}", self.base_id),
+        }
+    }
+}
+
+/// Whether `value` looks like an Airtable ID with the given prefix
+fn is_id(value: &str, prefix: &str) -> bool {
+    value.len() == 17
+        && value.starts_with(prefix)
+        && value.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Whether `value` looks like a base ID (`appXXXXXXXXXXXXXX`)
+pub fn is_base_id(value: &str) -> bool {
+    is_id(value, "app")
+}
+
+/// Whether `value` looks like a table ID (`tblXXXXXXXXXXXXXX`)
+pub fn is_table_id(value: &str) -> bool {
+    is_id(value, "tbl")
+}
+
+/// Split an alias target `base[/table]` into its parts
+pub fn parse_alias_target(target: &str) -> Result<(&str, Option<&str>)> {
+    let (base, table) = match target.split_once('/') {
+        Some((base, table)) => (base.trim(), Some(table.trim())),
+        None => (target.trim(), None),
+    };
+    if base.is_empty() || table.is_some_and(str::is_empty) {
+        return Err(Error::config(format!(
+            "Alias target '{}' must be BASE or BASE/TABLE",
+            target
+        )));
+    }
+    Ok((base, table))
+}
+
+/// Pick the single candidate matching `name` exactly, or else ignoring case
+pub(crate) fn unique_match<'a, T>(
+    candidates: &'a [T],
+    name: &str,
+    key: impl Fn(&T) -> &str,
+) -> std::result::Result<Option<&'a T>, usize> {
+    let exact: Vec<&T> = candidates.iter().filter(|c| key(c) == name).collect();
+    let matches = if exact.is_empty() {
+        candidates
+            .iter()
+            .filter(|c| key(c).eq_ignore_ascii_case(name))
+            .collect()
+    } else {
+        exact
+    };
+    match matches.len() {
+        0 => Ok(None),
+        1 => Ok(Some(matches[0])),
+        n => Err(n),
+    }
+}
+
+impl Client {
+    /// Resolve an alias, base ID or base name to a [`Target`]
+    ///
+    /// Only base names require a request (the base list). Tables named by an
+    /// alias are returned as written; use [`BaseHandle::resolve_table`] to turn
+    /// a table name into an ID.
+    pub async fn resolve(&self, name: &str) -> Result<Target> {
+        let name = name.strip_prefix('@').unwrap_or(name);
+
+        if let Some(target) = self.config().aliases.get(name) {
+            let (base, table) = parse_alias_target(target)?;
+            return Ok(Target {
+                base_id: self.resolve_base(base).await?,
+                table: table.map(str::to_string),
+            });
+        }
+        if let (Some(table), Some(base)) = (
+            self.config().table_aliases.get(name),
+            &self.config().default_base,
+        ) {
+            return Ok(Target {
+                base_id: self.resolve_base(base).await?,
+                table: Some(table.clone()),
+            });
+        }
+        Ok(Target {
+            base_id: self.resolve_base(name).await?,
+            table: None,
+        })
+    }
+
+    /// Resolve a base ID or a unique base name to a base ID
+    async fn resolve_base(&self, name: &str) -> Result<String> {
+        if is_base_id(name) {
+            return Ok(name.to_string());
+        }
+        let bases = self.bases().await?;
+        match unique_match(&bases, name, |base| &base.name) {
+            Ok(Some(base)) => Ok(base.id.clone()),
+            Ok(None) => Err(Error::config(format!("No alias or base named '{}'", name))),
+            Err(count) => Err(Error::config(format!(
+                "{} bases are named '{}'; use the base ID",
+                count, name
+            ))),
+        }
+    }
+}
+
+impl BaseHandle {
+    /// Resolve a table ID, name or unique case-insensitive name to a table ID
+    pub async fn resolve_table(&self, name: &str) -> Result<String> {
+        let schema = self.schema().await?;
+        if let Some(table) = schema.tables.iter().find(|t| t.id == name) {
+            return Ok(table.id.clone());
+        }
+        match unique_match(&schema.tables, name, |table| &table.name) {
+            Ok(Some(table)) => Ok(table.id.clone()),
+            Ok(None) => Err(Error::table_not_found(name)),
+            Err(count) => Err(Error::config(format!(
+                "{} tables in {} are named '{}'; use the table ID",
+                count,
+                self.id(),
+                name
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_alias_target() {
+        assert_eq!(
+            parse_alias_target("appAAAAAAAAAAAAAA/Cases").unwrap(),
+            ("appAAAAAAAAAAAAAA", Some("Cases"))
+        );
+        assert_eq!(parse_alias_target("Law Firm").unwrap(), ("Law Firm", None));
+        assert!(parse_alias_target("app/").is_err());
+        assert!(is_base_id("appAAAAAAAAAAAAAA"));
+        assert!(!is_base_id("apple"));
+        assert!(is_table_id("tblAAAAAAAAAAAAAA"));
+    }
+}
@@ -0,0 +1,492 @@
+//! Comparing base schemas
+//!
+//! [`BaseSchema::diff`] lists the changes that turn one schema into another:
+//! tables, fields and views that were added, removed or renamed, field type and
+//! description changes, select choices that were added or removed, and other
+//! field option changes.
+//!
+//! Tables are paired by ID and then, among the rest, by name, so two copies of
+//! a base (whose IDs differ) compare by name while a rename within one base is
+//! recognised by its ID. Fields and views are paired the same way within each
+//! pair of tables. IDs inside field options are not compared, except that a
+//! link's `linkedTableId` is compared by the linked table's name.
+
+use crate::models::{BaseSchema, FieldSchema, TableSchema, ViewSchema};
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+/// Field options holding IDs, which differ between copies of a base
+pub(crate) const ID_OPTIONS: [&str; 5] = [
+    "inverseLinkFieldId",
+    "recordLinkFieldId",
+    "fieldIdInLinkedTable",
+    "referencedFieldIds",
+    "viewIdForRecordSelection",
+];
+
+/// One difference between two schemas
+///
+/// Tables, fields and views are named as they are in the new schema, except
+/// for removed ones.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum SchemaChange {
+    /// A table exists only in the new schema
+    TableAdded { table: String },
+    /// A table exists only in the old schema
+    TableRemoved { table: String },
+    /// A table has a new name
+    TableRenamed { from: String, to: String },
+    /// A field exists only in the new schema
+    FieldAdded {
+        table: String,
+        field: String,
+        #[serde(rename = "type")]
+        field_type: String,
+    },
+    /// A field exists only in the old schema
+    FieldRemoved { table: String, field: String },
+    /// A field has a new name
+    FieldRenamed {
+        table: String,
+        from: String,
+        to: String,
+    },
+    /// A field has a new type
+    FieldTypeChanged {
+        table: String,
+        field: String,
+        from: String,
+        to: String,
+    },
+    /// Select choices were added or removed
+    ChoicesChanged {
+        table: String,
+        field: String,
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    /// Other field options changed
+    OptionsChanged {
+        table: String,
+        field: String,
+        from: Value,
+        to: Value,
+    },
+    /// A field has a new description
+    DescriptionChanged {
+        table: String,
+        field: String,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    /// A view exists only in the new schema
+    ViewAdded {
+        table: String,
+        view: String,
+        #[serde(rename = "type")]
+        view_type: String,
+    },
+    /// A view exists only in the old schema
+    ViewRemoved { table: String, view: String },
+    /// A view has a new name
+    ViewRenamed {
+        table: String,
+        from: String,
+        to: String,
+    },
+    /// A view has a new type
+    ViewTypeChanged {
+        table: String,
+        view: String,
+        from: String,
+        to: String,
+    },
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::TableAdded { table } => write!(f, "+ table {}", table),
+            SchemaChange::TableRemoved { table } => write!(f, "- table {}", table),
+            SchemaChange::TableRenamed { from, to } => {
+                write!(f, "~ table {} renamed to {}", from, to)
+            }
+            SchemaChange::FieldAdded {
+                table,
+                field,
+                field_type,
+            } => write!(f, "+ field {}.{} ({})", table, field, field_type),
+            SchemaChange::FieldRemoved { table, field } => {
+                write!(f, "- field {}.{}", table, field)
+            }
+            SchemaChange::FieldRenamed { table, from, to } => {
+                write!(f, "~ field {}.{} renamed to {}", table, from, to)
+            }
+            SchemaChange::FieldTypeChanged {
+                table,
+                field,
+                from,
+                to,
+            } => write!(f, "~ field {}.{} type {} → {}", table, field, from, to),
+            SchemaChange::ChoicesChanged {
+                table,
+                field,
+                added,
+                removed,
+            } => {
+                write!(f, "~ field {}.{} choices", table, field)?;
+                for choice in added {
+                    write!(f, " +{:?}", choice)?;
+                }
+                for choice in removed {
+                    write!(f, " -{:?}", choice)?;
+                }
+                Ok(())
+            }
+            SchemaChange::OptionsChanged {
+                table,
+                field,
+                from,
+                to,
+            } => write!(f, "~ field {}.{} options {} → {}", table, field, from, to),
+            SchemaChange::DescriptionChanged { table, field, .. } => {
+                write!(f, "~ field {}.{} description changed", table, field)
+            }
+            SchemaChange::ViewAdded {
+                table,
+                view,
+                view_type,
+            } => write!(f, "+ view {}.{} ({})", table, view, view_type),
+            SchemaChange::ViewRemoved { table, view } => write!(f, "- view {}.{}", table, view),
+            SchemaChange::ViewRenamed { table, from, to } => {
+                write!(f, "~ view {}.{} renamed to {}", table, from, to)
+            }
+            SchemaChange::ViewTypeChanged {
+                table,
+                view,
+                from,
+                to,
+            } => write!(f, "~ view {}.{} type {} → {}", table, view, from, to),
+        }
+    }
+}
+
+/// Changes from one schema to another, see [`BaseSchema::diff`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SchemaDiff {
+    /// Changes grouped by table, in the new schema's order; removals come last
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    /// Whether the schemas match
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "No schema differences");
+        }
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        writeln!(f, "{} difference(s)", self.changes.len())
+    }
+}
+
+impl BaseSchema {
+    /// Changes that turn this schema into `new`
+    pub fn diff(&self, new: &BaseSchema) -> SchemaDiff {
+        let mut changes = Vec::new();
+        let (pairs, removed) = pair(&self.tables, &new.tables);
+
+        for (old_table, new_table) in pairs {
+            match old_table {
+                Some(old_table) => diff_table(self, old_table, new, new_table, &mut changes),
+                None => changes.push(SchemaChange::TableAdded {
+                    table: new_table.name.clone(),
+                }),
+            }
+        }
+        changes.extend(removed.into_iter().map(|table| SchemaChange::TableRemoved {
+            table: table.name.clone(),
+        }));
+        SchemaDiff { changes }
+    }
+}
+
+/// Something matched by ID, then by name, when pairing two schemas
+pub(crate) trait Keyed {
+    fn key_id(&self) -> Option<&str>;
+    fn key_name(&self) -> &str;
+}
+
+macro_rules! keyed_schema {
+    ($($schema:ty),*) => {$(
+        impl Keyed for $schema {
+            fn key_id(&self) -> Option<&str> {
+                Some(&self.id)
+            }
+            fn key_name(&self) -> &str {
+                &self.name
+            }
+        }
+    )*};
+}
+
+keyed_schema!(TableSchema, FieldSchema, ViewSchema);
+
+/// Each item of `new` with its match in `old` (by ID, then by name), and the unmatched items of `old`
+#[allow(clippy::type_complexity)]
+pub(crate) fn pair<'a, O: Keyed, N: Keyed>(
+    old: &'a [O],
+    new: &'a [N],
+) -> (Vec<(Option<&'a O>, &'a N)>, Vec<&'a O>) {
+    let mut unmatched: Vec<&O> = old.iter().collect();
+    let mut take = |found: &dyn Fn(&O) -> bool| {
+        let index = unmatched.iter().position(|item| found(item))?;
+        Some(unmatched.remove(index))
+    };
+
+    let mut pairs: Vec<(Option<&O>, &N)> = new
+        .iter()
+        .map(|item| {
+            let id = item.key_id();
+            (take(&|o: &O| id.is_some() && o.key_id() == id), item)
+        })
+        .collect();
+    for (old_item, item) in pairs.iter_mut().filter(|(old_item, _)| old_item.is_none()) {
+        *old_item = take(&|o: &O| o.key_name() == item.key_name());
+    }
+    (pairs, unmatched)
+}
+
+fn diff_table(
+    old_schema: &BaseSchema,
+    old: &TableSchema,
+    new_schema: &BaseSchema,
+    new: &TableSchema,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let table = &new.name;
+    if old.name != new.name {
+        changes.push(SchemaChange::TableRenamed {
+            from: old.name.clone(),
+            to: new.name.clone(),
+        });
+    }
+
+    let (fields, removed) = pair(&old.fields, &new.fields);
+    for (old_field, new_field) in fields {
+        let Some(old_field) = old_field else {
+            changes.push(SchemaChange::FieldAdded {
+                table: table.clone(),
+                field: new_field.name.clone(),
+                field_type: new_field.field_type.clone(),
+            });
+            continue;
+        };
+        let field = &new_field.name;
+        if old_field.name != new_field.name {
+            changes.push(SchemaChange::FieldRenamed {
+                table: table.clone(),
+                from: old_field.name.clone(),
+                to: field.clone(),
+            });
+        }
+        if old_field.field_type != new_field.field_type {
+            changes.push(SchemaChange::FieldTypeChanged {
+                table: table.clone(),
+                field: field.clone(),
+                from: old_field.field_type.clone(),
+                to: new_field.field_type.clone(),
+            });
+        }
+
+        let (old_choices, new_choices) = (choices(old_field), choices(new_field));
+        let added: Vec<String> = new_choices
+            .iter()
+            .filter(|c| !old_choices.contains(c))
+            .cloned()
+            .collect();
+        let removed: Vec<String> = old_choices
+            .iter()
+            .filter(|c| !new_choices.contains(c))
+            .cloned()
+            .collect();
+        if !added.is_empty() || !removed.is_empty() {
+            changes.push(SchemaChange::ChoicesChanged {
+                table: table.clone(),
+                field: field.clone(),
+                added,
+                removed,
+            });
+        }
+
+        let (from, to) = (
+            comparable_options(old_field, old_schema),
+            comparable_options(new_field, new_schema),
+        );
+        if from != to {
+            changes.push(SchemaChange::OptionsChanged {
+                table: table.clone(),
+                field: field.clone(),
+                from,
+                to,
+            });
+        }
+        if old_field.description != new_field.description {
+            changes.push(SchemaChange::DescriptionChanged {
+                table: table.clone(),
+                field: field.clone(),
+                from: old_field.description.clone(),
+                to: new_field.description.clone(),
+            });
+        }
+    }
+    changes.extend(removed.into_iter().map(|field| SchemaChange::FieldRemoved {
+        table: table.clone(),
+        field: field.name.clone(),
+    }));
+
+    diff_views(table, &old.views, &new.views, changes);
+}
+
+fn diff_views(
+    table: &str,
+    old: &[ViewSchema],
+    new: &[ViewSchema],
+    changes: &mut Vec<SchemaChange>,
+) {
+    let (views, removed) = pair(old, new);
+    for (old_view, new_view) in views {
+        let Some(old_view) = old_view else {
+            changes.push(SchemaChange::ViewAdded {
+                table: table.to_string(),
+                view: new_view.name.clone(),
+                view_type: new_view.view_type.clone(),
+            });
+            continue;
+        };
+        if old_view.name != new_view.name {
+            changes.push(SchemaChange::ViewRenamed {
+                table: table.to_string(),
+                from: old_view.name.clone(),
+                to: new_view.name.clone(),
+            });
+        }
+        if old_view.view_type != new_view.view_type {
+            changes.push(SchemaChange::ViewTypeChanged {
+                table: table.to_string(),
+                view: new_view.name.clone(),
+                from: old_view.view_type.clone(),
+                to: new_view.view_type.clone(),
+            });
+        }
+    }
+    changes.extend(removed.into_iter().map(|view| SchemaChange::ViewRemoved {
+        table: table.to_string(),
+        view: view.name.clone(),
+    }));
+}
+
+/// Names of a select field's choices
+fn choices(field: &FieldSchema) -> Vec<String> {
+    field
+        .options
+        .as_ref()
+        .and_then(|options| options.get("choices"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|choice| choice.get("name").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Field options without choices and IDs, with the linked table given by name
+fn comparable_options(field: &FieldSchema, schema: &BaseSchema) -> Value {
+    let mut options = field.options.clone().unwrap_or(Value::Null);
+    if let Value::Object(map) = &mut options {
+        map.remove("choices");
+        map.retain(|key, _| !ID_OPTIONS.contains(&key.as_str()));
+        if let Some(linked) = field.linked_table_id() {
+            let name = schema
+                .tables
+                .iter()
+                .find(|t| t.id == linked)
+                .map_or(linked, |t| t.name.as_str());
+            map.insert("linkedTableId".to_string(), Value::from(name));
+        }
+        if map.is_empty() {
+            return Value::Null;
+        }
+    }
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema(value: Value) -> BaseSchema {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_diff_matches_by_id_then_name() {
+        let old = schema(json!({ "tables": [
+            { "id": "tbl1", "name": "Cases", "primaryFieldId": "fld1",
+              "fields": [
+                { "id": "fld1", "name": "Name", "type": "singleLineText" },
+                { "id": "fld2", "name": "Status", "type": "singleSelect",
+                  "options": { "choices": [{ "id": "sel1", "name": "Open" }, { "id": "sel2", "name": "Closed" }] } },
+                { "id": "fld3", "name": "Hours", "type": "number", "options": { "precision": 1 } },
+                { "id": "fld4", "name": "Old", "type": "singleLineText" }
+              ],
+              "views": [{ "id": "viw1", "name": "Grid view", "type": "grid" }] },
+            { "id": "tbl2", "name": "Archive", "primaryFieldId": "fld9",
+              "fields": [{ "id": "fld9", "name": "Name", "type": "singleLineText" }], "views": [] }
+        ]}));
+        let new = schema(json!({ "tables": [
+            { "id": "tbl1", "name": "Matters", "primaryFieldId": "fld1",
+              "fields": [
+                { "id": "fld1", "name": "Title", "type": "singleLineText" },
+                { "id": "fldX", "name": "Status", "type": "singleSelect",
+                  "options": { "choices": [{ "id": "selX", "name": "Open" }, { "id": "selY", "name": "Appealed" }] } },
+                { "id": "fld3", "name": "Hours", "type": "currency", "options": { "precision": 2, "symbol": "$" } },
+                { "id": "fld5", "name": "Priority", "type": "number" }
+              ],
+              "views": [{ "id": "viw1", "name": "All", "type": "kanban" }] }
+        ]}));
+
+        let diff = old.diff(&new);
+        let lines: Vec<String> = diff.changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            lines,
+            [
+                "~ table Cases renamed to Matters",
+                "~ field Matters.Name renamed to Title",
+                "~ field Matters.Status choices +\"Appealed\" -\"Closed\"",
+                "~ field Matters.Hours type number → currency",
+                "~ field Matters.Hours options {\"precision\":1} → {\"precision\":2,\"symbol\":\"$\"}",
+                "+ field Matters.Priority (number)",
+                "- field Matters.Old",
+                "~ view Matters.Grid view renamed to All",
+                "~ view Matters.All type grid → kanban",
+                "- table Archive",
+            ]
+        );
+        assert!(old.diff(&old).is_empty());
+        assert_eq!(
+            serde_json::to_value(&diff.changes[5]).unwrap(),
+            json!({ "change": "field_added", "table": "Matters", "field": "Priority", "type": "number" })
+        );
+    }
+}
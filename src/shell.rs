@@ -0,0 +1,659 @@
+//! Interactive shell for exploring bases
+//!
+//! `rsairtable shell` keeps a current base and table and runs record and schema
+//! commands against them:
+//!
+//! ```text
+//! rsairtable> use Law Firm
+//! rsairtable:Law Firm> use Cases
+//! rsairtable:Law Firm/Cases> records -n 5 -F "Case Number" -F Status
+//! ```
+//!
+//! Navigation (`use`, `tables`, `refresh`) is handled by the shell itself. The
+//! other commands become `rsairtable` command lines for the current base and
+//! table, run by a [`CommandRunner`], so `records -n 5` takes the same options
+//! and prints the same output as `rsairtable base BASE table TABLE records -n 5`
+//! (listings default to `--format table`).
+//!
+//! Base, table, field and view names complete with Tab from the schema fetched
+//! when a base is selected (`refresh` fetches it again). [`Shell::execute`] runs
+//! one line, so the shell can be driven without a terminal; [`Shell::run`] adds
+//! line editing and a history file next to the config file. Requires the
+//! `shell` feature.
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::models::{BaseInfo, BaseSchema, TableSchema};
+use crate::output::{OutputFormat, Rows};
+use crate::resolve::parse_alias_target;
+use async_trait::async_trait;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{CompletionType, Editor, Helper};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Shell commands and a one-line description of each, for `help`
+const COMMANDS: [(&str, &str); 13] = [
+    ("bases", "List accessible bases"),
+    (
+        "use",
+        "Select a base or table: use <BASE|TABLE|@ALIAS|BASE/TABLE|..>",
+    ),
+    ("tables", "List the tables of the current base"),
+    (
+        "schema",
+        "Show the fields of the current table, or of every table",
+    ),
+    (
+        "records",
+        "List records of the current table (see 'help records')",
+    ),
+    (
+        "get",
+        "Show a record: get <RECORD_ID> [-x FIELD]... (see 'help get')",
+    ),
+    ("create", "Create a record: create {\"Field\": \"value\"}"),
+    (
+        "update",
+        "Update a record: update <RECORD_ID> {\"Field\": \"value\"}",
+    ),
+    ("delete", "Delete a record: delete <RECORD_ID>"),
+    ("refresh", "Fetch the base list and schema again"),
+    (
+        "help",
+        "List commands, or show help for one: help <COMMAND>",
+    ),
+    ("exit", "Leave the shell (also Ctrl-D)"),
+    ("quit", "Leave the shell"),
+];
+
+/// Commands run by the [`CommandRunner`], with `--help` for `help COMMAND`
+const CLI_COMMANDS: [&str; 7] = [
+    "bases", "schema", "records", "get", "create", "update", "delete",
+];
+
+/// Options that take a field name, and those that take a view name
+const FIELD_OPTIONS: [&str; 6] = ["-F", "--field", "-S", "--sort", "-x", "--expand"];
+const VIEW_OPTIONS: [&str; 2] = ["-u", "--view"];
+
+/// Names offered by tab completion, shared with the line editor
+#[derive(Debug, Default)]
+struct Names {
+    bases: Vec<String>,
+    tables: Vec<String>,
+    fields: Vec<String>,
+    views: Vec<String>,
+}
+
+impl Names {
+    /// Start of the word being completed at `pos`, and its candidates
+    fn complete(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let before = &line[..pos];
+        let start = word_start(before);
+        let typed = &before[start..];
+        let quoted = typed.starts_with(['"', '\'']);
+        let prefix = typed.trim_start_matches(['"', '\'']).to_lowercase();
+        let previous = split_words(&before[..start]).unwrap_or_default();
+
+        let candidates: Vec<&String> = match previous.first().map(String::as_str) {
+            None => {
+                return (
+                    start,
+                    COMMANDS
+                        .iter()
+                        .map(|(name, _)| name.to_string())
+                        .filter(|name| name.starts_with(&prefix))
+                        .collect(),
+                )
+            }
+            Some("use") if previous.len() == 1 => self.tables.iter().chain(&self.bases).collect(),
+            Some(_) => match previous.last().map(String::as_str) {
+                Some(option) if FIELD_OPTIONS.contains(&option) => self.fields.iter().collect(),
+                Some(option) if VIEW_OPTIONS.contains(&option) => self.views.iter().collect(),
+                _ => Vec::new(),
+            },
+        };
+
+        let mut matches: Vec<String> = candidates
+            .into_iter()
+            .filter(|name| name.to_lowercase().starts_with(&prefix))
+            .map(|name| {
+                if quoted || name.contains(char::is_whitespace) {
+                    format!("\"{}\"", name)
+                } else {
+                    name.clone()
+                }
+            })
+            .collect();
+        matches.dedup();
+        (start, matches)
+    }
+}
+
+/// Byte offset where the last (possibly quoted) word of `text` starts
+fn word_start(text: &str) -> usize {
+    let mut start = 0;
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c.is_whitespace() => start = i + c.len_utf8(),
+            None => {}
+        }
+    }
+    start
+}
+
+/// Split a command line into words, honouring single and double quotes
+///
+/// Inside double quotes and unquoted text, a backslash escapes the next
+/// character.
+fn split_words(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('\''), c) => word.get_or_insert_with(String::new).push(c),
+            (_, '\\') => {
+                if let Some(next) = chars.next() {
+                    word.get_or_insert_with(String::new).push(next);
+                }
+            }
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (_, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if let Some(q) = quote {
+        return Err(Error::config(format!("Unterminated {} quote", q)));
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Runs `rsairtable` command lines for the shell
+///
+/// The CLI runs them with its own argument parser and command code; tests can
+/// record them instead.
+#[async_trait(?Send)]
+pub trait CommandRunner {
+    /// Run one command line, without the program name, printing its output
+    async fn run(&self, args: Vec<String>) -> Result<()>;
+}
+
+/// The selected base and its schema
+#[derive(Debug)]
+struct CurrentBase {
+    id: String,
+    name: String,
+    schema: BaseSchema,
+}
+
+/// Interactive session with a current base and table
+pub struct Shell {
+    client: Client,
+    runner: Box<dyn CommandRunner>,
+    bases: Option<Vec<BaseInfo>>,
+    base: Option<CurrentBase>,
+    table: Option<String>,
+    names: Arc<Mutex<Names>>,
+}
+
+impl Shell {
+    /// Create a shell with no base selected, running commands with `runner`
+    pub fn new<R: CommandRunner + 'static>(client: Client, runner: R) -> Self {
+        Self {
+            client,
+            runner: Box::new(runner),
+            bases: None,
+            base: None,
+            table: None,
+            names: Arc::new(Mutex::new(Names::default())),
+        }
+    }
+
+    /// Prompt showing the current base and table
+    pub fn prompt(&self) -> String {
+        match (&self.base, &self.table) {
+            (Some(base), Some(table)) => format!("rsairtable:{}/{}> ", base.name, table),
+            (Some(base), None) => format!("rsairtable:{}> ", base.name),
+            _ => "rsairtable> ".to_string(),
+        }
+    }
+
+    /// Completion candidates for `line` with the cursor at byte `pos`
+    ///
+    /// Returns where the completed word starts and the words that can replace it.
+    pub fn complete(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        self.names.lock().unwrap().complete(line, pos)
+    }
+
+    /// Select a base or table, as the `use` command does
+    pub async fn use_target(&mut self, target: &str) -> Result<String> {
+        let target = target.trim();
+        if target == ".." {
+            if self.table.take().is_none() {
+                self.base = None;
+            }
+            self.update_names();
+            return Ok(String::new());
+        }
+
+        if let Some(table) = self
+            .base
+            .as_ref()
+            .and_then(|base| find_table(&base.schema, target))
+        {
+            self.table = Some(table.name.clone());
+            self.update_names();
+            return Ok(String::new());
+        }
+
+        let is_alias = self
+            .client
+            .config()
+            .aliases
+            .contains_key(target.trim_start_matches('@'));
+        let resolved = match parse_alias_target(target)? {
+            (base, Some(table)) if !is_alias => {
+                let mut resolved = self.client.resolve(base).await?;
+                resolved.table = Some(table.to_string());
+                resolved
+            }
+            _ => self.client.resolve(target).await?,
+        };
+
+        self.select_base(&resolved.base_id).await?;
+        if let Some(table) = resolved.table {
+            let schema = &self.base.as_ref().expect("base was just selected").schema;
+            let table = find_table(schema, &table).ok_or_else(|| Error::table_not_found(&table))?;
+            self.table = Some(table.name.clone());
+        }
+        self.update_names();
+        Ok(String::new())
+    }
+
+    /// Run one command line and return its output, or `None` on `exit`
+    ///
+    /// Commands run by the [`CommandRunner`] print their own output and
+    /// return an empty string.
+    pub async fn execute(&mut self, line: &str) -> Result<Option<String>> {
+        let line = line.trim();
+        let (command, rest) = line
+            .split_once(char::is_whitespace)
+            .map(|(command, rest)| (command, rest.trim()))
+            .unwrap_or((line, ""));
+
+        let output = match command {
+            "" => String::new(),
+            "exit" | "quit" => return Ok(None),
+            "help" if CLI_COMMANDS.contains(&rest) => {
+                self.runner.run(help_args(rest)).await?;
+                String::new()
+            }
+            "help" => help(rest)?,
+            "use" => {
+                let words = split_words(rest)?;
+                match words.as_slice() {
+                    [target] => self.use_target(target).await?,
+                    _ => {
+                        return Err(Error::config(
+                            "Usage: use <BASE|TABLE|@ALIAS|BASE/TABLE|..>",
+                        ))
+                    }
+                }
+            }
+            "tables" => self.list_tables()?,
+            command if CLI_COMMANDS.contains(&command) => {
+                let args = self.cli_args(command, rest)?;
+                self.runner.run(args).await?;
+                String::new()
+            }
+            "refresh" => {
+                self.bases = None;
+                self.load_bases().await?;
+                if let Some(id) = self.base.as_ref().map(|base| base.id.clone()) {
+                    let table = self.table.take();
+                    self.select_base(&id).await?;
+                    self.table = table.filter(|name| {
+                        find_table(&self.base.as_ref().unwrap().schema, name).is_some()
+                    });
+                }
+                self.update_names();
+                String::new()
+            }
+            other => {
+                return Err(Error::config(format!(
+                    "Unknown command '{}'. Type 'help' for a list of commands",
+                    other
+                )))
+            }
+        };
+        Ok(Some(output))
+    }
+
+    /// Read and run commands until `exit` or end of input
+    ///
+    /// History is kept in `history` next to the config file.
+    pub async fn run(mut self) -> Result<()> {
+        let config = rustyline::Config::builder()
+            .completion_type(CompletionType::List)
+            .auto_add_history(true)
+            .build();
+        let mut editor: Editor<ShellHelper, DefaultHistory> =
+            Editor::with_config(config).map_err(|e| Error::Other(e.to_string()))?;
+        editor.set_helper(Some(ShellHelper {
+            names: Arc::clone(&self.names),
+        }));
+
+        let history = history_path();
+        if let Some(path) = &history {
+            // A missing history file is normal on first use
+            let _ = editor.load_history(path);
+        }
+
+        println!("rsairtable shell. Type 'help' for commands, Tab to complete, Ctrl-D to exit.");
+        if let Err(e) = self.load_bases().await {
+            eprintln!("❌ {}", e);
+        }
+
+        loop {
+            match editor.readline(&self.prompt()) {
+                Ok(line) => match self.execute(&line).await {
+                    Ok(Some(output)) => print!("{}", output),
+                    Ok(None) => break,
+                    Err(e) => eprintln!("❌ {}", e),
+                },
+                Err(ReadlineError::Interrupted) => continue,
+                Err(ReadlineError::Eof) => break,
+                Err(e) => return Err(Error::Other(e.to_string())),
+            }
+        }
+
+        if let Some(path) = &history {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            editor
+                .save_history(path)
+                .map_err(|e| Error::Other(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// The base list, fetched once
+    async fn load_bases(&mut self) -> Result<&[BaseInfo]> {
+        if self.bases.is_none() {
+            self.bases = Some(self.client.bases().await?);
+            self.update_names();
+        }
+        Ok(self.bases.as_deref().unwrap_or_default())
+    }
+
+    async fn select_base(&mut self, base_id: &str) -> Result<()> {
+        let schema = self.client.base(base_id).schema().await?;
+        // Listing bases needs a token scope that schema access does not
+        let name = match self.load_bases().await {
+            Ok(bases) => bases
+                .iter()
+                .find(|b| b.id == base_id)
+                .map(|b| b.name.clone()),
+            Err(_) => None,
+        };
+        self.base = Some(CurrentBase {
+            id: base_id.to_string(),
+            name: name.unwrap_or_else(|| base_id.to_string()),
+            schema,
+        });
+        self.table = None;
+        Ok(())
+    }
+
+    /// Refresh completion names from the current selection
+    fn update_names(&self) {
+        let mut names = self.names.lock().unwrap();
+        names.bases = self
+            .bases
+            .iter()
+            .flatten()
+            .map(|base| base.name.clone())
+            .collect();
+        names.tables = self
+            .base
+            .iter()
+            .flat_map(|base| &base.schema.tables)
+            .map(|table| table.name.clone())
+            .collect();
+        let table = self.table_schema().ok();
+        names.fields = table
+            .iter()
+            .flat_map(|t| &t.fields)
+            .map(|f| f.name.clone())
+            .collect();
+        names.views = table
+            .iter()
+            .flat_map(|t| &t.views)
+            .map(|v| v.name.clone())
+            .collect();
+    }
+
+    fn current_base(&self) -> Result<&CurrentBase> {
+        self.base
+            .as_ref()
+            .ok_or_else(|| Error::config("No base selected. Use 'use <BASE>' first"))
+    }
+
+    fn table_schema(&self) -> Result<&TableSchema> {
+        let base = self.current_base()?;
+        let table = self
+            .table
+            .as_deref()
+            .ok_or_else(|| Error::config("No table selected. Use 'use <TABLE>' first"))?;
+        find_table(&base.schema, table).ok_or_else(|| Error::table_not_found(table))
+    }
+
+    fn list_tables(&self) -> Result<String> {
+        let base = self.current_base()?;
+        let rows = base
+            .schema
+            .tables
+            .iter()
+            .map(|table| {
+                let primary = table
+                    .fields
+                    .iter()
+                    .find(|f| f.id == table.primary_field_id)
+                    .map(|f| f.name.clone())
+                    .unwrap_or_default();
+                vec![
+                    Value::String(table.id.clone()),
+                    Value::String(table.name.clone()),
+                    Value::String(primary),
+                    Value::from(table.fields.len()),
+                    Value::from(table.views.len()),
+                ]
+            })
+            .collect();
+        render(&Rows {
+            columns: ["id", "name", "primary field", "fields", "views"]
+                .map(String::from)
+                .to_vec(),
+            rows,
+        })
+    }
+
+    /// Command line for a [`CLI_COMMANDS`] command typed with arguments `rest`
+    fn cli_args(&self, command: &str, rest: &str) -> Result<Vec<String>> {
+        let words = split_words(rest)?;
+        let mut args: Vec<String> = match command {
+            "bases" => vec!["bases".to_string()],
+            "schema" if self.table.is_none() => {
+                vec!["base".to_string(), self.current_base()?.id.clone()]
+            }
+            _ => vec![
+                "base".to_string(),
+                self.current_base()?.id.clone(),
+                "table".to_string(),
+                self.table_schema()?.id.clone(),
+            ],
+        };
+        args.push(command.to_string());
+        match command {
+            // The JSON is passed on as typed, without splitting it into words
+            "create" => args.extend(["-j".to_string(), rest.to_string()]),
+            "update" => {
+                let (id, fields) = rest.split_once(char::is_whitespace).ok_or_else(|| {
+                    Error::config("Usage: update <RECORD_ID> {\"Field\": \"value\"}")
+                })?;
+                args.extend([id.to_string(), "-j".to_string(), fields.trim().to_string()]);
+            }
+            _ => args.extend(words.iter().cloned()),
+        }
+        let has_format = words
+            .iter()
+            .any(|word| word == "--format" || word.starts_with("--format="));
+        if matches!(command, "bases" | "schema" | "records") && !has_format {
+            args.extend(["--format".to_string(), "table".to_string()]);
+        }
+        Ok(args)
+    }
+}
+
+/// A table by ID, exact name, or unique case-insensitive name
+fn find_table<'a>(schema: &'a BaseSchema, name: &str) -> Option<&'a TableSchema> {
+    let tables = schema.tables.iter();
+    tables
+        .clone()
+        .find(|t| t.id == name || t.name == name)
+        .or_else(|| {
+            let mut matches = tables.filter(|t| t.name.eq_ignore_ascii_case(name));
+            match (matches.next(), matches.next()) {
+                (Some(table), None) => Some(table),
+                _ => None,
+            }
+        })
+}
+
+/// Text of `help` or `help COMMAND`
+fn help(command: &str) -> Result<String> {
+    match command {
+        "" => {
+            let mut text = String::from("Commands:\n");
+            for (name, about) in COMMANDS {
+                text.push_str(&format!("  {:<8} {}\n", name, about));
+            }
+            Ok(text)
+        }
+        other => COMMANDS
+            .iter()
+            .find(|(name, _)| *name == other)
+            .map(|(name, about)| format!("{}: {}\n", name, about))
+            .ok_or_else(|| Error::config(format!("Unknown command '{}'", other))),
+    }
+}
+
+/// Command line printing the CLI help of a [`CLI_COMMANDS`] command
+fn help_args(command: &str) -> Vec<String> {
+    let mut args = match command {
+        "bases" => vec!["bases"],
+        "schema" => vec!["base", "BASE", "schema"],
+        _ => vec!["base", "BASE", "table", "TABLE", command],
+    };
+    args.push("--help");
+    args.into_iter().map(str::to_string).collect()
+}
+
+fn render(rows: &Rows) -> Result<String> {
+    let mut out = Vec::new();
+    rows.write(&mut out, OutputFormat::Table)?;
+    String::from_utf8(out).map_err(|e| Error::Other(e.to_string()))
+}
+
+/// `history` in the config file's directory
+fn history_path() -> Option<PathBuf> {
+    crate::profile::default_path().map(|path| path.with_file_name("history"))
+}
+
+/// Completion for the line editor
+struct ShellHelper {
+    names: Arc<Mutex<Names>>,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(self.names.lock().unwrap().complete(line, pos))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_words_and_completion() {
+        assert_eq!(
+            split_words(r#"records -F "Case Number" -w 'x = "a b"' c\ d"#).unwrap(),
+            vec!["records", "-F", "Case Number", "-w", "x = \"a b\"", "c d"]
+        );
+        assert!(split_words("use \"Law").is_err());
+
+        let names = Names {
+            bases: vec!["Law Firm".to_string()],
+            tables: vec!["Cases".to_string(), "Clients".to_string()],
+            fields: vec!["Case Number".to_string(), "Status".to_string()],
+            views: vec!["Grid view".to_string()],
+        };
+        assert_eq!(
+            names.complete("re", 2),
+            (0, vec!["records".to_string(), "refresh".to_string()])
+        );
+        assert_eq!(
+            names.complete("use c", 5),
+            (4, vec!["Cases".to_string(), "Clients".to_string()])
+        );
+        assert_eq!(
+            names.complete("use l", 5),
+            (4, vec!["\"Law Firm\"".to_string()])
+        );
+        assert_eq!(
+            names.complete("records -F \"ca", 14),
+            (11, vec!["\"Case Number\"".to_string()])
+        );
+        assert_eq!(
+            names.complete("records -u ", 11),
+            (11, vec!["\"Grid view\"".to_string()])
+        );
+    }
+}
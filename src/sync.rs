@@ -0,0 +1,630 @@
+//! Local SQLite mirror of Airtable tables
+//!
+//! [`SqliteMirror`] copies tables into a SQLite database so they can be queried
+//! with SQL without calling the API. The first sync of a table pulls every
+//! record; later syncs fetch only records modified since the previous sync
+//! (using a `LAST_MODIFIED_TIME()` formula) and remove local records whose IDs
+//! no longer exist in Airtable.
+//!
+//! Each table becomes a SQLite table named after it in snake_case with:
+//!
+//! - `id` and `created_time` columns
+//! - `fields_json`, holding all of the record's fields as JSON
+//! - one typed column per field, derived from the field type
+//!
+//! The `_airtable_tables` and `_airtable_fields` tables keep the schema and the
+//! field-to-column mapping. Requires the `sync` feature.
+
+use crate::client::TableHandle;
+use crate::error::{Error, Result};
+use crate::models::{FieldSchema, Record, TableSchema};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+/// Default allowance for clock skew between this machine and Airtable
+const DEFAULT_OVERLAP_SECONDS: i64 = 60;
+
+/// Columns every mirrored table has
+const RESERVED_COLUMNS: [&str; 3] = ["id", "created_time", "fields_json"];
+
+/// SQLite storage class of a mirrored field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    /// Whole numbers and checkboxes (0/1)
+    Integer,
+    /// Decimal numbers
+    Real,
+    /// Strings, dates and single selects
+    Text,
+    /// Arrays and objects serialized as JSON text
+    Json,
+    /// Formula-like fields whose type varies: scalars stored natively, the rest as JSON
+    Any,
+}
+
+impl ColumnType {
+    /// Column type for an Airtable field type
+    pub fn for_field_type(field_type: &str) -> Self {
+        match field_type {
+            "checkbox" | "autoNumber" | "count" | "rating" => ColumnType::Integer,
+            "number" | "currency" | "percent" | "duration" => ColumnType::Real,
+            "singleLineText" | "multilineText" | "richText" | "email" | "url" | "phoneNumber"
+            | "singleSelect" | "date" | "dateTime" | "createdTime" | "lastModifiedTime" => {
+                ColumnType::Text
+            }
+            "formula" | "rollup" => ColumnType::Any,
+            _ => ColumnType::Json,
+        }
+    }
+
+    /// Declared SQLite type (empty for [`ColumnType::Any`])
+    pub fn sql_type(&self) -> &'static str {
+        match self {
+            ColumnType::Integer => "INTEGER",
+            ColumnType::Real => "REAL",
+            ColumnType::Text | ColumnType::Json => "TEXT",
+            ColumnType::Any => "",
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ColumnType::Integer => "integer",
+            ColumnType::Real => "real",
+            ColumnType::Text => "text",
+            ColumnType::Json => "json",
+            ColumnType::Any => "any",
+        }
+    }
+
+    /// Convert a field value for storage in a column of this type
+    fn to_sql(self, value: &Value) -> SqlValue {
+        match (self, value) {
+            (_, Value::Null) => SqlValue::Null,
+            (ColumnType::Integer | ColumnType::Any, Value::Bool(b)) => {
+                SqlValue::Integer(i64::from(*b))
+            }
+            (ColumnType::Integer, Value::Number(n)) => n
+                .as_i64()
+                .or_else(|| n.as_f64().map(|f| f as i64))
+                .map_or(SqlValue::Null, SqlValue::Integer),
+            (ColumnType::Real | ColumnType::Any, Value::Number(n)) => match n.as_i64() {
+                Some(i) if self == ColumnType::Any => SqlValue::Integer(i),
+                _ => n.as_f64().map_or(SqlValue::Null, SqlValue::Real),
+            },
+            (ColumnType::Text | ColumnType::Any, Value::String(s)) => SqlValue::Text(s.clone()),
+            (_, other) => SqlValue::Text(other.to_string()),
+        }
+    }
+}
+
+/// Mapping of an Airtable field to a SQLite column
+#[derive(Debug, Clone, PartialEq)]
+pub struct MirroredColumn {
+    /// Airtable field ID
+    pub field_id: String,
+    /// Airtable field name
+    pub field_name: String,
+    /// SQLite column name
+    pub column: String,
+    /// SQLite storage class
+    pub column_type: ColumnType,
+}
+
+/// Outcome of syncing one table
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncReport {
+    /// Airtable table name
+    pub table: String,
+    /// SQLite table the records were written to
+    pub sql_table: String,
+    /// Whether every record was pulled (first or forced sync)
+    pub full: bool,
+    /// Records fetched and written
+    pub fetched: usize,
+    /// Local records removed because they were deleted in Airtable
+    pub deleted: usize,
+    /// Records in the mirror after the sync
+    pub total: usize,
+}
+
+impl fmt::Display for SyncReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} -> {}: {} sync, {} fetched, {} deleted, {} total",
+            self.table,
+            self.sql_table,
+            if self.full { "full" } else { "incremental" },
+            self.fetched,
+            self.deleted,
+            self.total
+        )
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(error: rusqlite::Error) -> Self {
+        Error::Database(Box::new(error))
+    }
+}
+
+/// Airtable tables mirrored into a SQLite database
+#[derive(Debug)]
+pub struct SqliteMirror {
+    conn: Connection,
+    /// How far before the previous sync incremental syncs start looking
+    overlap: Duration,
+}
+
+impl SqliteMirror {
+    /// Open (or create) a mirror database file
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// Open a mirror held in memory
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS _airtable_tables (
+                 base_id TEXT NOT NULL,
+                 table_name TEXT NOT NULL,
+                 table_id TEXT NOT NULL,
+                 sql_table TEXT NOT NULL UNIQUE,
+                 primary_field_id TEXT NOT NULL,
+                 schema_json TEXT NOT NULL,
+                 last_sync TEXT,
+                 record_count INTEGER NOT NULL DEFAULT 0,
+                 PRIMARY KEY (base_id, table_name)
+             );
+             CREATE TABLE IF NOT EXISTS _airtable_fields (
+                 base_id TEXT NOT NULL,
+                 table_name TEXT NOT NULL,
+                 field_id TEXT NOT NULL,
+                 field_name TEXT NOT NULL,
+                 field_type TEXT NOT NULL,
+                 column_name TEXT NOT NULL,
+                 column_type TEXT NOT NULL,
+                 options_json TEXT,
+                 PRIMARY KEY (base_id, table_name, field_id)
+             );",
+        )?;
+        Ok(Self {
+            conn,
+            overlap: Duration::seconds(DEFAULT_OVERLAP_SECONDS),
+        })
+    }
+
+    /// Set how far before the previous sync incremental syncs look for changes
+    ///
+    /// Defaults to 60 seconds to allow for clock skew; records in the overlap are
+    /// simply fetched again.
+    pub fn with_overlap(mut self, overlap: Duration) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Underlying SQLite connection, for running queries against the mirror
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Time of the last successful sync of a table (by name or table ID)
+    pub fn last_sync(&self, base_id: &str, table: &str) -> Result<Option<DateTime<Utc>>> {
+        let last_sync: Option<Option<String>> = self
+            .conn
+            .query_row(
+                "SELECT last_sync FROM _airtable_tables
+                 WHERE base_id = ?1 AND (table_name = ?2 OR table_id = ?2)",
+                params![base_id, table],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        last_sync
+            .flatten()
+            .map(|time| {
+                DateTime::parse_from_rfc3339(&time)
+                    .map(|time| time.with_timezone(&Utc))
+                    .map_err(|e| Error::Other(format!("Invalid last_sync '{}': {}", time, e)))
+            })
+            .transpose()
+    }
+
+    /// Column mapping of a mirrored table
+    pub fn columns(&self, base_id: &str, table_name: &str) -> Result<Vec<MirroredColumn>> {
+        let mut statement = self.conn.prepare(
+            "SELECT field_id, field_name, column_name, field_type FROM _airtable_fields
+             WHERE base_id = ?1 AND table_name = ?2 ORDER BY rowid",
+        )?;
+        let columns = statement
+            .query_map(params![base_id, table_name], |row| {
+                Ok(MirroredColumn {
+                    field_id: row.get(0)?,
+                    field_name: row.get(1)?,
+                    column: row.get(2)?,
+                    column_type: ColumnType::for_field_type(&row.get::<_, String>(3)?),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(columns)
+    }
+
+    /// Sync a table, incrementally if it has been synced before
+    pub async fn sync_table(&mut self, table: &TableHandle) -> Result<SyncReport> {
+        let since = self.last_sync(table.base_id(), table.name())?;
+        self.sync(table, since).await
+    }
+
+    /// Pull every record of a table, replacing its mirrored contents
+    pub async fn resync_table(&mut self, table: &TableHandle) -> Result<SyncReport> {
+        self.sync(table, None).await
+    }
+
+    async fn sync(
+        &mut self,
+        table: &TableHandle,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<SyncReport> {
+        let started = Utc::now();
+        let schema = table.schema().await?;
+
+        // Fetch before touching the database so a failed request leaves it unchanged
+        let (records, remote_ids) = match since {
+            None => {
+                let records = table.all().await?;
+                let ids = records.iter().map(|r| r.id.clone()).collect();
+                (records, ids)
+            }
+            Some(since) => {
+                let since = since - self.overlap;
+                let formula = format!(
+                    "IS_AFTER(LAST_MODIFIED_TIME(), DATETIME_PARSE('{}'))",
+                    since.to_rfc3339()
+                );
+                let records = fetch_all(table, Some(&formula), None).await?;
+                let primary = schema
+                    .fields
+                    .iter()
+                    .find(|field| field.id == schema.primary_field_id)
+                    .map(|field| field.name.as_str());
+                let ids = fetch_all(table, None, primary)
+                    .await?
+                    .into_iter()
+                    .map(|r| r.id)
+                    .collect();
+                (records, ids)
+            }
+        };
+
+        let tx = self.conn.transaction()?;
+        let sql_table = ensure_table(&tx, table.base_id(), &schema)?;
+        let columns = ensure_columns(&tx, table.base_id(), &schema, &sql_table)?;
+        upsert_records(&tx, &sql_table, &columns, &records)?;
+        let deleted = delete_missing(&tx, &sql_table, &remote_ids)?;
+
+        let total: i64 = tx.query_row(
+            &format!("SELECT COUNT(*) FROM {}", quote(&sql_table)),
+            [],
+            |row| row.get(0),
+        )?;
+        tx.execute(
+            "UPDATE _airtable_tables SET last_sync = ?1, record_count = ?2
+             WHERE base_id = ?3 AND table_name = ?4",
+            params![started.to_rfc3339(), total, table.base_id(), schema.name],
+        )?;
+        tx.commit()?;
+
+        Ok(SyncReport {
+            table: schema.name,
+            sql_table,
+            full: since.is_none(),
+            fetched: records.len(),
+            deleted,
+            total: total as usize,
+        })
+    }
+}
+
+/// Fetch every page of a (filtered or field-restricted) listing
+async fn fetch_all(
+    table: &TableHandle,
+    formula: Option<&str>,
+    only_field: Option<&str>,
+) -> Result<Vec<Record>> {
+    let mut all_records = Vec::new();
+    let mut offset = None;
+    loop {
+        let mut query = table.list().offset(offset.clone());
+        if let Some(formula) = formula {
+            query = query.filter_by_formula(formula);
+        }
+        if let Some(field) = only_field {
+            query = query.fields(&[field]);
+        }
+        let (records, next_offset) = query.execute().await?;
+        all_records.extend(records);
+        if next_offset.is_none() {
+            return Ok(all_records);
+        }
+        offset = next_offset;
+    }
+}
+
+/// Record the table's schema and create its SQLite table, returning the table name
+fn ensure_table(conn: &Connection, base_id: &str, schema: &TableSchema) -> Result<String> {
+    let existing: Option<String> = conn
+        .query_row(
+            "SELECT sql_table FROM _airtable_tables WHERE base_id = ?1 AND table_name = ?2",
+            params![base_id, schema.name],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    let sql_table = match existing {
+        Some(sql_table) => sql_table,
+        None => {
+            let mut sql_table = snake_case(&schema.name);
+            let taken: bool = conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM _airtable_tables WHERE sql_table = ?1)",
+                params![sql_table],
+                |row| row.get(0),
+            )?;
+            if taken || sql_table.starts_with("_airtable") {
+                sql_table = format!("{}_{}", sql_table, base_id.to_lowercase());
+            }
+            sql_table
+        }
+    };
+
+    conn.execute(
+        "INSERT INTO _airtable_tables (base_id, table_name, table_id, sql_table, primary_field_id, schema_json)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT (base_id, table_name) DO UPDATE SET
+             table_id = excluded.table_id,
+             primary_field_id = excluded.primary_field_id,
+             schema_json = excluded.schema_json",
+        params![
+            base_id,
+            schema.name,
+            schema.id,
+            sql_table,
+            schema.primary_field_id,
+            serde_json::to_string(schema)?
+        ],
+    )?;
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                 id TEXT PRIMARY KEY,
+                 created_time TEXT NOT NULL,
+                 fields_json TEXT NOT NULL
+             )",
+            quote(&sql_table)
+        ),
+        [],
+    )?;
+    Ok(sql_table)
+}
+
+/// Map fields to columns (keeping existing column names) and add missing columns
+fn ensure_columns(
+    conn: &Connection,
+    base_id: &str,
+    schema: &TableSchema,
+    sql_table: &str,
+) -> Result<Vec<MirroredColumn>> {
+    let mut known: HashMap<String, String> = HashMap::new();
+    {
+        let mut statement = conn.prepare(
+            "SELECT field_id, column_name FROM _airtable_fields WHERE base_id = ?1 AND table_name = ?2",
+        )?;
+        let rows = statement.query_map(params![base_id, schema.name], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        for row in rows {
+            let (field_id, column) = row?;
+            known.insert(field_id, column);
+        }
+    }
+
+    let mut existing: HashSet<String> = {
+        let mut statement = conn.prepare(&format!("PRAGMA table_info({})", quote(sql_table)))?;
+        let names = statement
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<rusqlite::Result<HashSet<_>>>()?;
+        names
+    };
+    let mut used: HashSet<String> = known.values().cloned().collect();
+    used.extend(RESERVED_COLUMNS.iter().map(|c| c.to_string()));
+
+    let mut columns = Vec::new();
+    for field in &schema.fields {
+        let column = match known.get(&field.id) {
+            Some(column) => column.clone(),
+            None => unique_column(field, &mut used),
+        };
+        let column_type = ColumnType::for_field_type(&field.field_type);
+
+        if !existing.contains(&column) {
+            conn.execute(
+                &format!(
+                    "ALTER TABLE {} ADD COLUMN {} {}",
+                    quote(sql_table),
+                    quote(&column),
+                    column_type.sql_type()
+                ),
+                [],
+            )?;
+            existing.insert(column.clone());
+        }
+
+        conn.execute(
+            "INSERT INTO _airtable_fields
+                 (base_id, table_name, field_id, field_name, field_type, column_name, column_type, options_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT (base_id, table_name, field_id) DO UPDATE SET
+                 field_name = excluded.field_name,
+                 field_type = excluded.field_type,
+                 column_type = excluded.column_type,
+                 options_json = excluded.options_json",
+            params![
+                base_id,
+                schema.name,
+                field.id,
+                field.name,
+                field.field_type,
+                column,
+                column_type.as_str(),
+                field.options.as_ref().map(|options| options.to_string())
+            ],
+        )?;
+
+        columns.push(MirroredColumn {
+            field_id: field.id.clone(),
+            field_name: field.name.clone(),
+            column,
+            column_type,
+        });
+    }
+    Ok(columns)
+}
+
+/// A snake_case column name for a field that is not already in use
+fn unique_column(field: &FieldSchema, used: &mut HashSet<String>) -> String {
+    let base = snake_case(&field.name);
+    let mut column = base.clone();
+    let mut suffix = 2;
+    while used.contains(&column) {
+        column = format!("{}_{}", base, suffix);
+        suffix += 1;
+    }
+    used.insert(column.clone());
+    column
+}
+
+/// Insert or replace records, filling the JSON and typed columns
+fn upsert_records(
+    conn: &Connection,
+    sql_table: &str,
+    columns: &[MirroredColumn],
+    records: &[Record],
+) -> Result<()> {
+    let names: Vec<String> = RESERVED_COLUMNS
+        .iter()
+        .map(|c| c.to_string())
+        .chain(columns.iter().map(|c| quote(&c.column)))
+        .collect();
+    let placeholders: Vec<String> = (1..=names.len()).map(|i| format!("?{}", i)).collect();
+    let mut statement = conn.prepare(&format!(
+        "INSERT OR REPLACE INTO {} ({}) VALUES ({})",
+        quote(sql_table),
+        names.join(", "),
+        placeholders.join(", ")
+    ))?;
+
+    for record in records {
+        let mut values = vec![
+            SqlValue::Text(record.id.clone()),
+            SqlValue::Text(record.created_time.to_rfc3339()),
+            SqlValue::Text(serde_json::to_string(&record.fields)?),
+        ];
+        values.extend(columns.iter().map(|column| {
+            record
+                .fields
+                .get(&column.field_name)
+                .map_or(SqlValue::Null, |value| column.column_type.to_sql(value))
+        }));
+        statement.execute(rusqlite::params_from_iter(values))?;
+    }
+    Ok(())
+}
+
+/// Delete local records whose IDs are not in `remote_ids`, returning how many were removed
+fn delete_missing(
+    conn: &Connection,
+    sql_table: &str,
+    remote_ids: &HashSet<String>,
+) -> Result<usize> {
+    let local_ids: Vec<String> = {
+        let mut statement = conn.prepare(&format!("SELECT id FROM {}", quote(sql_table)))?;
+        let ids = statement
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        ids
+    };
+
+    let mut statement = conn.prepare(&format!("DELETE FROM {} WHERE id = ?1", quote(sql_table)))?;
+    let mut deleted = 0;
+    for id in local_ids.iter().filter(|id| !remote_ids.contains(*id)) {
+        deleted += statement.execute(params![id])?;
+    }
+    Ok(deleted)
+}
+
+/// Quote a SQLite identifier
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Lowercase snake_case identifier (`Case Name` -> `case_name`)
+fn snake_case(name: &str) -> String {
+    let mut result = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() {
+            result.extend(c.to_lowercase());
+        } else if !result.is_empty() && !result.ends_with('_') {
+            result.push('_');
+        }
+    }
+    let result = result.trim_end_matches('_').to_string();
+    match result.chars().next() {
+        None => "field".to_string(),
+        Some(c) if c.is_numeric() => format!("_{}", result),
+        Some(_) => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_column_types_and_values() {
+        assert_eq!(ColumnType::for_field_type("number"), ColumnType::Real);
+        assert_eq!(ColumnType::for_field_type("checkbox"), ColumnType::Integer);
+        assert_eq!(
+            ColumnType::for_field_type("multipleRecordLinks"),
+            ColumnType::Json
+        );
+
+        assert_eq!(
+            ColumnType::Integer.to_sql(&json!(true)),
+            SqlValue::Integer(1)
+        );
+        assert_eq!(ColumnType::Real.to_sql(&json!(3)), SqlValue::Real(3.0));
+        assert_eq!(ColumnType::Any.to_sql(&json!(3)), SqlValue::Integer(3));
+        assert_eq!(
+            ColumnType::Json.to_sql(&json!(["recA", "recB"])),
+            SqlValue::Text("[\"recA\",\"recB\"]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_snake_case_identifiers() {
+        assert_eq!(snake_case("Case Name"), "case_name");
+        assert_eq!(snake_case("Filed (Date)"), "filed_date");
+        assert_eq!(snake_case("2024 Budget"), "_2024_budget");
+        assert_eq!(snake_case("!!!"), "field");
+    }
+}
@@ -0,0 +1,1305 @@
+//! In-process fake Airtable server for offline tests
+//!
+//! [`FakeAirtable`] listens on a local port and serves the records, metadata,
+//! comments and enterprise audit log endpoints from in-memory state. Point a client at it with
+//! [`Config::with_endpoint_url`] (or use [`FakeAirtable::client`]) to run
+//! integration tests without network access or an API token.
+//!
+//! Available with the `testing` cargo feature.
+//!
+//! The fake mirrors the behaviour the client relies on: offset pagination
+//! (100 records or 1000 bases per page), the 10-record limit on batch writes,
+//! unknown field rejection and Airtable-shaped error bodies. Faults such as rate limits, server
+//! errors and dropped connections can be queued with [`FakeAirtable::fail_next`],
+//! or aimed at a later request with [`FakeAirtable::fail_request`].
+//! `filterByFormula` supports field references, literals, comparisons,
+//! `AND`/`OR`/`NOT`, `RECORD_ID()` and record timestamps; `sort` orders by field values; views
+//! are accepted but not modelled.
+//! Tables and fields can be created, renamed and described through the
+//! metadata API; link fields get an inverse field, but linked record values are
+//! not mirrored to it.
+//!
+//! ```rust,no_run
+//! use rsairtable::testing::FakeAirtable;
+//! use serde_json::json;
+//!
+//! # async fn example() -> rsairtable::Result<()> {
+//! let fake = FakeAirtable::start().await?;
+//! fake.add_table("appFakeBase000001", "Cases", &[("Name", "singleLineText")]);
+//! fake.insert("appFakeBase000001", "Cases", vec![json!({ "Name": "Smith v. Jones" })]);
+//!
+//! let records = fake.client().base("appFakeBase000001").table("Cases").all().await?;
+//! assert_eq!(records.len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+mod formula;
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::Result;
+use crate::models::*;
+use crate::transport::ReqwestTransport;
+use chrono::{DateTime, Utc};
+use formula::{Formula, Row};
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// API key accepted by the fake server
+pub const FAKE_API_KEY: &str = "patFakeAirtableToken";
+
+/// ID of the user the fake server authenticates every request as
+pub const FAKE_USER_ID: &str = "usrFakeUser000001";
+
+/// Maximum number of records per page and per batch write
+const PAGE_SIZE: usize = 100;
+const BATCH_SIZE: usize = 10;
+/// Maximum number of bases per page of `GET /meta/bases`
+const BASES_PAGE_SIZE: usize = 1000;
+
+/// A fault to inject instead of handling the next request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Respond with 429 `RATE_LIMIT_REACHED`
+    RateLimit,
+    /// Respond with the given status and a `SERVER_ERROR` body
+    Status(u16),
+    /// Close the connection without responding
+    Disconnect,
+}
+
+/// In-process fake Airtable server
+#[derive(Debug)]
+pub struct FakeAirtable {
+    url: String,
+    state: Arc<Mutex<State>>,
+    server: JoinHandle<()>,
+}
+
+impl FakeAirtable {
+    /// Start a fake server on a random local port
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}/v0", listener.local_addr()?);
+        let state = Arc::new(Mutex::new(State::default()));
+        let server = tokio::spawn(serve(listener, state.clone()));
+
+        Ok(Self { url, state, server })
+    }
+
+    /// Endpoint URL to use with [`Config::with_endpoint_url`]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Configuration pointing at this server with the accepted API key
+    pub fn config(&self) -> Config {
+        Config::new(FAKE_API_KEY.to_string()).with_endpoint_url(&self.url)
+    }
+
+    /// Client connected to this server
+    pub fn client(&self) -> Client {
+        Client::with_transport(self.config(), ReqwestTransport::new())
+    }
+
+    /// Add a table with the given `(name, type)` fields, creating the base if needed
+    ///
+    /// The first field becomes the primary field. Returns the table ID.
+    pub fn add_table(&self, base_id: &str, table_name: &str, fields: &[(&str, &str)]) -> String {
+        let mut state = self.state.lock().unwrap();
+        let State { bases, ids, .. } = &mut *state;
+
+        let base = match bases.iter_mut().position(|base| base.id == base_id) {
+            Some(index) => &mut bases[index],
+            None => {
+                bases.push(FakeBase {
+                    id: base_id.to_string(),
+                    name: base_id.to_string(),
+                    tables: Vec::new(),
+                });
+                bases.last_mut().unwrap()
+            }
+        };
+
+        let fields: Vec<FieldSchema> = fields
+            .iter()
+            .map(|(name, field_type)| FieldSchema {
+                id: ids.next("fld"),
+                name: name.to_string(),
+                field_type: field_type.to_string(),
+                options: None,
+                description: None,
+            })
+            .collect();
+
+        let schema = TableSchema {
+            id: ids.next("tbl"),
+            name: table_name.to_string(),
+            description: None,
+            primary_field_id: fields.first().map(|f| f.id.clone()).unwrap_or_default(),
+            fields,
+            views: vec![ViewSchema {
+                id: ids.next("viw"),
+                name: "Grid view".to_string(),
+                view_type: "grid".to_string(),
+            }],
+        };
+        let table_id = schema.id.clone();
+
+        base.tables.push(FakeTable {
+            schema,
+            records: Vec::new(),
+            modified: HashMap::new(),
+            comments: HashMap::new(),
+        });
+        table_id
+    }
+
+    /// Add an empty base (bases are also created by [`FakeAirtable::add_table`])
+    pub fn add_base(&self, base_id: &str, name: &str) {
+        let mut state = self.state.lock().unwrap();
+        if !state.bases.iter().any(|base| base.id == base_id) {
+            state.bases.push(FakeBase {
+                id: base_id.to_string(),
+                name: name.to_string(),
+                tables: Vec::new(),
+            });
+        }
+    }
+
+    /// Insert records directly into a table, bypassing the HTTP layer
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table does not exist or a record uses an unknown field.
+    pub fn insert(&self, base_id: &str, table: &str, records: Vec<Value>) -> Vec<Record> {
+        let mut state = self.state.lock().unwrap();
+        let State { bases, ids, .. } = &mut *state;
+        let table = find_table(bases, base_id, table).expect("fake table does not exist");
+
+        records
+            .iter()
+            .map(|fields| {
+                let fields = table
+                    .normalize(fields)
+                    .expect("fake record uses unknown fields");
+                table.insert(ids, fields)
+            })
+            .collect()
+    }
+
+    /// Current records of a table
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table does not exist.
+    pub fn records(&self, base_id: &str, table: &str) -> Vec<Record> {
+        let mut state = self.state.lock().unwrap();
+        find_table(&mut state.bases, base_id, table)
+            .expect("fake table does not exist")
+            .records
+            .clone()
+    }
+
+    /// Append events to the enterprise audit log (shared by every enterprise account)
+    ///
+    /// Events are served oldest first and, like Airtable, every page carries a
+    /// `next` cursor, so readers stop on an empty page.
+    pub fn add_audit_events(&self, events: Vec<Value>) {
+        self.state.lock().unwrap().audit_events.extend(events);
+    }
+
+    /// Inject a fault in place of the next request (faults are used in order)
+    pub fn fail_next(&self, fault: Fault) {
+        self.state.lock().unwrap().faults.push_back(fault);
+    }
+
+    /// Inject a fault in place of request number `request`, counted as in
+    /// [`FakeAirtable::request_count`]
+    pub fn fail_request(&self, request: usize, fault: Fault) {
+        self.state.lock().unwrap().scheduled_faults.push((request, fault));
+    }
+
+    /// Number of requests received so far
+    pub fn request_count(&self) -> usize {
+        self.state.lock().unwrap().requests
+    }
+}
+
+impl Drop for FakeAirtable {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// Server state shared with the connection tasks
+#[derive(Debug, Default)]
+struct State {
+    bases: Vec<FakeBase>,
+    ids: IdGenerator,
+    faults: VecDeque<Fault>,
+    /// Faults for a given request number
+    scheduled_faults: Vec<(usize, Fault)>,
+    requests: usize,
+    audit_events: Vec<Value>,
+}
+
+#[derive(Debug)]
+struct FakeBase {
+    id: String,
+    name: String,
+    tables: Vec<FakeTable>,
+}
+
+#[derive(Debug)]
+struct FakeTable {
+    schema: TableSchema,
+    records: Vec<Record>,
+    /// Last modification time of each record (for `LAST_MODIFIED_TIME()`)
+    modified: HashMap<String, DateTime<Utc>>,
+    comments: HashMap<String, Vec<Comment>>,
+}
+
+/// Generates Airtable-style IDs (`rec`, `tbl`, `fld`, ... followed by 14 characters)
+#[derive(Debug, Default)]
+struct IdGenerator {
+    counter: u64,
+}
+
+impl IdGenerator {
+    fn next(&mut self, prefix: &str) -> String {
+        self.counter += 1;
+        format!("{}Fake{:010}", prefix, self.counter)
+    }
+}
+
+/// Error response: status code and Airtable error body
+type Failure = (u16, Value);
+
+fn failure(status: u16, error_type: &str, message: impl Into<String>) -> Failure {
+    (
+        status,
+        json!({ "error": { "type": error_type, "message": message.into() } }),
+    )
+}
+
+fn record_not_found(record_id: &str) -> Failure {
+    failure(
+        404,
+        "MODEL_ID_NOT_FOUND",
+        format!("Could not find record {}", record_id),
+    )
+}
+
+fn find_table<'a>(
+    bases: &'a mut [FakeBase],
+    base_id: &str,
+    table: &str,
+) -> std::result::Result<&'a mut FakeTable, Failure> {
+    let base = bases
+        .iter_mut()
+        .find(|base| base.id == base_id)
+        .ok_or_else(|| failure(404, "NOT_FOUND", "Could not find what you are looking for"))?;
+
+    base.tables
+        .iter_mut()
+        .find(|t| t.schema.name == table || t.schema.id == table)
+        .ok_or_else(|| {
+            failure(
+                404,
+                "TABLE_NOT_FOUND",
+                format!("Could not find table {} in application {}", table, base_id),
+            )
+        })
+}
+
+/// Airtable omits empty values from records
+fn is_empty_value(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::String(s) => s.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+/// Sort order of two cells: empty first, then numbers, then text
+fn compare_values(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    let key = |value: Option<&Value>| match value {
+        None | Some(Value::Null) => (0, 0.0, String::new()),
+        Some(Value::Number(n)) => (1, n.as_f64().unwrap_or_default(), String::new()),
+        Some(Value::Bool(b)) => (1, f64::from(u8::from(*b)), String::new()),
+        Some(Value::String(s)) => (2, 0.0, s.to_lowercase()),
+        Some(other) => (2, 0.0, other.to_string()),
+    };
+    let (a, b) = (key(a), key(b));
+    a.0.cmp(&b.0)
+        .then(a.1.total_cmp(&b.1))
+        .then_with(|| a.2.cmp(&b.2))
+}
+
+/// Get the records array of a batch write, enforcing the batch size limit
+fn batch_records(body: &Value) -> std::result::Result<&Vec<Value>, Failure> {
+    match body.get("records").and_then(Value::as_array) {
+        Some(records) if !records.is_empty() && records.len() <= BATCH_SIZE => Ok(records),
+        Some(_) => Err(failure(
+            422,
+            "INVALID_RECORDS",
+            format!(
+                "You must provide between 1 and {} records per request",
+                BATCH_SIZE
+            ),
+        )),
+        None => Err(failure(
+            422,
+            "INVALID_REQUEST_MISSING_FIELDS",
+            "Could not find field \"records\" in the request body",
+        )),
+    }
+}
+
+impl FakeTable {
+    /// Map field names or IDs to names, rejecting unknown fields and dropping empty values
+    fn normalize(&self, fields: &Value) -> std::result::Result<Fields, Failure> {
+        let object = fields.as_object().ok_or_else(|| {
+            failure(
+                422,
+                "INVALID_REQUEST_UNKNOWN",
+                "Invalid request: \"fields\" must be an object",
+            )
+        })?;
+
+        let mut normalized = Fields::new();
+        for (key, value) in object {
+            normalized.insert(self.field_name(key)?.to_string(), value.clone());
+        }
+        Ok(normalized)
+    }
+
+    /// Resolve a field name or ID to the field's name
+    fn field_name(&self, key: &str) -> std::result::Result<&str, Failure> {
+        self.schema
+            .fields
+            .iter()
+            .find(|f| f.name == key || f.id == key)
+            .map(|f| f.name.as_str())
+            .ok_or_else(|| {
+                failure(
+                    422,
+                    "UNKNOWN_FIELD_NAME",
+                    format!("Unknown field name: \"{}\"", key),
+                )
+            })
+    }
+
+    fn insert(&mut self, ids: &mut IdGenerator, mut fields: Fields) -> Record {
+        fields.retain(|_, value| !is_empty_value(value));
+        let record = Record {
+            id: ids.next("rec"),
+            created_time: Utc::now(),
+            fields,
+        };
+        self.records.push(record.clone());
+        self.modified.insert(record.id.clone(), record.created_time);
+        record
+    }
+
+    fn position(&self, record_id: &str) -> std::result::Result<usize, Failure> {
+        self.records
+            .iter()
+            .position(|r| r.id == record_id)
+            .ok_or_else(|| record_not_found(record_id))
+    }
+
+    fn apply(&mut self, index: usize, fields: Fields, replace: bool) -> Record {
+        let record = &mut self.records[index];
+        if replace {
+            record.fields.clear();
+        }
+        record.fields.extend(fields);
+        record.fields.retain(|_, value| !is_empty_value(value));
+        self.modified.insert(record.id.clone(), Utc::now());
+        record.clone()
+    }
+
+    fn list(&self, query: &[(String, String)]) -> std::result::Result<Value, Failure> {
+        let param = |name: &str| {
+            query
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+
+        let page_size = match param("pageSize") {
+            Some(value) => value
+                .parse::<usize>()
+                .ok()
+                .filter(|size| (1..=PAGE_SIZE).contains(size))
+                .ok_or_else(|| {
+                    failure(
+                        422,
+                        "INVALID_PAGE_SIZE",
+                        format!("pageSize must be between 1 and {}", PAGE_SIZE),
+                    )
+                })?,
+            None => PAGE_SIZE,
+        };
+
+        let max_records =
+            match param("maxRecords") {
+                Some(value) => Some(value.parse::<usize>().map_err(|_| {
+                    failure(422, "INVALID_MAX_RECORDS", "maxRecords must be a number")
+                })?),
+                None => None,
+            };
+
+        let start = match param("offset") {
+            Some(offset) => offset
+                .strip_prefix("itrFake")
+                .and_then(|index| index.parse::<usize>().ok())
+                .ok_or_else(|| {
+                    failure(
+                        422,
+                        "LIST_RECORDS_ITERATOR_NOT_AVAILABLE",
+                        "Iterator not available",
+                    )
+                })?,
+            None => 0,
+        };
+
+        let formula = param("filterByFormula")
+            .map(Formula::parse)
+            .transpose()
+            .map_err(|e| {
+                failure(
+                    422,
+                    "INVALID_FILTER_BY_FORMULA",
+                    format!("The formula for filtering records is invalid: {}", e),
+                )
+            })?;
+
+        let selected: Vec<&str> = query
+            .iter()
+            .filter(|(key, _)| key == "fields[]")
+            .map(|(_, value)| value.as_str())
+            .collect();
+
+        let mut sort = Vec::new();
+        while let Some(field) = param(&format!("sort[{}][field]", sort.len())) {
+            let direction = param(&format!("sort[{}][direction]", sort.len()));
+            sort.push((field, direction == Some("desc")));
+        }
+
+        let mut matching: Vec<&Record> = self
+            .records
+            .iter()
+            .filter(|r| {
+                formula.as_ref().is_none_or(|f| {
+                    f.matches(&Row {
+                        id: &r.id,
+                        fields: &r.fields,
+                        created_time: r.created_time,
+                        last_modified: self.modified.get(&r.id).copied().unwrap_or(r.created_time),
+                    })
+                })
+            })
+            .collect();
+
+        matching.sort_by(|a, b| {
+            sort.iter()
+                .map(|(field, descending)| {
+                    let order = compare_values(a.fields.get(*field), b.fields.get(*field));
+                    if *descending {
+                        order.reverse()
+                    } else {
+                        order
+                    }
+                })
+                .find(|order| order.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let limit = max_records.map_or(matching.len(), |max| max.min(matching.len()));
+        let end = (start + page_size).min(limit);
+        let records: Vec<Value> = matching
+            .get(start..end)
+            .unwrap_or_default()
+            .iter()
+            .map(|record| {
+                let mut record = (*record).clone();
+                if !selected.is_empty() {
+                    record
+                        .fields
+                        .retain(|name, _| selected.contains(&name.as_str()));
+                }
+                json!(record)
+            })
+            .collect();
+
+        let mut body = json!({ "records": records });
+        if end < limit {
+            body["offset"] = json!(format!("itrFake{}", end));
+        }
+        Ok(body)
+    }
+
+    fn create(
+        &mut self,
+        ids: &mut IdGenerator,
+        body: &Value,
+    ) -> std::result::Result<Value, Failure> {
+        if body.get("records").is_some() {
+            let fields = batch_records(body)?
+                .iter()
+                .map(|record| self.normalize(record.get("fields").unwrap_or(&json!({}))))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            let created: Vec<Record> = fields
+                .into_iter()
+                .map(|fields| self.insert(ids, fields))
+                .collect();
+            return Ok(json!({ "records": created }));
+        }
+
+        let fields = body.get("fields").ok_or_else(|| {
+            failure(
+                422,
+                "INVALID_REQUEST_MISSING_FIELDS",
+                "Could not find field \"fields\" in the request body",
+            )
+        })?;
+        let fields = self.normalize(fields)?;
+        Ok(json!(self.insert(ids, fields)))
+    }
+
+    fn update_many(
+        &mut self,
+        ids: &mut IdGenerator,
+        body: &Value,
+        replace: bool,
+    ) -> std::result::Result<Value, Failure> {
+        let records = batch_records(body)?;
+        if let Some(upsert) = body.get("performUpsert") {
+            return self.upsert(ids, records, upsert, replace);
+        }
+
+        let mut updates = Vec::new();
+        for record in records {
+            let record_id = record.get("id").and_then(Value::as_str).ok_or_else(|| {
+                failure(
+                    422,
+                    "INVALID_RECORDS",
+                    "Every record must have an \"id\" unless performUpsert is set",
+                )
+            })?;
+            let index = self.position(record_id)?;
+            let fields = self.normalize(record.get("fields").unwrap_or(&json!({})))?;
+            updates.push((index, fields));
+        }
+
+        let updated: Vec<Record> = updates
+            .into_iter()
+            .map(|(index, fields)| self.apply(index, fields, replace))
+            .collect();
+        Ok(json!({ "records": updated }))
+    }
+
+    fn upsert(
+        &mut self,
+        ids: &mut IdGenerator,
+        records: &[Value],
+        upsert: &Value,
+        replace: bool,
+    ) -> std::result::Result<Value, Failure> {
+        let merge_on: Vec<String> = upsert
+            .get("fieldsToMergeOn")
+            .and_then(Value::as_array)
+            .filter(|names| !names.is_empty())
+            .ok_or_else(|| {
+                failure(
+                    422,
+                    "INVALID_REQUEST_UNKNOWN",
+                    "performUpsert.fieldsToMergeOn must be a non-empty array",
+                )
+            })?
+            .iter()
+            .map(|name| {
+                self.field_name(name.as_str().unwrap_or_default())
+                    .map(str::to_string)
+            })
+            .collect::<std::result::Result<_, _>>()?;
+
+        // Plan every write against the current records before applying any of them
+        let mut plan = Vec::new();
+        for record in records {
+            let fields = self.normalize(record.get("fields").unwrap_or(&json!({})))?;
+            let target = match record.get("id").and_then(Value::as_str) {
+                Some(record_id) => Some(self.position(record_id)?),
+                None => {
+                    let matches: Vec<usize> = self
+                        .records
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, existing)| {
+                            merge_on.iter().all(|name| {
+                                fields.get(name).is_some_and(|value| {
+                                    existing.fields.get(name).unwrap_or(&Value::Null) == value
+                                })
+                            })
+                        })
+                        .map(|(index, _)| index)
+                        .collect();
+                    if matches.len() > 1 {
+                        return Err(failure(
+                            422,
+                            "INVALID_VALUE_FOR_COLUMN",
+                            "Multiple records match the fieldsToMergeOn values",
+                        ));
+                    }
+                    matches.first().copied()
+                }
+            };
+            plan.push((target, fields));
+        }
+
+        let mut written = Vec::new();
+        let mut created_ids = Vec::new();
+        let mut updated_ids = Vec::new();
+        for (target, fields) in plan {
+            let record = match target {
+                Some(index) => {
+                    let record = self.apply(index, fields, replace);
+                    updated_ids.push(record.id.clone());
+                    record
+                }
+                None => {
+                    let record = self.insert(ids, fields);
+                    created_ids.push(record.id.clone());
+                    record
+                }
+            };
+            written.push(record);
+        }
+
+        Ok(json!({
+            "records": written,
+            "createdRecords": created_ids,
+            "updatedRecords": updated_ids
+        }))
+    }
+
+    fn delete_many(&mut self, query: &[(String, String)]) -> std::result::Result<Value, Failure> {
+        let record_ids: Vec<&str> = query
+            .iter()
+            .filter(|(key, _)| key == "records[]")
+            .map(|(_, value)| value.as_str())
+            .collect();
+        if record_ids.is_empty() || record_ids.len() > BATCH_SIZE {
+            return Err(failure(
+                422,
+                "INVALID_RECORDS",
+                format!(
+                    "You must provide between 1 and {} records per request",
+                    BATCH_SIZE
+                ),
+            ));
+        }
+        for record_id in &record_ids {
+            self.position(record_id)?;
+        }
+
+        self.records
+            .retain(|r| !record_ids.contains(&r.id.as_str()));
+        let deleted: Vec<Value> = record_ids
+            .iter()
+            .map(|id| json!({ "id": id, "deleted": true }))
+            .collect();
+        Ok(json!({ "records": deleted }))
+    }
+}
+
+/// A parsed HTTP request
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn json(&self) -> std::result::Result<Value, Failure> {
+        serde_json::from_slice(&self.body).map_err(|e| {
+            failure(
+                422,
+                "INVALID_REQUEST_UNKNOWN",
+                format!("Invalid request: could not parse JSON body: {}", e),
+            )
+        })
+    }
+}
+
+impl State {
+    /// Handle a request, returning `None` when the connection should be dropped
+    fn handle(&mut self, request: &Request) -> Option<(u16, Value)> {
+        self.requests += 1;
+
+        let scheduled = self
+            .scheduled_faults
+            .iter()
+            .position(|(request, _)| *request == self.requests)
+            .map(|i| self.scheduled_faults.remove(i).1);
+        if let Some(fault) = scheduled.or_else(|| self.faults.pop_front()) {
+            return match fault {
+                Fault::RateLimit => Some(failure(
+                    429,
+                    "RATE_LIMIT_REACHED",
+                    "Rate limit exceeded. Please try again later",
+                )),
+                Fault::Status(status) => Some(failure(
+                    status,
+                    "SERVER_ERROR",
+                    "Try again. If the problem persists, contact support.",
+                )),
+                Fault::Disconnect => None,
+            };
+        }
+
+        let expected = format!("Bearer {}", FAKE_API_KEY);
+        if request.header("authorization") != Some(expected.as_str()) {
+            return Some(failure(
+                401,
+                "AUTHENTICATION_REQUIRED",
+                "Authentication required",
+            ));
+        }
+
+        Some(match self.route(request) {
+            Ok(body) => (200, body),
+            Err(failure) => failure,
+        })
+    }
+
+    fn create_base(&mut self, body: &Value) -> std::result::Result<Value, Failure> {
+        let name = body.get("name").and_then(Value::as_str);
+        let tables = body.get("tables").and_then(Value::as_array);
+        let (Some(name), Some(tables)) = (name, tables) else {
+            return Err(failure(
+                422,
+                "INVALID_REQUEST_MISSING_FIELDS",
+                "A base needs a name and tables",
+            ));
+        };
+        if tables.is_empty() {
+            return Err(failure(
+                422,
+                "INVALID_REQUEST_UNKNOWN",
+                "A base needs at least one table",
+            ));
+        }
+
+        let base_id = self.ids.next("app");
+        self.bases.push(FakeBase {
+            id: base_id.clone(),
+            name: name.to_string(),
+            tables: Vec::new(),
+        });
+        let tables = tables
+            .iter()
+            .map(|table| self.create_table(&base_id, table))
+            .collect::<std::result::Result<Vec<Value>, Failure>>()?;
+        Ok(json!({ "id": base_id, "tables": tables }))
+    }
+
+    fn create_table(&mut self, base_id: &str, body: &Value) -> std::result::Result<Value, Failure> {
+        let definition: TableDefinition = serde_json::from_value(body.clone())
+            .map_err(|e| failure(422, "INVALID_REQUEST_UNKNOWN", e.to_string()))?;
+        let State { bases, ids, .. } = self;
+        let base = bases
+            .iter_mut()
+            .find(|base| base.id == base_id)
+            .ok_or_else(|| failure(404, "NOT_FOUND", "Could not find what you are looking for"))?;
+        if base.tables.iter().any(|t| t.schema.name == definition.name) {
+            return Err(failure(
+                422,
+                "DUPLICATE_TABLE_NAME",
+                format!("Table name \"{}\" is already used", definition.name),
+            ));
+        }
+        if definition.fields.is_empty() {
+            return Err(failure(
+                422,
+                "INVALID_REQUEST_UNKNOWN",
+                "A table needs at least one field",
+            ));
+        }
+
+        let fields: Vec<FieldSchema> = definition
+            .fields
+            .into_iter()
+            .map(|field| FieldSchema {
+                id: ids.next("fld"),
+                name: field.name,
+                field_type: field.field_type,
+                options: field.options,
+                description: field.description,
+            })
+            .collect();
+        let schema = TableSchema {
+            id: ids.next("tbl"),
+            name: definition.name,
+            description: definition.description,
+            primary_field_id: fields[0].id.clone(),
+            fields,
+            views: vec![ViewSchema {
+                id: ids.next("viw"),
+                name: "Grid view".to_string(),
+                view_type: "grid".to_string(),
+            }],
+        };
+        base.tables.push(FakeTable {
+            schema: schema.clone(),
+            records: Vec::new(),
+            modified: HashMap::new(),
+            comments: HashMap::new(),
+        });
+        Ok(json!(schema))
+    }
+
+    /// Change the name and/or description of a table, or of one of its fields
+    fn update_schema(
+        &mut self,
+        base_id: &str,
+        table: &str,
+        field_id: Option<&str>,
+        body: &Value,
+    ) -> std::result::Result<Value, Failure> {
+        let name = body.get("name").and_then(Value::as_str);
+        let description = body.get("description").and_then(Value::as_str);
+        let taken = |names: Vec<&str>| name.is_some_and(|name| names.contains(&name));
+
+        if field_id.is_none() {
+            if let Some(base) = self.bases.iter().find(|base| base.id == base_id) {
+                let others = base
+                    .tables
+                    .iter()
+                    .filter(|t| t.schema.name != table && t.schema.id != table)
+                    .map(|t| t.schema.name.as_str());
+                if taken(others.collect()) {
+                    return Err(failure(
+                        422,
+                        "DUPLICATE_TABLE_NAME",
+                        format!(
+                            "Table name \"{}\" is already used",
+                            name.unwrap_or_default()
+                        ),
+                    ));
+                }
+            }
+        }
+        let schema = &mut find_table(&mut self.bases, base_id, table)?.schema;
+
+        let Some(field_id) = field_id else {
+            if let Some(name) = name {
+                schema.name = name.to_string();
+            }
+            if let Some(description) = description {
+                schema.description = Some(description.to_string());
+            }
+            return Ok(json!(schema));
+        };
+
+        let position = schema
+            .fields
+            .iter()
+            .position(|f| f.id == field_id)
+            .ok_or_else(|| {
+                failure(
+                    404,
+                    "NOT_FOUND",
+                    format!("Could not find field {}", field_id),
+                )
+            })?;
+        let others = schema.fields.iter().filter(|f| f.id != field_id);
+        if taken(others.map(|f| f.name.as_str()).collect()) {
+            return Err(failure(
+                422,
+                "DUPLICATE_OR_EMPTY_FIELD_NAME",
+                format!(
+                    "Field name \"{}\" is already used",
+                    name.unwrap_or_default()
+                ),
+            ));
+        }
+        let field = &mut schema.fields[position];
+        if let Some(name) = name {
+            field.name = name.to_string();
+        }
+        if let Some(description) = description {
+            field.description = Some(description.to_string());
+        }
+        Ok(json!(field))
+    }
+
+    /// Add a field; link fields also get an inverse field in the linked table
+    fn create_field(
+        &mut self,
+        base_id: &str,
+        table: &str,
+        body: &Value,
+    ) -> std::result::Result<Value, Failure> {
+        let definition: FieldDefinition = serde_json::from_value(body.clone())
+            .map_err(|e| failure(422, "INVALID_REQUEST_UNKNOWN", e.to_string()))?;
+        let State { bases, ids, .. } = self;
+        let base = bases
+            .iter_mut()
+            .find(|base| base.id == base_id)
+            .ok_or_else(|| failure(404, "NOT_FOUND", "Could not find what you are looking for"))?;
+        let index = base
+            .tables
+            .iter()
+            .position(|t| t.schema.name == table || t.schema.id == table)
+            .ok_or_else(|| {
+                failure(
+                    404,
+                    "TABLE_NOT_FOUND",
+                    format!("Could not find table {}", table),
+                )
+            })?;
+        if base.tables[index]
+            .schema
+            .fields
+            .iter()
+            .any(|f| f.name == definition.name)
+        {
+            return Err(failure(
+                422,
+                "DUPLICATE_OR_EMPTY_FIELD_NAME",
+                format!("Field name \"{}\" is already used", definition.name),
+            ));
+        }
+
+        let mut field = FieldSchema {
+            id: ids.next("fld"),
+            name: definition.name,
+            field_type: definition.field_type,
+            options: definition.options,
+            description: definition.description,
+        };
+
+        if field.field_type == "multipleRecordLinks" {
+            let linked_id = field
+                .options
+                .as_ref()
+                .and_then(|options| options.get("linkedTableId"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| {
+                    failure(
+                        422,
+                        "INVALID_FIELD_TYPE_OPTIONS",
+                        "linkedTableId is required",
+                    )
+                })?;
+            let linked = base
+                .tables
+                .iter()
+                .position(|t| t.schema.id == linked_id)
+                .ok_or_else(|| {
+                    failure(422, "INVALID_FIELD_TYPE_OPTIONS", "linkedTableId not found")
+                })?;
+
+            if linked != index {
+                let source = &base.tables[index].schema;
+                let mut name = source.name.clone();
+                while base.tables[linked]
+                    .schema
+                    .fields
+                    .iter()
+                    .any(|f| f.name == name)
+                {
+                    name.push_str(" 2");
+                }
+                let inverse = FieldSchema {
+                    id: ids.next("fld"),
+                    name,
+                    field_type: "multipleRecordLinks".to_string(),
+                    options: Some(
+                        json!({ "linkedTableId": source.id, "inverseLinkFieldId": field.id }),
+                    ),
+                    description: None,
+                };
+                field.options =
+                    Some(json!({ "linkedTableId": linked_id, "inverseLinkFieldId": inverse.id }));
+                base.tables[linked].schema.fields.push(inverse);
+            }
+        }
+
+        base.tables[index].schema.fields.push(field.clone());
+        Ok(json!(field))
+    }
+
+    fn route(&mut self, request: &Request) -> std::result::Result<Value, Failure> {
+        let not_found = || failure(404, "NOT_FOUND", "Could not find what you are looking for");
+
+        let path = request.path.strip_prefix("/v0/").ok_or_else(not_found)?;
+        let segments: Vec<String> = path
+            .trim_end_matches('/')
+            .split('/')
+            .map(|s| {
+                urlencoding::decode(s)
+                    .map(|s| s.into_owned())
+                    .unwrap_or_else(|_| s.to_string())
+            })
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let method = request.method.as_str();
+
+        match segments.as_slice() {
+            ["meta", "whoami"] if method == "GET" => Ok(json!({
+                "id": FAKE_USER_ID,
+                "email": "fake.user@example.com",
+                "name": "Fake User"
+            })),
+            ["meta", "bases"] if method == "GET" => {
+                let start = match request.query.iter().find(|(key, _)| key == "offset") {
+                    Some((_, offset)) => offset.parse().map_err(|_| {
+                        failure(422, "LIST_BASES_ITERATOR_NOT_AVAILABLE", "Invalid offset")
+                    })?,
+                    None => 0,
+                };
+                let bases: Vec<Value> = self
+                    .bases
+                    .iter()
+                    .skip(start)
+                    .take(BASES_PAGE_SIZE)
+                    .map(|base| json!({ "id": base.id, "name": base.name, "permissionLevel": "create" }))
+                    .collect();
+                let end = start + bases.len();
+                if end < self.bases.len() {
+                    Ok(json!({ "bases": bases, "offset": end.to_string() }))
+                } else {
+                    Ok(json!({ "bases": bases }))
+                }
+            }
+            ["meta", "bases"] if method == "POST" => self.create_base(&request.json()?),
+            ["meta", "bases", base_id, "tables"] if method == "POST" => {
+                self.create_table(base_id, &request.json()?)
+            }
+            ["meta", "bases", base_id, "tables", table, "fields"] if method == "POST" => {
+                self.create_field(base_id, table, &request.json()?)
+            }
+            ["meta", "bases", base_id, "tables", table] if method == "PATCH" => {
+                self.update_schema(base_id, table, None, &request.json()?)
+            }
+            ["meta", "bases", base_id, "tables", table, "fields", field_id]
+                if method == "PATCH" =>
+            {
+                self.update_schema(base_id, table, Some(field_id), &request.json()?)
+            }
+            ["meta", "bases", base_id, "tables"] if method == "GET" => {
+                let base = self
+                    .bases
+                    .iter()
+                    .find(|base| base.id == *base_id)
+                    .ok_or_else(not_found)?;
+                let tables: Vec<&TableSchema> = base.tables.iter().map(|t| &t.schema).collect();
+                Ok(json!({ "tables": tables }))
+            }
+            ["meta", "enterpriseAccounts", _, "auditLogEvents"] if method == "GET" => {
+                let param = |name: &str| {
+                    request
+                        .query
+                        .iter()
+                        .find(|(key, _)| key == name)
+                        .map(|(_, value)| value.as_str())
+                };
+                let start: usize = param("next").and_then(|n| n.parse().ok()).unwrap_or(0);
+                let page_size: usize = param("pageSize")
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or(PAGE_SIZE);
+                let events = self.audit_events.iter().skip(start).take(page_size);
+                let events: Vec<&Value> = events.collect();
+                let next = (start + events.len()).to_string();
+                Ok(json!({ "events": events, "pagination": { "next": next } }))
+            }
+            ["meta", ..] => Err(not_found()),
+            [base_id, table] => {
+                let State { bases, ids, .. } = self;
+                let table = find_table(bases, base_id, table)?;
+                match method {
+                    "GET" => table.list(&request.query),
+                    "POST" => table.create(ids, &request.json()?),
+                    "PATCH" => table.update_many(ids, &request.json()?, false),
+                    "PUT" => table.update_many(ids, &request.json()?, true),
+                    "DELETE" => table.delete_many(&request.query),
+                    _ => Err(not_found()),
+                }
+            }
+            [base_id, table, record_id] => {
+                let table = find_table(&mut self.bases, base_id, table)?;
+                let index = table.position(record_id)?;
+                match method {
+                    "GET" => Ok(json!(table.records[index])),
+                    "PATCH" | "PUT" => {
+                        let body = request.json()?;
+                        let fields = table.normalize(body.get("fields").unwrap_or(&json!({})))?;
+                        Ok(json!(table.apply(index, fields, method == "PUT")))
+                    }
+                    "DELETE" => {
+                        table.records.remove(index);
+                        table.comments.remove(*record_id);
+                        Ok(json!({ "id": record_id, "deleted": true }))
+                    }
+                    _ => Err(not_found()),
+                }
+            }
+            [base_id, table, record_id, "comments"] => {
+                let State { bases, ids, .. } = self;
+                let table = find_table(bases, base_id, table)?;
+                table.position(record_id)?;
+                match method {
+                    "GET" => {
+                        let comments = table.comments.get(*record_id).cloned().unwrap_or_default();
+                        Ok(json!({ "comments": comments, "offset": null }))
+                    }
+                    "POST" => {
+                        let body = request.json()?;
+                        let text = body.get("text").and_then(Value::as_str).ok_or_else(|| {
+                            failure(
+                                422,
+                                "INVALID_REQUEST_MISSING_FIELDS",
+                                "Could not find field \"text\" in the request body",
+                            )
+                        })?;
+                        let comment = Comment {
+                            id: ids.next("com"),
+                            author: Collaborator {
+                                id: FAKE_USER_ID.to_string(),
+                                name: "Fake User".to_string(),
+                                email: "fake.user@example.com".to_string(),
+                            },
+                            text: text.to_string(),
+                            created_time: Utc::now(),
+                        };
+                        // Airtable lists comments newest first
+                        table
+                            .comments
+                            .entry(record_id.to_string())
+                            .or_default()
+                            .insert(0, comment.clone());
+                        Ok(json!(comment))
+                    }
+                    _ => Err(not_found()),
+                }
+            }
+            _ => Err(not_found()),
+        }
+    }
+}
+
+/// Accept connections until the server is dropped
+async fn serve(listener: TcpListener, state: Arc<Mutex<State>>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        let state = state.clone();
+        tokio::spawn(async move {
+            let _ = handle_connection(stream, state).await;
+        });
+    }
+}
+
+/// Serve a single request and close the connection
+async fn handle_connection(mut stream: TcpStream, state: Arc<Mutex<State>>) -> std::io::Result<()> {
+    let Some(request) = read_request(&mut stream).await? else {
+        return Ok(());
+    };
+
+    let response = state.lock().unwrap().handle(&request);
+    let Some((status, body)) = response else {
+        return Ok(());
+    };
+
+    let body = body.to_string();
+    let reason = reqwest::StatusCode::from_u16(status)
+        .ok()
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or("");
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        reason,
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Read an HTTP/1.1 request with a `Content-Length` body
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<Request>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+
+    let header_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or_default().to_string();
+
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+    let content_length = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    let mut body = buffer[header_end + 4..].to_vec();
+    while body.len() < content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+
+    let (path, query) = target.split_once('?').unwrap_or((target.as_str(), ""));
+    let query = url::form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect();
+
+    Ok(Some(Request {
+        method,
+        path: path.to_string(),
+        query,
+        headers,
+        body,
+    }))
+}
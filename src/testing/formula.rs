@@ -0,0 +1,419 @@
+//! Minimal `filterByFormula` evaluator for the fake Airtable server
+//!
+//! Supports field references (`{Name}`), string and number literals, the
+//! comparison operators `=`, `!=`, `<`, `<=`, `>` and `>=`, parentheses and the
+//! functions `AND`, `OR`, `NOT`, `TRUE`, `FALSE`, `BLANK`, `RECORD_ID`,
+//! `CREATED_TIME`, `LAST_MODIFIED_TIME`, `DATETIME_PARSE`, `IS_BEFORE` and
+//! `IS_AFTER`.
+
+use crate::models::Fields;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+/// Record data a formula is evaluated against
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Row<'a> {
+    pub id: &'a str,
+    pub fields: &'a Fields,
+    pub created_time: DateTime<Utc>,
+    pub last_modified: DateTime<Utc>,
+}
+
+/// Parsed formula expression
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Formula {
+    Field(String),
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    Blank,
+    RecordId,
+    Compare(Box<Formula>, Op, Box<Formula>),
+    Not(Box<Formula>),
+    And(Vec<Formula>),
+    Or(Vec<Formula>),
+    CreatedTime,
+    LastModifiedTime,
+    DatetimeParse(Box<Formula>),
+    IsBefore(Box<Formula>, Box<Formula>),
+    IsAfter(Box<Formula>, Box<Formula>),
+}
+
+/// Comparison operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Field(String),
+    Text(String),
+    Number(f64),
+    Ident(String),
+    Op(Op),
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl Formula {
+    /// Parse a formula, returning a description of the problem on failure
+    pub(crate) fn parse(source: &str) -> Result<Formula, String> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let formula = parser.expression()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(formula),
+            Some(token) => Err(format!("unexpected {:?}", token)),
+        }
+    }
+
+    /// Whether a record matches the formula
+    pub(crate) fn matches(&self, row: &Row) -> bool {
+        truthy(&self.eval(row))
+    }
+
+    fn eval(&self, row: &Row) -> Value {
+        match self {
+            Formula::Field(name) => row.fields.get(name).cloned().unwrap_or(Value::Null),
+            Formula::Text(text) => Value::String(text.clone()),
+            Formula::Number(number) => serde_json::json!(number),
+            Formula::Bool(value) => Value::Bool(*value),
+            Formula::Blank => Value::Null,
+            Formula::RecordId => Value::String(row.id.to_string()),
+            Formula::Not(inner) => Value::Bool(!inner.matches(row)),
+            Formula::And(items) => Value::Bool(items.iter().all(|item| item.matches(row))),
+            Formula::Or(items) => Value::Bool(items.iter().any(|item| item.matches(row))),
+            Formula::Compare(left, op, right) => {
+                Value::Bool(compare(&left.eval(row), *op, &right.eval(row)))
+            }
+            Formula::CreatedTime => Value::String(row.created_time.to_rfc3339()),
+            Formula::LastModifiedTime => Value::String(row.last_modified.to_rfc3339()),
+            Formula::DatetimeParse(inner) => as_datetime(&inner.eval(row))
+                .map_or(Value::Null, |time| Value::String(time.to_rfc3339())),
+            Formula::IsBefore(left, right) => Value::Bool(
+                matches!((as_datetime(&left.eval(row)), as_datetime(&right.eval(row))), (Some(l), Some(r)) if l < r),
+            ),
+            Formula::IsAfter(left, right) => Value::Bool(
+                matches!((as_datetime(&left.eval(row)), as_datetime(&right.eval(row))), (Some(l), Some(r)) if l > r),
+            ),
+        }
+    }
+}
+
+/// Compare numerically when either side is a number, otherwise as text
+fn compare(left: &Value, op: Op, right: &Value) -> bool {
+    let ordering = if left.is_number() || right.is_number() {
+        match (as_number(left), as_number(right)) {
+            (Some(l), Some(r)) => l.partial_cmp(&r),
+            _ => None,
+        }
+    } else {
+        Some(as_text(left).cmp(&as_text(right)))
+    };
+
+    let Some(ordering) = ordering else {
+        return op == Op::Ne;
+    };
+    match op {
+        Op::Eq => ordering.is_eq(),
+        Op::Ne => ordering.is_ne(),
+        Op::Lt => ordering.is_lt(),
+        Op::Le => ordering.is_le(),
+        Op::Gt => ordering.is_gt(),
+        Op::Ge => ordering.is_ge(),
+    }
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Null => Some(0.0),
+        Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(b) => if *b { "1" } else { "0" }.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(as_text).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+fn as_datetime(value: &Value) -> Option<DateTime<Utc>> {
+    let text = value.as_str()?;
+    DateTime::parse_from_rfc3339(text)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(_) => true,
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '{' => {
+                chars.next();
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err("unterminated field reference".to_string()),
+                    }
+                }
+                tokens.push(Token::Field(name));
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => match chars.next() {
+                            Some(escaped) => text.push(escaped),
+                            None => return Err("unterminated string".to_string()),
+                        },
+                        Some(ch) if ch == c => break,
+                        Some(ch) => text.push(ch),
+                        None => return Err("unterminated string".to_string()),
+                    }
+                }
+                tokens.push(Token::Text(text));
+            }
+            '0'..='9' | '.' => {
+                let mut number = String::new();
+                while let Some(&d) = chars.peek() {
+                    if d.is_ascii_digit() || d == '.' {
+                        number.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let value = number
+                    .parse()
+                    .map_err(|_| format!("invalid number '{}'", number))?;
+                tokens.push(Token::Number(value));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&d) = chars.peek() {
+                    if d.is_ascii_alphanumeric() || d == '_' {
+                        ident.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            '(' | ')' | ',' | '=' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    ',' => Token::Comma,
+                    _ => Token::Op(Op::Eq),
+                });
+            }
+            '!' | '<' | '>' => {
+                chars.next();
+                let with_eq = chars.next_if_eq(&'=').is_some();
+                tokens.push(Token::Op(match (c, with_eq) {
+                    ('!', true) => Op::Ne,
+                    ('<', false) => Op::Lt,
+                    ('<', true) => Op::Le,
+                    ('>', false) => Op::Gt,
+                    ('>', true) => Op::Ge,
+                    _ => return Err("unexpected '!'".to_string()),
+                }));
+            }
+            other => return Err(format!("unexpected character '{}'", other)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("expected {:?}, found {:?}", expected, token)),
+            None => Err(format!("expected {:?}, found end of formula", expected)),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Formula, String> {
+        let left = self.operand()?;
+        if let Some(Token::Op(op)) = self.tokens.get(self.pos).cloned() {
+            self.pos += 1;
+            let right = self.operand()?;
+            return Ok(Formula::Compare(Box::new(left), op, Box::new(right)));
+        }
+        Ok(left)
+    }
+
+    fn operand(&mut self) -> Result<Formula, String> {
+        match self.next() {
+            Some(Token::Field(name)) => Ok(Formula::Field(name)),
+            Some(Token::Text(text)) => Ok(Formula::Text(text)),
+            Some(Token::Number(number)) => Ok(Formula::Number(number)),
+            Some(Token::LParen) => {
+                let inner = self.expression()?;
+                self.expect(Token::RParen)?;
+                Ok(inner)
+            }
+            Some(Token::Ident(name)) => self.function(&name),
+            Some(token) => Err(format!("unexpected {:?}", token)),
+            None => Err("unexpected end of formula".to_string()),
+        }
+    }
+
+    fn function(&mut self, name: &str) -> Result<Formula, String> {
+        self.expect(Token::LParen)?;
+        let mut args = Vec::new();
+        if self.tokens.get(self.pos) == Some(&Token::RParen) {
+            self.pos += 1;
+        } else {
+            loop {
+                args.push(self.expression()?);
+                match self.next() {
+                    Some(Token::Comma) => continue,
+                    Some(Token::RParen) => break,
+                    _ => return Err(format!("malformed arguments to {}()", name)),
+                }
+            }
+        }
+
+        match (name.to_ascii_uppercase().as_str(), args.len()) {
+            ("AND", n) if n > 0 => Ok(Formula::And(args)),
+            ("OR", n) if n > 0 => Ok(Formula::Or(args)),
+            ("NOT", 1) => Ok(Formula::Not(Box::new(args.remove(0)))),
+            ("TRUE", 0) => Ok(Formula::Bool(true)),
+            ("FALSE", 0) => Ok(Formula::Bool(false)),
+            ("BLANK", 0) => Ok(Formula::Blank),
+            ("RECORD_ID", 0) => Ok(Formula::RecordId),
+            ("CREATED_TIME", 0) => Ok(Formula::CreatedTime),
+            ("LAST_MODIFIED_TIME", 0) => Ok(Formula::LastModifiedTime),
+            ("DATETIME_PARSE", 1) => Ok(Formula::DatetimeParse(Box::new(args.remove(0)))),
+            ("IS_BEFORE", 2) => {
+                let left = args.remove(0);
+                Ok(Formula::IsBefore(Box::new(left), Box::new(args.remove(0))))
+            }
+            ("IS_AFTER", 2) => {
+                let left = args.remove(0);
+                Ok(Formula::IsAfter(Box::new(left), Box::new(args.remove(0))))
+            }
+            _ => Err(format!("unsupported function {}()", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(value: Value) -> Fields {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn row(fields: &Fields) -> Row<'_> {
+        let time = DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        Row {
+            id: "recTest",
+            fields,
+            created_time: time,
+            last_modified: time,
+        }
+    }
+
+    #[test]
+    fn test_formula_comparisons_and_functions() {
+        let fields = fields(json!({ "Name": "Smith v. Jones", "Year": 1999 }));
+        let record = row(&fields);
+
+        assert!(Formula::parse("NOT({Name} = '')").unwrap().matches(&record));
+        assert!(
+            Formula::parse("OR(RECORD_ID() = 'recOther', RECORD_ID() = 'recTest')")
+                .unwrap()
+                .matches(&record)
+        );
+        assert!(Formula::parse("{Name} = \"Smith v. Jones\"")
+            .unwrap()
+            .matches(&record));
+        assert!(Formula::parse("AND({Year} >= 1990, {Year} < 2000)")
+            .unwrap()
+            .matches(&record));
+        assert!(!Formula::parse("OR({Missing}, {Year} > 2000)")
+            .unwrap()
+            .matches(&record));
+        assert!(Formula::parse("{Missing} = BLANK()")
+            .unwrap()
+            .matches(&record));
+    }
+
+    #[test]
+    fn test_formula_datetime_functions() {
+        let fields = Fields::new();
+        let record = row(&fields);
+
+        assert!(Formula::parse(
+            "IS_AFTER(LAST_MODIFIED_TIME(), DATETIME_PARSE('2024-03-01T11:59:00Z'))"
+        )
+        .unwrap()
+        .matches(&record));
+        assert!(!Formula::parse(
+            "IS_BEFORE(CREATED_TIME(), DATETIME_PARSE('2024-03-01T12:00:00Z'))"
+        )
+        .unwrap()
+        .matches(&record));
+    }
+
+    #[test]
+    fn test_formula_rejects_unsupported_syntax() {
+        assert!(Formula::parse("{Name").is_err());
+        assert!(Formula::parse("SEARCH('a', {Name})").is_err());
+        assert!(Formula::parse("{A} = 'x' extra").is_err());
+    }
+}
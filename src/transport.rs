@@ -0,0 +1,314 @@
+//! HTTP transport abstraction for RSAirtable
+//!
+//! Every request made by [`Client`](crate::Client) goes through a [`Transport`].
+//! The default [`ReqwestTransport`] sends requests over the network with reqwest;
+//! custom transports can serve canned responses in tests or wrap requests with
+//! their own HTTP stack.
+
+use crate::error::{Error, Result};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::time::Duration;
+
+/// HTTP methods used by the Airtable API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    Get,
+    Post,
+    Patch,
+    Put,
+    Delete,
+}
+
+impl Method {
+    /// Method name as sent on the wire
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Patch => "PATCH",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Body of an outgoing request
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestBody {
+    /// No body
+    Empty,
+    /// JSON body
+    Json(serde_json::Value),
+    /// Single-file multipart form upload
+    Multipart(MultipartFile),
+}
+
+/// File sent as a multipart form part
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultipartFile {
+    /// Form field name
+    pub field_name: String,
+    /// File name reported to the server
+    pub filename: String,
+    /// MIME type of the file
+    pub content_type: String,
+    /// File content
+    pub bytes: Vec<u8>,
+}
+
+/// An HTTP request built by the client
+///
+/// The `Debug` output redacts the `Authorization` header.
+#[derive(Clone, PartialEq)]
+pub struct HttpRequest {
+    /// HTTP method
+    pub method: Method,
+    /// Absolute URL including query string
+    pub url: String,
+    /// Request headers as (name, value) pairs
+    pub headers: Vec<(String, String)>,
+    /// Request body
+    pub body: RequestBody,
+    /// Timeout for the whole request (if any)
+    pub timeout: Option<Duration>,
+}
+
+impl HttpRequest {
+    /// Create a request with no headers or body
+    pub fn new<S: Into<String>>(method: Method, url: S) -> Self {
+        Self {
+            method,
+            url: url.into(),
+            headers: Vec::new(),
+            body: RequestBody::Empty,
+            timeout: None,
+        }
+    }
+
+    /// Create a GET request
+    pub fn get<S: Into<String>>(url: S) -> Self {
+        Self::new(Method::Get, url)
+    }
+
+    /// Create a POST request
+    pub fn post<S: Into<String>>(url: S) -> Self {
+        Self::new(Method::Post, url)
+    }
+
+    /// Create a PATCH request
+    pub fn patch<S: Into<String>>(url: S) -> Self {
+        Self::new(Method::Patch, url)
+    }
+
+    /// Create a DELETE request
+    pub fn delete<S: Into<String>>(url: S) -> Self {
+        Self::new(Method::Delete, url)
+    }
+
+    /// Set a JSON body
+    pub fn json(mut self, body: serde_json::Value) -> Self {
+        self.body = RequestBody::Json(body);
+        self
+    }
+
+    /// Set a multipart file body
+    pub fn multipart(mut self, file: MultipartFile) -> Self {
+        self.body = RequestBody::Multipart(file);
+        self
+    }
+
+    /// Add a header, replacing any existing header with the same name
+    pub fn header<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        let name = name.into();
+        self.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(&name));
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    /// Get a header value by name (case-insensitive)
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+}
+
+impl fmt::Debug for HttpRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let headers: Vec<(&str, &str)> = self
+            .headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), redact_header(name, value)))
+            .collect();
+        f.debug_struct("HttpRequest")
+            .field("method", &self.method)
+            .field("url", &self.url)
+            .field("headers", &headers)
+            .field("body", &self.body)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// Value shown in place of secret header values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Header value safe to log or persist (`Authorization` is replaced by [`REDACTED`])
+pub fn redact_header<'a>(name: &str, value: &'a str) -> &'a str {
+    if name.eq_ignore_ascii_case("authorization") {
+        REDACTED
+    } else {
+        value
+    }
+}
+
+/// An HTTP response returned by a transport
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers as (name, value) pairs
+    pub headers: Vec<(String, String)>,
+    /// Raw response body
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Create a response with the given status and body
+    pub fn new<B: Into<Vec<u8>>>(status: u16, body: B) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Create a JSON response with the given status
+    pub fn json(status: u16, body: &serde_json::Value) -> Self {
+        Self::new(status, body.to_string()).with_header("content-type", "application/json")
+    }
+
+    /// Add a response header
+    pub fn with_header<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Whether the status code is 2xx
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Get a header value by name (case-insensitive)
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    /// Deserialize the body as JSON
+    pub fn parse_json<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
+}
+
+/// Sends HTTP requests on behalf of the client
+///
+/// Implementations must return non-2xx responses as `Ok(HttpResponse)`; the
+/// client turns them into API errors. `Err` is reserved for requests that could
+/// not be sent or whose response could not be read.
+#[async_trait]
+pub trait Transport: fmt::Debug + Send + Sync {
+    /// Send a request and return the response
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+/// Default transport sending requests over the network with reqwest
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Create a transport with a default reqwest client
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a transport using an existing reqwest client (proxies, TLS settings, ...)
+    pub fn from_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let method = match request.method {
+            Method::Get => reqwest::Method::GET,
+            Method::Post => reqwest::Method::POST,
+            Method::Patch => reqwest::Method::PATCH,
+            Method::Put => reqwest::Method::PUT,
+            Method::Delete => reqwest::Method::DELETE,
+        };
+
+        let mut builder = self.client.request(method, &request.url);
+
+        for (name, value) in &request.headers {
+            let value = reqwest::header::HeaderValue::from_str(value).map_err(|e| {
+                Error::config(format!("Invalid value for header '{}': {}", name, e))
+            })?;
+            builder = builder.header(name.as_str(), value);
+        }
+
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        builder = match request.body {
+            RequestBody::Empty => builder,
+            RequestBody::Json(body) => builder.json(&body),
+            RequestBody::Multipart(file) => {
+                let part = reqwest::multipart::Part::bytes(file.bytes)
+                    .file_name(file.filename)
+                    .mime_str(&file.content_type)
+                    .map_err(|e| Error::Api {
+                        status: 400,
+                        message: format!("Invalid content type: {}", e),
+                    })?;
+                builder.multipart(reqwest::multipart::Form::new().part(file.field_name, part))
+            }
+        };
+
+        let response = builder.send().await?;
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (name.to_string(), value.to_string()))
+            })
+            .collect();
+        let body = response.bytes().await?.to_vec();
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
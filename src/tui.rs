@@ -0,0 +1,675 @@
+//! Full-screen record browser
+//!
+//! `rsairtable tui base <BASE> table <TABLE>` shows a table's records in a
+//! scrolling grid with one column per field, in schema order. Records are
+//! fetched a page at a time as the cursor reaches the last loaded row. The
+//! browser can filter with a formula, sort by the selected column, switch to one
+//! of the table's views, open a record with its comments, and edit a cell after
+//! confirming the new value.
+//!
+//! [`Browser`] keeps the state and handles key presses without a terminal;
+//! [`Browser::run`] draws it with ratatui until `q` is pressed. Requires the
+//! `tui` feature.
+
+use crate::client::TableHandle;
+use crate::error::Result;
+use crate::models::{Comment, FieldSchema, Record, TableSchema};
+use crate::output::cell_text;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table, TableState, Wrap,
+};
+use ratatui::Frame;
+use serde_json::{json, Value};
+
+/// Widest a grid column gets, in characters
+const MAX_COLUMN_WIDTH: usize = 30;
+
+/// Rows moved by Page Up and Page Down
+const PAGE_ROWS: usize = 20;
+
+/// Key summary shown when there is no status message
+const KEY_HELP: &str = "↑↓←→ move  Enter open  e edit  / filter  s sort  v view  r reload  q quit";
+
+/// Field types whose cells can be edited as text
+const EDITABLE_TYPES: [&str; 16] = [
+    "singleLineText",
+    "multilineText",
+    "richText",
+    "email",
+    "url",
+    "phoneNumber",
+    "number",
+    "currency",
+    "percent",
+    "rating",
+    "duration",
+    "checkbox",
+    "singleSelect",
+    "multipleSelects",
+    "date",
+    "dateTime",
+];
+
+/// What the keyboard is currently driving
+#[derive(Debug, Clone)]
+enum Mode {
+    /// Moving around the grid
+    Grid,
+    /// Reading a record and its comments
+    Detail {
+        record: Box<Record>,
+        comments: Vec<Comment>,
+        scroll: u16,
+    },
+    /// Typing a filter formula
+    Filter(String),
+    /// Typing a new value for the selected cell
+    Edit { field: String, text: String },
+    /// Waiting for y/n before updating a cell
+    Confirm {
+        record_id: String,
+        field: String,
+        value: Value,
+    },
+    /// Choosing a view (0 is all records)
+    Views(usize),
+}
+
+/// Grid browser state for one table
+pub struct Browser {
+    table: TableHandle,
+    schema: TableSchema,
+    records: Vec<Record>,
+    next_offset: Option<String>,
+    formula: Option<String>,
+    /// Sort field and whether it is descending
+    sort: Option<(String, bool)>,
+    view: Option<String>,
+    row: usize,
+    column: usize,
+    mode: Mode,
+    status: String,
+    table_state: TableState,
+}
+
+impl Browser {
+    /// Fetch the table schema and first page of records
+    pub async fn new(table: TableHandle) -> Result<Self> {
+        let schema = table.schema().await?;
+        let mut browser = Self {
+            table,
+            schema,
+            records: Vec::new(),
+            next_offset: None,
+            formula: None,
+            sort: None,
+            view: None,
+            row: 0,
+            column: 0,
+            mode: Mode::Grid,
+            status: String::new(),
+            table_state: TableState::default(),
+        };
+        browser.reload().await?;
+        Ok(browser)
+    }
+
+    /// Records loaded so far
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// Record under the cursor
+    pub fn selected(&self) -> Option<&Record> {
+        self.records.get(self.row)
+    }
+
+    /// Field under the cursor
+    pub fn selected_field(&self) -> Option<&FieldSchema> {
+        self.schema.fields.get(self.column)
+    }
+
+    /// Last status or error message
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// Draw until `q` or Ctrl-C, restoring the terminal afterwards
+    pub async fn run(mut self) -> Result<()> {
+        let mut terminal = ratatui::try_init()?;
+        let result = async {
+            loop {
+                terminal.draw(|frame| self.draw(frame))?;
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press && !self.handle_key(key).await {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+        .await;
+        ratatui::restore();
+        result
+    }
+
+    /// Apply one key press; returns `false` when the browser should close
+    ///
+    /// Request errors are shown in the status line rather than returned.
+    pub async fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return false;
+        }
+        let result = match std::mem::replace(&mut self.mode, Mode::Grid) {
+            Mode::Grid => match key.code {
+                KeyCode::Char('q') => return false,
+                code => self.grid_key(code).await,
+            },
+            Mode::Detail {
+                record,
+                comments,
+                scroll,
+            } => {
+                let scroll = match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => scroll.saturating_sub(1),
+                    KeyCode::Down | KeyCode::Char('j') => scroll.saturating_add(1),
+                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => return true,
+                    _ => scroll,
+                };
+                self.mode = Mode::Detail {
+                    record,
+                    comments,
+                    scroll,
+                };
+                Ok(())
+            }
+            Mode::Filter(mut text) => match key.code {
+                KeyCode::Enter => {
+                    let text = text.trim();
+                    self.formula = (!text.is_empty()).then(|| text.to_string());
+                    self.reload().await
+                }
+                KeyCode::Esc => Ok(()),
+                code => {
+                    edit_text(&mut text, code);
+                    self.mode = Mode::Filter(text);
+                    Ok(())
+                }
+            },
+            Mode::Edit { field, mut text } => match key.code {
+                KeyCode::Enter => {
+                    let schema = self.schema.fields.iter().find(|f| f.name == field);
+                    match schema.map(|f| parse_cell(f, &text)) {
+                        Some(Ok(value)) => {
+                            self.mode = Mode::Confirm {
+                                record_id: self
+                                    .selected()
+                                    .map(|r| r.id.clone())
+                                    .unwrap_or_default(),
+                                field,
+                                value,
+                            };
+                        }
+                        Some(Err(e)) => {
+                            self.status = e;
+                            self.mode = Mode::Edit { field, text };
+                        }
+                        None => {}
+                    }
+                    Ok(())
+                }
+                KeyCode::Esc => Ok(()),
+                code => {
+                    edit_text(&mut text, code);
+                    self.mode = Mode::Edit { field, text };
+                    Ok(())
+                }
+            },
+            Mode::Confirm {
+                record_id,
+                field,
+                value,
+            } => match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    self.update_cell(&record_id, &field, value).await
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.status = "Edit cancelled".to_string();
+                    Ok(())
+                }
+                _ => {
+                    self.mode = Mode::Confirm {
+                        record_id,
+                        field,
+                        value,
+                    };
+                    Ok(())
+                }
+            },
+            Mode::Views(selected) => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.mode = Mode::Views(selected.saturating_sub(1));
+                    Ok(())
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.mode = Mode::Views((selected + 1).min(self.schema.views.len()));
+                    Ok(())
+                }
+                KeyCode::Enter => {
+                    self.view = selected
+                        .checked_sub(1)
+                        .and_then(|i| self.schema.views.get(i))
+                        .map(|view| view.name.clone());
+                    self.reload().await
+                }
+                KeyCode::Esc => Ok(()),
+                _ => {
+                    self.mode = Mode::Views(selected);
+                    Ok(())
+                }
+            },
+        };
+        if let Err(e) = result {
+            self.status = format!("Error: {}", e);
+        }
+        true
+    }
+
+    async fn grid_key(&mut self, code: KeyCode) -> Result<()> {
+        self.status.clear();
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => self.row = self.row.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_down(1).await?,
+            KeyCode::PageUp => self.row = self.row.saturating_sub(PAGE_ROWS),
+            KeyCode::PageDown => self.move_down(PAGE_ROWS).await?,
+            KeyCode::Home | KeyCode::Char('g') => self.row = 0,
+            KeyCode::End | KeyCode::Char('G') => self.row = self.records.len().saturating_sub(1),
+            KeyCode::Left | KeyCode::Char('h') => self.column = self.column.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.column = (self.column + 1).min(self.schema.fields.len().saturating_sub(1))
+            }
+            KeyCode::Enter => {
+                if let Some(record) = self.selected().cloned() {
+                    let comments = self.table.comments(&record.id).await?;
+                    self.mode = Mode::Detail {
+                        record: Box::new(record),
+                        comments,
+                        scroll: 0,
+                    };
+                }
+            }
+            KeyCode::Char('/') | KeyCode::Char('f') => {
+                self.mode = Mode::Filter(self.formula.clone().unwrap_or_default());
+            }
+            KeyCode::Char('s') => {
+                if let Some(field) = self.selected_field().map(|f| f.name.clone()) {
+                    self.sort = match self.sort.take() {
+                        Some((current, false)) if current == field => Some((field, true)),
+                        Some((current, true)) if current == field => None,
+                        _ => Some((field, false)),
+                    };
+                    self.reload().await?;
+                }
+            }
+            KeyCode::Char('v') => {
+                let selected = self
+                    .view
+                    .as_ref()
+                    .and_then(|name| self.schema.views.iter().position(|v| &v.name == name))
+                    .map_or(0, |i| i + 1);
+                self.mode = Mode::Views(selected);
+            }
+            KeyCode::Char('e') => self.start_edit(),
+            KeyCode::Char('r') => self.reload().await?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn move_down(&mut self, rows: usize) -> Result<()> {
+        let target = self.row + rows;
+        while target >= self.records.len() && self.next_offset.is_some() {
+            self.load_page().await?;
+        }
+        self.row = target.min(self.records.len().saturating_sub(1));
+        Ok(())
+    }
+
+    fn start_edit(&mut self) {
+        let (Some(record), Some(field)) = (self.selected(), self.selected_field()) else {
+            return;
+        };
+        if !EDITABLE_TYPES.contains(&field.field_type.as_str()) {
+            self.status = format!(
+                "{} is a {} field and cannot be edited here",
+                field.name, field.field_type
+            );
+            return;
+        }
+        let text = record
+            .fields
+            .get(&field.name)
+            .map(cell_text)
+            .unwrap_or_default();
+        self.mode = Mode::Edit {
+            field: field.name.clone(),
+            text,
+        };
+    }
+
+    async fn update_cell(&mut self, record_id: &str, field: &str, value: Value) -> Result<()> {
+        let updated = self
+            .table
+            .update(record_id, json!({ field: value }))
+            .await?;
+        if let Some(record) = self.records.iter_mut().find(|r| r.id == updated.id) {
+            *record = updated;
+        }
+        self.status = format!("Updated {} of {}", field, record_id);
+        Ok(())
+    }
+
+    /// Start again from the first page with the current filter, sort and view
+    async fn reload(&mut self) -> Result<()> {
+        self.records.clear();
+        self.next_offset = None;
+        self.row = 0;
+        self.table_state = TableState::default();
+        self.load_page().await
+    }
+
+    async fn load_page(&mut self) -> Result<()> {
+        let mut query = self.table.list().offset(self.next_offset.take());
+        if let Some(formula) = &self.formula {
+            query = query.filter_by_formula(formula.as_str());
+        }
+        if let Some(view) = &self.view {
+            query = query.view(view.as_str());
+        }
+        if let Some((field, descending)) = &self.sort {
+            let direction = if *descending { "desc" } else { "asc" };
+            query = query.sort(vec![format!("{} {}", field, direction)]);
+        }
+        let (records, offset) = query.execute().await?;
+        self.records.extend(records);
+        self.next_offset = offset;
+        Ok(())
+    }
+
+    /// Draw the browser into `frame`
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [title, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        frame.render_widget(Paragraph::new(self.title()), title);
+        if let Mode::Detail { .. } = self.mode {
+            let [grid, detail] =
+                Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                    .areas(body);
+            self.draw_grid(frame, grid);
+            self.draw_detail(frame, detail);
+        } else {
+            self.draw_grid(frame, body);
+        }
+        if let Mode::Views(selected) = self.mode {
+            self.draw_views(frame, body, selected);
+        }
+        frame.render_widget(Paragraph::new(self.footer()), footer);
+    }
+
+    fn title(&self) -> Line<'static> {
+        let mut parts = vec![self.schema.name.clone()];
+        if let Some(view) = &self.view {
+            parts.push(format!("view: {}", view));
+        }
+        if let Some(formula) = &self.formula {
+            parts.push(format!("filter: {}", formula));
+        }
+        if let Some((field, descending)) = &self.sort {
+            parts.push(format!(
+                "sort: {} {}",
+                field,
+                if *descending { "↓" } else { "↑" }
+            ));
+        }
+        let more = if self.next_offset.is_some() { "+" } else { "" };
+        parts.push(format!("{}{} records", self.records.len(), more));
+        Line::from(Span::styled(
+            parts.join(" · "),
+            Style::new().add_modifier(Modifier::BOLD),
+        ))
+    }
+
+    fn footer(&self) -> Line<'static> {
+        match &self.mode {
+            Mode::Filter(text) => Line::from(format!("Filter formula (empty clears): {}▏", text)),
+            Mode::Edit { field, text } => Line::from(format!("{}: {}▏", field, text)),
+            Mode::Confirm {
+                record_id,
+                field,
+                value,
+            } => Line::from(Span::styled(
+                format!(
+                    "Set {} of {} to {}? (y/n)",
+                    field,
+                    record_id,
+                    serde_json::to_string(value).unwrap_or_default()
+                ),
+                Style::new().add_modifier(Modifier::REVERSED),
+            )),
+            _ if !self.status.is_empty() => Line::from(self.status.clone()),
+            _ => Line::from(Span::styled(
+                KEY_HELP,
+                Style::new().add_modifier(Modifier::DIM),
+            )),
+        }
+    }
+
+    fn draw_grid(&mut self, frame: &mut Frame, area: Rect) {
+        let widths: Vec<usize> = self
+            .schema
+            .fields
+            .iter()
+            .map(|field| {
+                self.records
+                    .iter()
+                    .filter_map(|r| r.fields.get(&field.name))
+                    .map(|value| cell_text(value).chars().count())
+                    .chain([field.name.chars().count()])
+                    .max()
+                    .unwrap_or(0)
+                    .clamp(4, MAX_COLUMN_WIDTH)
+            })
+            .collect();
+
+        // Scroll horizontally so the selected column is the rightmost that fits
+        let available = usize::from(area.width.saturating_sub(2));
+        let mut first = self.column.min(widths.len().saturating_sub(1));
+        let mut used = widths.get(first).copied().unwrap_or(0);
+        while first > 0 && used + 1 + widths[first - 1] <= available {
+            first -= 1;
+            used += 1 + widths[first];
+        }
+        let columns = &self.schema.fields[first.min(self.schema.fields.len())..];
+
+        let header = Row::new(columns.iter().map(|f| Cell::from(f.name.clone())))
+            .style(Style::new().add_modifier(Modifier::BOLD | Modifier::UNDERLINED));
+        let rows = self.records.iter().map(|record| {
+            Row::new(columns.iter().map(|field| {
+                let text = record
+                    .fields
+                    .get(&field.name)
+                    .map(cell_text)
+                    .unwrap_or_default()
+                    .replace(['\n', '\r', '\t'], " ");
+                Cell::from(text)
+            }))
+        });
+        let constraints = widths[first.min(widths.len())..]
+            .iter()
+            .map(|w| Constraint::Length(*w as u16));
+
+        let grid = Table::new(rows, constraints)
+            .header(header)
+            .block(Block::bordered())
+            .row_highlight_style(Style::new().add_modifier(Modifier::BOLD))
+            .cell_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        self.table_state
+            .select(Some(self.row).filter(|_| !self.records.is_empty()));
+        self.table_state.select_column(Some(self.column - first));
+        frame.render_stateful_widget(grid, area, &mut self.table_state);
+    }
+
+    fn draw_detail(&self, frame: &mut Frame, area: Rect) {
+        let Mode::Detail {
+            record,
+            comments,
+            scroll,
+        } = &self.mode
+        else {
+            return;
+        };
+        let bold = Style::new().add_modifier(Modifier::BOLD);
+        let mut lines = vec![Line::from(vec![
+            Span::styled("id: ", bold),
+            Span::raw(record.id.clone()),
+        ])];
+        for field in &self.schema.fields {
+            if let Some(value) = record.fields.get(&field.name) {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{}: ", field.name), bold),
+                    Span::raw(cell_text(value)),
+                ]));
+            }
+        }
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            format!("Comments ({})", comments.len()),
+            bold,
+        )));
+        for comment in comments {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{} {}: ",
+                        comment.author.name,
+                        comment.created_time.format("%Y-%m-%d %H:%M")
+                    ),
+                    Style::new().add_modifier(Modifier::ITALIC),
+                ),
+                Span::raw(comment.text.clone()),
+            ]));
+        }
+
+        let detail = Paragraph::new(lines)
+            .block(Block::bordered().title(" Record (Esc to close) "))
+            .wrap(Wrap { trim: false })
+            .scroll((*scroll, 0));
+        frame.render_widget(detail, area);
+    }
+
+    fn draw_views(&self, frame: &mut Frame, area: Rect, selected: usize) {
+        let items: Vec<ListItem> = std::iter::once(ListItem::new("(all records)"))
+            .chain(
+                self.schema
+                    .views
+                    .iter()
+                    .map(|view| ListItem::new(format!("{} ({})", view.name, view.view_type))),
+            )
+            .collect();
+        let height = (items.len() as u16 + 2).min(area.height);
+        let width = (area.width / 2).max(30).min(area.width);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        let list = List::new(items)
+            .block(Block::bordered().title(" View (Enter to choose) "))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_widget(Clear, popup);
+        frame.render_stateful_widget(
+            list,
+            popup,
+            &mut ListState::default().with_selected(Some(selected)),
+        );
+    }
+}
+
+/// Apply a typing key to an input line
+fn edit_text(text: &mut String, code: KeyCode) {
+    match code {
+        KeyCode::Char(c) => text.push(c),
+        KeyCode::Backspace => {
+            text.pop();
+        }
+        _ => {}
+    }
+}
+
+/// Value to send for `text` typed into a cell of `field`; empty clears the cell
+fn parse_cell(field: &FieldSchema, text: &str) -> std::result::Result<Value, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(Value::Null);
+    }
+    match field.field_type.as_str() {
+        "number" | "currency" | "percent" | "rating" | "duration" => {
+            if let Ok(n) = text.parse::<i64>() {
+                return Ok(Value::from(n));
+            }
+            text.parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number)
+                .ok_or_else(|| format!("{} needs a number", field.name))
+        }
+        "checkbox" => match text.to_lowercase().as_str() {
+            "true" | "yes" | "y" | "x" | "1" => Ok(Value::Bool(true)),
+            "false" | "no" | "n" | "0" => Ok(Value::Bool(false)),
+            _ => Err(format!("{} needs yes or no", field.name)),
+        },
+        "multipleSelects" => Ok(Value::Array(
+            text.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(Value::from)
+                .collect(),
+        )),
+        _ => Ok(Value::from(text)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cell_by_field_type() {
+        let field = |field_type: &str| FieldSchema {
+            id: "fld1".to_string(),
+            name: "F".to_string(),
+            field_type: field_type.to_string(),
+            options: None,
+            description: None,
+        };
+        assert_eq!(parse_cell(&field("number"), "42"), Ok(json!(42)));
+        assert_eq!(parse_cell(&field("currency"), "1.5"), Ok(json!(1.5)));
+        assert!(parse_cell(&field("number"), "lots").is_err());
+        assert_eq!(parse_cell(&field("checkbox"), "Yes"), Ok(json!(true)));
+        assert_eq!(
+            parse_cell(&field("multipleSelects"), "a, b,"),
+            Ok(json!(["a", "b"]))
+        );
+        assert_eq!(parse_cell(&field("singleLineText"), " x "), Ok(json!("x")));
+        assert_eq!(parse_cell(&field("date"), ""), Ok(Value::Null));
+    }
+}
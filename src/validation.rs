@@ -0,0 +1,383 @@
+//! Schema-aware validation of record writes
+//!
+//! This module checks record payloads against a [`TableSchema`] before they are
+//! sent to Airtable, so that every problem in a batch is reported at once instead
+//! of Airtable rejecting the payload one request at a time.
+//!
+//! Validation is opt-in: attach a [`WriteValidator`] to a table with
+//! [`TableHandle::with_validator`](crate::client::TableHandle::with_validator) or
+//! [`TableHandle::validated`](crate::client::TableHandle::validated), or call
+//! [`WriteValidator::validate_batch`] directly on a whole import before sending it.
+
+use crate::error::{Error, Result};
+use crate::models::{FieldSchema, TableSchema};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A single problem found in a record write
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    /// Index of the record within the batch (0 for single-record writes)
+    pub row: usize,
+    /// Field name (or ID) as given in the payload
+    pub field: String,
+    /// What is wrong with the value
+    pub problem: ValidationProblem,
+}
+
+/// Kinds of problems detected by [`WriteValidator`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationProblem {
+    /// The record payload is not a JSON object of fields
+    NotAnObject,
+    /// No field with this name or ID exists in the table
+    UnknownField,
+    /// The field is computed by Airtable (formula, rollup, lookup, ...)
+    ComputedField { field_type: String },
+    /// The value has the wrong JSON shape for the field type
+    WrongType {
+        field_type: String,
+        expected: &'static str,
+    },
+    /// A select value is not one of the field's choices
+    InvalidChoice { value: String },
+    /// A linked record ID is not of the form `recXXXXXXXXXXXXXX`
+    InvalidRecordId { value: String },
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.problem {
+            ValidationProblem::NotAnObject => {
+                write!(f, "row {}: record fields must be a JSON object", self.row)
+            }
+            ValidationProblem::UnknownField => {
+                write!(f, "row {}: unknown field '{}'", self.row, self.field)
+            }
+            ValidationProblem::ComputedField { field_type } => write!(
+                f,
+                "row {}: field '{}' is a computed {} field and cannot be written",
+                self.row, self.field, field_type
+            ),
+            ValidationProblem::WrongType {
+                field_type,
+                expected,
+            } => write!(
+                f,
+                "row {}: field '{}' ({}) expects {}",
+                self.row, self.field, field_type, expected
+            ),
+            ValidationProblem::InvalidChoice { value } => write!(
+                f,
+                "row {}: '{}' is not a choice of field '{}' (enable typecast to create it)",
+                self.row, value, self.field
+            ),
+            ValidationProblem::InvalidRecordId { value } => write!(
+                f,
+                "row {}: field '{}' contains malformed record ID '{}'",
+                self.row, self.field, value
+            ),
+        }
+    }
+}
+
+/// Validates record writes against a table schema
+#[derive(Debug, Clone)]
+pub struct WriteValidator {
+    fields: Vec<FieldSchema>,
+    /// Index into `fields` by field name and by field ID
+    lookup: HashMap<String, usize>,
+}
+
+impl WriteValidator {
+    /// Create a validator for the given table schema
+    pub fn new(schema: &TableSchema) -> Self {
+        let fields = schema.fields.clone();
+        let mut lookup = HashMap::new();
+        for (index, field) in fields.iter().enumerate() {
+            lookup.insert(field.name.clone(), index);
+            lookup.insert(field.id.clone(), index);
+        }
+        Self { fields, lookup }
+    }
+
+    /// Check a single record's fields and return every problem found
+    pub fn check(&self, row: usize, fields: &Value, typecast: bool) -> Vec<ValidationIssue> {
+        let Some(fields) = fields.as_object() else {
+            return vec![ValidationIssue {
+                row,
+                field: String::new(),
+                problem: ValidationProblem::NotAnObject,
+            }];
+        };
+
+        let mut issues = Vec::new();
+        for (name, value) in fields {
+            let problems = match self.lookup.get(name) {
+                Some(&index) => check_value(&self.fields[index], value, typecast),
+                None => vec![ValidationProblem::UnknownField],
+            };
+            issues.extend(problems.into_iter().map(|problem| ValidationIssue {
+                row,
+                field: name.clone(),
+                problem,
+            }));
+        }
+        issues
+    }
+
+    /// Validate a single record's fields
+    pub fn validate(&self, fields: &Value, typecast: bool) -> Result<()> {
+        self.validate_batch([fields], typecast)
+    }
+
+    /// Validate a batch of records, reporting all problems together
+    ///
+    /// Rows are numbered by their position in `records`.
+    pub fn validate_batch<'a, I>(&self, records: I, typecast: bool) -> Result<()>
+    where
+        I: IntoIterator<Item = &'a Value>,
+    {
+        let issues: Vec<ValidationIssue> = records
+            .into_iter()
+            .enumerate()
+            .flat_map(|(row, fields)| self.check(row, fields, typecast))
+            .collect();
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation { issues })
+        }
+    }
+}
+
+/// Check one value against its field definition
+fn check_value(field: &FieldSchema, value: &Value, typecast: bool) -> Vec<ValidationProblem> {
+    let field_type = field.field_type.as_str();
+
+    if field.is_computed() {
+        return vec![ValidationProblem::ComputedField {
+            field_type: field.field_type.clone(),
+        }];
+    }
+
+    // Null clears the field for every writable type
+    if value.is_null() {
+        return Vec::new();
+    }
+
+    let wrong_type = |expected: &'static str| {
+        vec![ValidationProblem::WrongType {
+            field_type: field.field_type.clone(),
+            expected,
+        }]
+    };
+
+    // With typecast enabled Airtable converts strings into most field types
+    let typecast_string = typecast && value.is_string();
+
+    match field_type {
+        "singleLineText" | "multilineText" | "richText" | "email" | "url" | "phoneNumber"
+        | "date" | "dateTime"
+            if !value.is_string() && !typecast =>
+        {
+            wrong_type("a string")
+        }
+        "number" | "currency" | "percent" | "duration" | "rating"
+            if !value.is_number() && !typecast_string =>
+        {
+            wrong_type("a number")
+        }
+        "checkbox" if !value.is_boolean() && !typecast_string => wrong_type("true or false"),
+        "singleSelect" => {
+            let Some(choice) = value.as_str() else {
+                return wrong_type("a string choice name");
+            };
+            if !typecast && !has_choice(field, choice) {
+                vec![ValidationProblem::InvalidChoice {
+                    value: choice.to_string(),
+                }]
+            } else {
+                Vec::new()
+            }
+        }
+        "multipleSelects" => {
+            if typecast_string {
+                return Vec::new();
+            }
+            let Some(choices) = value.as_array().filter(|a| a.iter().all(Value::is_string)) else {
+                return wrong_type("an array of string choice names");
+            };
+            if typecast {
+                return Vec::new();
+            }
+            choices
+                .iter()
+                .filter_map(Value::as_str)
+                .filter(|choice| !has_choice(field, choice))
+                .map(|choice| ValidationProblem::InvalidChoice {
+                    value: choice.to_string(),
+                })
+                .collect()
+        }
+        "multipleRecordLinks" => {
+            if typecast_string {
+                return Vec::new();
+            }
+            let Some(links) = value.as_array() else {
+                return wrong_type("an array of record IDs");
+            };
+            let mut problems = Vec::new();
+            for link in links {
+                let id = link
+                    .as_str()
+                    .or_else(|| link.get("id").and_then(Value::as_str));
+                match id {
+                    // With typecast Airtable matches strings against the primary field
+                    Some(_) if typecast => {}
+                    Some(id) if is_record_id(id) => {}
+                    Some(id) => problems.push(ValidationProblem::InvalidRecordId {
+                        value: id.to_string(),
+                    }),
+                    None => return wrong_type("an array of record IDs"),
+                }
+            }
+            problems
+        }
+        "singleCollaborator" if !is_collaborator(value) => {
+            wrong_type("an object with an id or email")
+        }
+        "multipleCollaborators" if !all_items(value, is_collaborator) => {
+            wrong_type("an array of objects with an id or email")
+        }
+        "multipleAttachments" if !all_items(value, is_attachment) => {
+            wrong_type("an array of objects with a url")
+        }
+        "barcode" if !value.get("text").is_some_and(Value::is_string) => {
+            wrong_type("an object with a text string")
+        }
+        // Valid values, and unknown or newer field types left for Airtable to check
+        _ => Vec::new(),
+    }
+}
+
+/// Whether a value is an array whose items all satisfy `predicate`
+fn all_items(value: &Value, predicate: fn(&Value) -> bool) -> bool {
+    value
+        .as_array()
+        .is_some_and(|items| items.iter().all(predicate))
+}
+
+/// Whether a value references an attachment by URL or existing ID
+fn is_attachment(value: &Value) -> bool {
+    value.get("url").is_some_and(Value::is_string) || value.get("id").is_some()
+}
+
+/// Whether a select field lists the given choice name
+fn has_choice(field: &FieldSchema, choice: &str) -> bool {
+    field
+        .options
+        .as_ref()
+        .and_then(|options| options.get("choices"))
+        .and_then(Value::as_array)
+        .is_some_and(|choices| {
+            choices
+                .iter()
+                .any(|c| c.get("name").and_then(Value::as_str) == Some(choice))
+        })
+}
+
+/// Whether a value references a collaborator by ID or email
+fn is_collaborator(value: &Value) -> bool {
+    value.get("id").is_some_and(Value::is_string)
+        || value.get("email").is_some_and(Value::is_string)
+}
+
+/// Whether a string looks like an Airtable record ID (`rec` + 14 alphanumerics)
+fn is_record_id(id: &str) -> bool {
+    id.len() == 17 && id.starts_with("rec") && id[3..].chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn test_schema() -> TableSchema {
+        serde_json::from_value(json!({
+            "id": "tblTestTable00001",
+            "name": "Cases",
+            "primaryFieldId": "fldName0000000001",
+            "fields": [
+                {"id": "fldName0000000001", "name": "Name", "type": "singleLineText"},
+                {"id": "fldCount000000001", "name": "Hours", "type": "number"},
+                {"id": "fldDone0000000001", "name": "Closed", "type": "checkbox"},
+                {"id": "fldTotal000000001", "name": "Total", "type": "formula"},
+                {"id": "fldStage000000001", "name": "Stage", "type": "singleSelect",
+                 "options": {"choices": [{"name": "Open"}, {"name": "Closed"}]}},
+                {"id": "fldTags0000000001", "name": "Tags", "type": "multipleSelects",
+                 "options": {"choices": [{"name": "urgent"}]}},
+                {"id": "fldClient00000001", "name": "Client", "type": "multipleRecordLinks"}
+            ],
+            "views": []
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_valid_write_passes() {
+        let validator = WriteValidator::new(&test_schema());
+        let fields = json!({
+            "Name": "Smith v. Jones",
+            "fldCount000000001": 4.5,
+            "Closed": false,
+            "Stage": "Open",
+            "Tags": ["urgent"],
+            "Client": ["recAAAAAAAAAAAAAA"]
+        });
+        assert!(validator.validate(&fields, false).is_ok());
+    }
+
+    #[test]
+    fn test_reports_every_problem_in_batch() {
+        let validator = WriteValidator::new(&test_schema());
+        let records = [
+            json!({"Name": "ok"}),
+            json!({"Nmae": "typo", "Total": 3}),
+            json!({"Hours": "four", "Stage": "Pending", "Client": ["12345"]}),
+        ];
+
+        let Err(Error::Validation { issues }) = validator.validate_batch(&records, false) else {
+            panic!("expected validation error");
+        };
+
+        let problems: Vec<(usize, &str)> = issues
+            .iter()
+            .map(|issue| (issue.row, issue.field.as_str()))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (1, "Nmae"),
+                (1, "Total"),
+                (2, "Client"),
+                (2, "Hours"),
+                (2, "Stage")
+            ]
+        );
+        assert!(matches!(
+            issues[1].problem,
+            ValidationProblem::ComputedField { .. }
+        ));
+    }
+
+    #[test]
+    fn test_typecast_relaxes_choices_and_strings() {
+        let validator = WriteValidator::new(&test_schema());
+        let fields = json!({"Hours": "4", "Stage": "Pending", "Tags": ["new"], "Closed": "yes"});
+        assert!(validator.check(0, &fields, false).len() == 4);
+        assert!(validator.validate(&fields, true).is_ok());
+    }
+}
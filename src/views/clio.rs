@@ -21,6 +21,12 @@ impl ClioView {
     }
 }
 
+impl Default for ClioView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ViewProcessor for ClioView {
     fn name(&self) -> &'static str {
         "clio"
@@ -50,7 +56,7 @@ impl ViewProcessor for ClioView {
         match record.fields.get("Clio Matter ID") {
             Some(value) => {
                 // Check if value is not null and not an empty string
-                !value.is_null() && value.as_str().map_or(false, |s| !s.trim().is_empty())
+                !value.is_null() && value.as_str().is_some_and(|s| !s.trim().is_empty())
             }
             None => false,
         }
@@ -116,7 +122,7 @@ mod tests {
         use chrono::{DateTime, Utc};
         Record {
             id: id.to_string(),
-            fields,
+            fields: fields.into_iter().collect(),
             created_time: DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
                 .unwrap()
                 .with_timezone(&Utc),
@@ -169,7 +169,7 @@ async fn test_step4_config_integration() {
         .with_verbose(true)
         .with_timeout(30);
 
-    let client = Client::from_config(config);
+    let client = Client::from_config(config).expect("Should create client from config");
 
     // Test that configured client works
     match client.whoami().await {
@@ -202,7 +202,7 @@ fn test_step4_client_configuration() {
         .with_verbose(true)
         .with_endpoint_url("https://api.custom.airtable.com/v0");
 
-    let _client2 = Client::from_config(config);
+    let _client2 = Client::from_config(config).expect("Should create client from config");
     // Verify creation succeeded
 
     // Test environment-based creation (if env vars available)
@@ -0,0 +1,122 @@
+//! Base backup and restore tests against the in-process fake Airtable server
+
+use rsairtable::testing::FakeAirtable;
+use rsairtable::{BackupManifest, BackupOptions};
+use serde_json::json;
+
+const SOURCE_BASE: &str = "appFakeBase000001";
+const TARGET_BASE: &str = "appFakeBase000002";
+
+#[tokio::test]
+async fn test_backup_and_restore_round_trip() {
+    let dir = std::env::temp_dir().join(format!("rsairtable-backup-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let fake = FakeAirtable::start().await.unwrap();
+    let authors_id = fake.add_table(SOURCE_BASE, "Authors", &[("Name", "singleLineText")]);
+    let books_id = fake.add_table(
+        SOURCE_BASE,
+        "Books",
+        &[
+            ("Title", "singleLineText"),
+            ("Pages", "number"),
+            ("Summary", "formula"),
+        ],
+    );
+    let source = fake.client().base(SOURCE_BASE);
+    // Creates the inverse "Authors" field in Books
+    source
+        .table("Authors")
+        .create_field(json!({
+            "name": "Books",
+            "type": "multipleRecordLinks",
+            "options": { "linkedTableId": books_id }
+        }))
+        .await
+        .unwrap();
+
+    let books = fake.insert(
+        SOURCE_BASE,
+        "Books",
+        vec![
+            json!({ "Title": "Earthsea", "Pages": 183 }),
+            json!({ "Title": "Dune", "Pages": 412 }),
+        ],
+    );
+    fake.insert(
+        SOURCE_BASE,
+        "Authors",
+        vec![
+            json!({ "Name": "Le Guin", "Books": [books[0].id] }),
+            json!({ "Name": "Herbert", "Books": [books[1].id] }),
+        ],
+    );
+    source
+        .table("Books")
+        .add_comment(&books[1].id, "First edition")
+        .await
+        .unwrap();
+
+    let manifest = source
+        .backup(&dir, &BackupOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(manifest.tables.len(), 2);
+    assert_eq!(manifest.tables[1].records, 2);
+    assert_eq!(manifest.tables[1].comments, 1);
+    let written: BackupManifest =
+        serde_json::from_slice(&std::fs::read(dir.join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(written.base_id, SOURCE_BASE);
+    let lines =
+        std::fs::read_to_string(dir.join("tables").join(format!("{}.ndjson", authors_id))).unwrap();
+    assert_eq!(lines.lines().count(), 2);
+
+    fake.add_base(TARGET_BASE, "Restored");
+    let report = fake.client().base(TARGET_BASE).restore(&dir).await.unwrap();
+    assert_eq!(report.skipped_fields, vec!["Books.Summary (formula)"]);
+    assert_eq!(report.tables[0].linked, 2);
+    assert_eq!(report.tables[1].comments, 1);
+
+    let new_authors = fake.records(TARGET_BASE, "Authors");
+    let new_books = fake.records(TARGET_BASE, "Books");
+    let dune = new_books
+        .iter()
+        .find(|r| r.fields["Title"] == "Dune")
+        .unwrap();
+    let herbert = new_authors
+        .iter()
+        .find(|r| r.fields["Name"] == "Herbert")
+        .unwrap();
+    assert_eq!(dune.fields["Pages"], json!(412));
+    assert_eq!(herbert.fields["Books"], json!([dune.id]));
+    assert!(!dune.fields.contains_key("Summary"));
+
+    let comments = fake
+        .client()
+        .base(TARGET_BASE)
+        .table("Books")
+        .comments(&dune.id)
+        .await
+        .unwrap();
+    assert!(comments[0].text.starts_with("[Fake User at "));
+    assert!(comments[0].text.ends_with("] First edition"));
+
+    // The inverse link field is created by Airtable rather than copied from the backup
+    let schema = fake.client().base(TARGET_BASE).schema().await.unwrap();
+    let link_fields: Vec<(&str, &str)> = schema
+        .tables
+        .iter()
+        .flat_map(|t| t.fields.iter().map(move |f| (t, f)))
+        .filter(|(_, f)| f.field_type == "multipleRecordLinks")
+        .map(|(t, f)| (t.name.as_str(), f.name.as_str()))
+        .collect();
+    assert_eq!(
+        link_fields,
+        vec![("Authors", "Books"), ("Books", "Authors")]
+    );
+    // Computed fields are neither created nor written
+    let books = schema.tables.iter().find(|t| t.name == "Books").unwrap();
+    assert!(books.fields.iter().all(|f| f.name != "Summary"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
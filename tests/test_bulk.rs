@@ -0,0 +1,106 @@
+//! Streaming NDJSON bulk write tests against the in-process fake Airtable server
+
+use rsairtable::testing::FakeAirtable;
+use rsairtable::{BulkFailure, BulkOperation, BulkOutcome};
+use serde_json::json;
+
+const BASE_ID: &str = "appFakeBase000001";
+
+async fn run(
+    fake: &FakeAirtable,
+    operation: BulkOperation,
+    input: &str,
+) -> (Vec<BulkOutcome>, Vec<BulkFailure>) {
+    let table = fake.client().base(BASE_ID).table("People");
+    let mut outcomes = Vec::new();
+    let mut failures = Vec::new();
+    let summary = table
+        .bulk_write(&operation, input.as_bytes(), |result| match result {
+            Ok(outcome) => outcomes.push(outcome),
+            Err(failure) => failures.push(failure),
+        })
+        .await
+        .unwrap();
+    assert_eq!(summary.succeeded, outcomes.len());
+    assert_eq!(summary.failed, failures.len());
+    (outcomes, failures)
+}
+
+#[tokio::test]
+async fn test_bulk_create_batches_and_reports_bad_lines() {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_table(BASE_ID, "People", &[("Name", "singleLineText")]);
+
+    let mut input: String = (1..=12)
+        .map(|i| format!("{{\"fields\": {{\"Name\": \"P{}\"}}}}\n", i))
+        .collect();
+    input.push_str("\nnot json\n{\"Name\": \"Last\"}\n");
+
+    let (outcomes, failures) = run(&fake, BulkOperation::Create, &input).await;
+    assert_eq!(outcomes.len(), 13);
+    assert!(outcomes.iter().all(|o| o.status == "created"));
+    assert_eq!(outcomes[12].line, 15);
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].line, 14);
+    assert!(failures[0].to_string().starts_with("line 14: invalid JSON"));
+
+    let records = fake.records(BASE_ID, "People");
+    assert_eq!(records.len(), 13);
+    assert_eq!(records[12].fields["Name"], json!("Last"));
+}
+
+#[tokio::test]
+async fn test_bulk_update_upsert_and_delete() {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_table(
+        BASE_ID,
+        "People",
+        &[("Email", "email"), ("Name", "singleLineText")],
+    );
+    fake.insert(
+        BASE_ID,
+        "People",
+        vec![
+            json!({ "Email": "ada@example.com", "Name": "Ada" }),
+            json!({ "Email": "bob@example.com", "Name": "Bob" }),
+        ],
+    );
+    let ids: Vec<String> = fake
+        .records(BASE_ID, "People")
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+
+    let update = format!(
+        "{{\"id\": \"{}\", \"fields\": {{\"Name\": \"Ada L.\"}}}}\n{{\"Name\": \"no id\"}}\n",
+        ids[0]
+    );
+    let (outcomes, failures) = run(&fake, BulkOperation::Update, &update).await;
+    assert_eq!(outcomes[0].id, ids[0]);
+    assert_eq!(outcomes[0].status, "updated");
+    assert_eq!(failures[0].line, 2);
+
+    let upsert = "{\"Email\": \"bob@example.com\", \"Name\": \"Robert\"}\n\
+                  {\"Email\": \"cy@example.com\", \"Name\": \"Cy\"}\n";
+    let (outcomes, _) = run(
+        &fake,
+        BulkOperation::Upsert(vec!["Email".to_string()]),
+        upsert,
+    )
+    .await;
+    let statuses: Vec<&str> = outcomes.iter().map(|o| o.status).collect();
+    assert_eq!(statuses, vec!["updated", "created"]);
+    assert_eq!(outcomes[0].id, ids[1]);
+
+    let delete = format!("{}\n\"{}\"\n", ids[0], ids[1]);
+    let (outcomes, failures) = run(&fake, BulkOperation::Delete, &delete).await;
+    assert!(failures.is_empty());
+    assert!(outcomes.iter().all(|o| o.status == "deleted"));
+
+    let names: Vec<_> = fake
+        .records(BASE_ID, "People")
+        .iter()
+        .map(|r| r.fields["Name"].clone())
+        .collect();
+    assert_eq!(names, vec![json!("Cy")]);
+}
@@ -0,0 +1,149 @@
+//! Response cache tests against the in-process fake Airtable server
+
+use rsairtable::testing::FakeAirtable;
+use rsairtable::{
+    CacheEndpoint, Client, Config, Error, FieldDefinition, ReqwestTransport, ResponseCache,
+    TableDefinition,
+};
+use serde_json::json;
+use std::time::Duration;
+
+const BASE_ID: &str = "appFakeBase000001";
+
+async fn fake_with_tables() -> FakeAirtable {
+    let fake = FakeAirtable::start().await.unwrap();
+    for table in ["Cases", "CasesArchive"] {
+        fake.add_table(BASE_ID, table, &[("Name", "singleLineText")]);
+        fake.insert(BASE_ID, table, vec![json!({ "Name": "Smith v. Jones" })]);
+    }
+    fake
+}
+
+#[tokio::test]
+async fn test_cache_serves_repeated_reads() {
+    let fake = fake_with_tables().await;
+    let client = fake.client().with_cache(ResponseCache::in_memory(16));
+    let table = client.base(BASE_ID).table("Cases");
+
+    for _ in 0..5 {
+        assert_eq!(table.all().await.unwrap().len(), 1);
+    }
+    client.whoami().await.unwrap();
+    client.whoami().await.unwrap();
+    table.schema().await.unwrap();
+    table.schema().await.unwrap();
+    assert_eq!(fake.request_count(), 3, "one request per distinct read");
+
+    // Different queries are cached separately
+    table.list().max_records(1).execute().await.unwrap();
+    assert_eq!(fake.request_count(), 4);
+}
+
+#[tokio::test]
+async fn test_writes_invalidate_only_the_written_table() {
+    let fake = fake_with_tables().await;
+    let client = fake.client().with_cache(ResponseCache::in_memory(16));
+    let cases = client.base(BASE_ID).table("Cases");
+    let archive = client.base(BASE_ID).table("CasesArchive");
+
+    cases.all().await.unwrap();
+    archive.all().await.unwrap();
+    assert_eq!(fake.request_count(), 2);
+
+    // A clone of the client shares the cache
+    client
+        .clone()
+        .base(BASE_ID)
+        .table("Cases")
+        .create(json!({ "Name": "Doe v. Roe" }))
+        .await
+        .unwrap();
+    assert_eq!(
+        cases.all().await.unwrap().len(),
+        2,
+        "fresh read after write"
+    );
+    archive.all().await.unwrap();
+    assert_eq!(fake.request_count(), 4, "archive still served from cache");
+}
+
+#[tokio::test]
+async fn test_cache_ttls_and_disk_store() {
+    let dir = std::env::temp_dir().join(format!("rsairtable-test-cache-{}", std::process::id()));
+    let fake = fake_with_tables().await;
+    let cache = || {
+        ResponseCache::on_disk(&dir)
+            .with_ttl(CacheEndpoint::List, Duration::from_millis(200))
+            .with_ttl(CacheEndpoint::Whoami, Duration::ZERO)
+    };
+
+    let table = fake
+        .client()
+        .with_cache(cache())
+        .base(BASE_ID)
+        .table("Cases");
+    table.all().await.unwrap();
+
+    // A new client reads the entry written to disk by the first one
+    let client = fake.client().with_cache(cache());
+    client.base(BASE_ID).table("Cases").all().await.unwrap();
+    assert_eq!(fake.request_count(), 1);
+
+    client.whoami().await.unwrap();
+    client.whoami().await.unwrap();
+    assert_eq!(fake.request_count(), 3, "whoami caching disabled");
+
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    client.base(BASE_ID).table("Cases").all().await.unwrap();
+    assert_eq!(fake.request_count(), 4, "expired entries are refetched");
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn test_cache_entries_are_scoped_to_the_token() {
+    let dir = std::env::temp_dir().join(format!(
+        "rsairtable-test-cache-token-{}",
+        std::process::id()
+    ));
+    let fake = fake_with_tables().await;
+
+    let client = fake.client().with_cache(ResponseCache::on_disk(&dir));
+    client.base(BASE_ID).table("Cases").all().await.unwrap();
+
+    // Another token sharing the store must not be served the first token's read
+    let config = Config::new("patOtherToken.0000".to_string()).with_endpoint_url(fake.url());
+    let other = Client::with_transport(config, ReqwestTransport::new())
+        .with_cache(ResponseCache::on_disk(&dir));
+    let error = other.base(BASE_ID).table("Cases").all().await.unwrap_err();
+    assert!(matches!(error, Error::Api { status: 401, .. }), "{error:?}");
+    assert_eq!(fake.request_count(), 2);
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn test_create_base_invalidates_the_base_list() {
+    let fake = fake_with_tables().await;
+    let client = fake.client().with_cache(ResponseCache::in_memory(16));
+
+    assert_eq!(client.bases().await.unwrap().len(), 1);
+    assert_eq!(client.bases().await.unwrap().len(), 1);
+    assert_eq!(fake.request_count(), 1);
+
+    let fields = vec![FieldDefinition::new("Name", "singleLineText")];
+    let created = client
+        .create_base(
+            "wspFakeWorkspace1",
+            "Matters",
+            vec![TableDefinition::new("Cases", fields)],
+        )
+        .await
+        .unwrap();
+    let bases = client.bases().await.unwrap();
+    assert_eq!(
+        bases.last().unwrap().id,
+        created.id,
+        "fresh list after create"
+    );
+}
@@ -0,0 +1,118 @@
+//! Record/replay cassette tests
+//!
+//! Interactions are recorded against the in-process fake Airtable server and
+//! replayed after the server has been shut down.
+
+use rsairtable::testing::{FakeAirtable, FAKE_API_KEY};
+use rsairtable::{CassetteMode, Client, Config, Error};
+use serde_json::json;
+use std::path::PathBuf;
+
+const BASE_ID: &str = "appFakeBase000001";
+
+fn cassette_path(name: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("rsairtable-cassettes-{}", std::process::id()))
+        .join(format!("{}.json", name))
+}
+
+#[tokio::test]
+async fn test_cassette_records_and_replays() {
+    let path = cassette_path("roundtrip");
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_table(BASE_ID, "Cases", &[("Name", "singleLineText")]);
+    fake.insert(BASE_ID, "Cases", vec![json!({ "Name": "Smith v. Jones" })]);
+
+    let recording = Client::from_config(fake.config().with_cassette(&path, CassetteMode::Record))
+        .expect("record mode should not need an existing cassette");
+    let table = recording.base(BASE_ID).table("Cases");
+    let recorded = table.all().await.unwrap();
+    let created = table.create(json!({ "Name": "Doe v. Roe" })).await.unwrap();
+
+    let endpoint = fake.url().to_string();
+    drop(fake);
+
+    let cassette = std::fs::read_to_string(&path).unwrap();
+    assert!(
+        !cassette.contains(FAKE_API_KEY),
+        "Authorization must be scrubbed"
+    );
+    assert!(cassette.contains("[REDACTED]"));
+
+    let config = Config::new(FAKE_API_KEY.to_string())
+        .with_endpoint_url(endpoint)
+        .with_cassette(&path, CassetteMode::Replay);
+    let replaying = Client::from_config(config).unwrap();
+    let table = replaying.base(BASE_ID).table("Cases");
+
+    let replayed = table.all().await.unwrap();
+    assert_eq!(replayed.len(), recorded.len());
+    assert_eq!(replayed[0].id, recorded[0].id);
+    assert_eq!(
+        table
+            .create(json!({ "Name": "Doe v. Roe" }))
+            .await
+            .unwrap()
+            .id,
+        created.id
+    );
+
+    // Each interaction is served once; a repeat is an unexpected request
+    match table.create(json!({ "Name": "Doe v. Roe" })).await {
+        Err(Error::Other(message)) => assert!(message.contains("Unexpected request")),
+        other => panic!("expected unexpected-request error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_cassette_replays_errors_and_rejects_different_bodies() {
+    let path = cassette_path("errors");
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_table(BASE_ID, "Cases", &[("Name", "singleLineText")]);
+
+    let recording =
+        Client::from_config(fake.config().with_cassette(&path, CassetteMode::Record)).unwrap();
+    let table = recording.base(BASE_ID).table("Cases");
+    assert!(table.get("recMissing0000001").await.is_err());
+    table.create(json!({ "Name": "A" })).await.unwrap();
+    let config = fake.config().with_cassette(&path, CassetteMode::Replay);
+    drop(fake);
+
+    let replaying = Client::from_config(config).unwrap();
+    let table = replaying.base(BASE_ID).table("Cases");
+
+    match table.get("recMissing0000001").await {
+        Err(Error::Api { status, .. }) => assert_eq!(status, 404),
+        other => panic!("expected replayed 404, got {:?}", other),
+    }
+    assert!(matches!(
+        table.create(json!({ "Name": "B" })).await,
+        Err(Error::Other(_))
+    ));
+}
+
+#[test]
+fn test_cassette_replay_requires_existing_file() {
+    let config = Config::new(FAKE_API_KEY.to_string())
+        .with_cassette(cassette_path("missing"), CassetteMode::Replay);
+    match Client::from_config(config) {
+        Err(Error::Config(message)) => assert!(message.contains("Failed to read cassette")),
+        other => panic!("expected config error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn test_invalid_cassette_mode_is_a_config_error() {
+    // The only test in this binary that reads the environment
+    std::env::set_var("PERSONAL_ACCESS_TOKEN", FAKE_API_KEY);
+    std::env::set_var("AIRTABLE_CASSETTE_MODE", "playback");
+    let result = Config::from_env();
+    std::env::remove_var("AIRTABLE_CASSETTE_MODE");
+
+    match result {
+        Err(Error::Config(message)) => {
+            assert!(message.contains("Invalid cassette mode 'playback'"))
+        }
+        other => panic!("expected config error, got {:?}", other),
+    }
+}
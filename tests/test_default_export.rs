@@ -0,0 +1,134 @@
+//! The CLI's default export run against the in-process fake Airtable server
+
+use rsairtable::testing::{FakeAirtable, FAKE_API_KEY};
+use serde_json::json;
+use std::process::Command;
+
+const BASE_ID: &str = "appFakeBase000001";
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_default_export_paginates_and_writes_configured_file() {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_table(
+        BASE_ID,
+        "Matters",
+        &[("Name", "singleLineText"), ("Notes", "multilineText")],
+    );
+    let records = (1..=150)
+        .map(|i| json!({ "Name": format!("M-{:03}", i), "Notes": "n" }))
+        .collect();
+    fake.insert(BASE_ID, "Matters", records);
+
+    let dir =
+        std::env::temp_dir().join(format!("rsairtable-default-export-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("rsairtable")).unwrap();
+    let output_path = dir.join("matters.csv");
+    let config = format!(
+        "[profiles.fake]\ntoken = \"{}\"\nbase = \"{}\"\nendpoint_url = \"{}\"\n\n\
+         [profiles.fake.default_export]\ntable = \"Matters\"\nfields = [\"Name\"]\n\
+         output = \"{}\"\nformat = \"csv\"\n",
+        FAKE_API_KEY,
+        BASE_ID,
+        fake.url(),
+        output_path.display()
+    );
+    std::fs::write(dir.join("rsairtable/config.toml"), config).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rsairtable"))
+        .env("XDG_CONFIG_HOME", &dir)
+        .env("RSAIRTABLE_PROFILE", "fake")
+        .env_remove("RSAIRTABLE_CONFIG")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "default command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let csv = std::fs::read_to_string(&output_path).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "id,Name");
+    assert_eq!(lines.len(), 151);
+    assert!(lines[150].ends_with(",M-150"));
+    assert_eq!(
+        std::fs::read_dir(&dir).unwrap().count(),
+        2,
+        "no temp file left"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_default_dry_run_shows_the_base_the_export_uses() {
+    const OTHER_ID: &str = "appFakeBase000002";
+    let fake = FakeAirtable::start().await.unwrap();
+    for (base_id, name) in [(BASE_ID, "M-firm"), (OTHER_ID, "M-other")] {
+        fake.add_table(base_id, "Matters", &[("Name", "singleLineText")]);
+        fake.insert(base_id, "Matters", vec![json!({ "Name": name })]);
+    }
+
+    let dir = std::env::temp_dir().join(format!("rsairtable-default-base-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("rsairtable")).unwrap();
+    let config = format!(
+        "[profiles.fake]\ntoken = \"{}\"\nendpoint_url = \"{}\"\n\n\
+         [profiles.fake.default_export]\ntable = \"Matters\"\noutput = \"matters.json\"\n",
+        FAKE_API_KEY,
+        fake.url()
+    );
+    std::fs::write(dir.join("rsairtable/config.toml"), config).unwrap();
+    // Run in `dir` so no .env file supplies a key
+    let rsairtable = |args: &[&str]| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_rsairtable"));
+        command
+            .args(args)
+            .current_dir(&dir)
+            .env("XDG_CONFIG_HOME", &dir)
+            .env("XDG_CACHE_HOME", &dir)
+            .env("BASE", OTHER_ID)
+            .env_remove("RSAIRTABLE_CONFIG")
+            .env_remove("RSAIRTABLE_PROFILE")
+            .env_remove("PERSONAL_ACCESS_TOKEN")
+            .env_remove("AIRTABLE_API_KEY")
+            .env_remove("AIRTABLE_ACCESS_TOKEN");
+        command
+    };
+
+    // With two bases, only BASE picks one, and the dry run says so
+    let output = rsairtable(&["--default-dry-run"]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("(profile 'fake')"), "{}", stdout);
+    assert!(
+        stdout.contains(&format!("base:    {} (from BASE)", OTHER_ID)),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("table:   Matters"), "{}", stdout);
+
+    let output = rsairtable(&[]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "default command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let exported = std::fs::read_to_string(dir.join("matters.json")).unwrap();
+    assert!(exported.contains("M-other"), "{}", exported);
+    assert!(!exported.contains("M-firm"), "{}", exported);
+
+    // An API key from the environment bypasses the profile and its export
+    let output = rsairtable(&["--default-dry-run"])
+        .env("PERSONAL_ACCESS_TOKEN", "patENV")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("Default command:\n"), "{}", stdout);
+    assert!(stdout.contains("table:   Cases"), "{}", stdout);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,129 @@
+//! Enterprise audit log paging against the in-process fake Airtable server
+
+use rsairtable::testing::{FakeAirtable, FAKE_API_KEY, FAKE_USER_ID};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::process::{Command, Output};
+
+const ACCOUNT_ID: &str = "entFakeAccount001";
+
+fn event(index: usize) -> Value {
+    json!({
+        "id": format!("ent{:014}", index),
+        "timestamp": "2024-01-01T00:00:00.000Z",
+        "action": "createBase",
+        "actor": { "type": "user", "user": { "id": FAKE_USER_ID } },
+        "modelId": "appFakeBase000001",
+        "modelType": "base"
+    })
+}
+
+fn rsairtable(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rsairtable"))
+        .args(args)
+        .env("XDG_CONFIG_HOME", dir)
+        .env("XDG_CACHE_HOME", dir)
+        .env("RSAIRTABLE_PROFILE", "fake")
+        .env_remove("RSAIRTABLE_CONFIG")
+        .env_remove("ENTERPRISE_ACCOUNT")
+        .output()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_audit_log_pages_until_an_empty_page() {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_audit_events((0..5).map(event).collect());
+    let enterprise = fake.client().enterprise(ACCOUNT_ID);
+
+    let (events, next) = enterprise.audit_log().page_size(2).execute().await.unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(next.as_deref(), Some("2"));
+
+    let events = enterprise.audit_log().page_size(2).all().await.unwrap();
+    let ids: Vec<&str> = events.iter().map(|e| e.id.as_str()).collect();
+    assert_eq!(ids.len(), 5);
+    assert_eq!(ids[4], "ent00000000000004");
+    // Three full or partial pages and one empty page that ends the log
+    assert_eq!(fake.request_count(), 5);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_audit_log_cli_prints_events_with_offset() {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_audit_events((0..3).map(event).collect());
+
+    let dir = std::env::temp_dir().join(format!("rsairtable-enterprise-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("rsairtable")).unwrap();
+    let config = format!(
+        "[profiles.fake]\ntoken = \"{}\"\nendpoint_url = \"{}\"\n",
+        FAKE_API_KEY,
+        fake.url()
+    );
+    std::fs::write(dir.join("rsairtable/config.toml"), config).unwrap();
+
+    let output = rsairtable(
+        &dir,
+        &["enterprise", ACCOUNT_ID, "audit-log", "--page-size", "2"],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let page: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(page["events"].as_array().unwrap().len(), 2);
+    assert_eq!(page["offset"], "2");
+
+    let output = rsairtable(
+        &dir,
+        &[
+            "enterprise",
+            ACCOUNT_ID,
+            "audit-log",
+            "--page-size",
+            "2",
+            "--next",
+            "2",
+        ],
+    );
+    let page: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(page["events"][0]["id"], "ent00000000000002");
+
+    let output = rsairtable(
+        &dir,
+        &[
+            "enterprise",
+            ACCOUNT_ID,
+            "audit-log",
+            "--page-size",
+            "2",
+            "--all",
+            "--format",
+            "ndjson",
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 3, "{}", stdout);
+
+    let csv = dir.join("events.csv");
+    let output = rsairtable(
+        &dir,
+        &[
+            "enterprise",
+            ACCOUNT_ID,
+            "audit-log",
+            "--all",
+            "--format",
+            "csv",
+            "--output",
+            csv.to_str().unwrap(),
+        ],
+    );
+    assert!(output.status.success());
+    let csv = std::fs::read_to_string(csv).unwrap();
+    assert_eq!(csv.lines().count(), 4, "{}", csv);
+    assert!(csv.lines().next().unwrap().contains("action"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
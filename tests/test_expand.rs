@@ -0,0 +1,128 @@
+//! Linked record expansion tests against the in-process fake Airtable server
+
+use rsairtable::testing::FakeAirtable;
+use rsairtable::LinkExpander;
+use serde_json::json;
+
+const BASE_ID: &str = "appFakeBase000001";
+
+/// Courts ← Judges ← Cases, with each link field added to the later table
+async fn fake_with_links() -> FakeAirtable {
+    let fake = FakeAirtable::start().await.unwrap();
+    let courts_id = fake.add_table(BASE_ID, "Courts", &[("Name", "singleLineText")]);
+    let judges_id = fake.add_table(BASE_ID, "Judges", &[("Name", "singleLineText")]);
+    fake.add_table(BASE_ID, "Cases", &[("Case Number", "singleLineText")]);
+
+    let base = fake.client().base(BASE_ID);
+    for (table, field, linked) in [
+        ("Judges", "Court", &courts_id),
+        ("Cases", "Judge", &judges_id),
+    ] {
+        base.table(table)
+            .create_field(json!({
+                "name": field,
+                "type": "multipleRecordLinks",
+                "options": { "linkedTableId": linked }
+            }))
+            .await
+            .unwrap();
+    }
+
+    fake.insert(BASE_ID, "Courts", vec![json!({ "Name": "N.D. Cal" })]);
+    let court = fake.records(BASE_ID, "Courts")[0].id.clone();
+    fake.insert(
+        BASE_ID,
+        "Judges",
+        vec![
+            json!({ "Name": "Alsup", "Court": [court] }),
+            json!({ "Name": "Koh", "Court": [court] }),
+        ],
+    );
+    let judges: Vec<String> = fake
+        .records(BASE_ID, "Judges")
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+    fake.insert(
+        BASE_ID,
+        "Cases",
+        vec![
+            json!({ "Case Number": "C-1", "Judge": [judges[0], judges[1]] }),
+            json!({ "Case Number": "C-2", "Judge": [judges[0]] }),
+        ],
+    );
+    fake
+}
+
+#[tokio::test]
+async fn test_expand_links_to_depth() {
+    let fake = fake_with_links().await;
+    let base = fake.client().base(BASE_ID);
+    let table = base.table("Cases");
+    let case_id = fake.records(BASE_ID, "Cases")[0].id.clone();
+
+    let mut record = table.get(&case_id).await.unwrap();
+    record.expand_links(&base, &["Judge"], 1).await.unwrap();
+    let judges = record.fields["Judge"].as_array().unwrap();
+    assert_eq!(judges[0]["fields"]["Name"], json!("Alsup"));
+    assert_eq!(judges[1]["fields"]["Name"], json!("Koh"));
+    assert!(judges[0]["fields"]["Court"][0].is_string());
+
+    let mut record = table.get(&case_id).await.unwrap();
+    record.expand_links(&base, &["*"], 2).await.unwrap();
+    let court = &record.fields["Judge"][0]["fields"]["Court"][0];
+    assert_eq!(court["fields"]["Name"], json!("N.D. Cal"));
+
+    let mut record = table.get(&case_id).await.unwrap();
+    let error = record
+        .expand_links(&base, &["Case Number"], 1)
+        .await
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("not a linked record field"),
+        "{}",
+        error
+    );
+    let error = record
+        .expand_links(&base, &["*", "Judge"], 1)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("'*'"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_expander_caches_linked_records() {
+    let fake = fake_with_links().await;
+    let base = fake.client().base(BASE_ID);
+    let mut records = base.table("Cases").all().await.unwrap();
+
+    let requests = fake.request_count();
+    let mut expander = LinkExpander::new(&base).await.unwrap();
+    for record in &mut records {
+        expander
+            .expand(record, "Cases", &["Judge"], 2)
+            .await
+            .unwrap();
+    }
+    // Two judges and one court, each fetched once across both cases
+    assert_eq!(expander.fetched(), 3);
+    // The schema, then one request per linked table and level for the first case
+    assert_eq!(fake.request_count() - requests, 3);
+
+    let mut records = base.table("Cases").all().await.unwrap();
+    let requests = fake.request_count();
+    let mut expander = LinkExpander::new(&base).await.unwrap();
+    expander
+        .expand_all(&mut records, "Cases", &[], 2)
+        .await
+        .unwrap();
+    assert_eq!(fake.request_count() - requests, 3);
+    assert_eq!(
+        records[0].fields["Judge"][1]["fields"]["Name"],
+        json!("Koh")
+    );
+    assert_eq!(
+        records[1].fields["Judge"][0]["fields"]["Court"][0]["fields"]["Name"],
+        json!("N.D. Cal")
+    );
+}
@@ -0,0 +1,429 @@
+//! Offline integration tests against the in-process fake Airtable server
+//!
+//! These mirror the live record retrieval, creation, update and pagination
+//! tests but need neither network access nor an API token.
+
+use rsairtable::testing::{FakeAirtable, Fault, FAKE_API_KEY};
+use rsairtable::{Client, Config, Error, FieldDefinition, TableDefinition};
+use serde_json::json;
+
+const BASE_ID: &str = "appFakeBase000001";
+const TABLE: &str = "TestCaseLaw";
+
+async fn fake_with_cases(count: usize) -> FakeAirtable {
+    let fake = FakeAirtable::start()
+        .await
+        .expect("fake server should start");
+    fake.add_table(
+        BASE_ID,
+        TABLE,
+        &[
+            ("Name", "singleLineText"),
+            ("Year", "number"),
+            ("Court", "singleLineText"),
+        ],
+    );
+    let records = (0..count)
+        .map(|i| json!({ "Name": format!("Case {}", i), "Year": 1990 + (i % 30) }))
+        .collect();
+    fake.insert(BASE_ID, TABLE, records);
+    fake
+}
+
+fn api_error(result: Result<impl std::fmt::Debug, Error>) -> (u16, String) {
+    match result {
+        Err(Error::Api { status, message }) => (status, message),
+        other => panic!("expected API error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_fake_pagination_follows_offsets() {
+    let fake = fake_with_cases(250).await;
+    let table = fake.client().base(BASE_ID).table(TABLE);
+
+    let records = table.all().await.expect("listing should succeed");
+    assert_eq!(records.len(), 250);
+    assert_eq!(fake.request_count(), 3, "100 records per page");
+
+    let (page, offset) = table
+        .list()
+        .page_size(10)
+        .max_records(25)
+        .execute()
+        .await
+        .unwrap();
+    assert_eq!(page.len(), 10);
+    let (page, _) = table
+        .list()
+        .max_records(25)
+        .offset(offset)
+        .execute()
+        .await
+        .unwrap();
+    assert_eq!(page.len(), 15, "maxRecords caps the total across pages");
+
+    let mut iterator = table.iterate().page_size(40).build().await.unwrap();
+    let mut pages = 0;
+    while let Some(page) = iterator.next().await {
+        assert!(page.unwrap().len() <= 40);
+        pages += 1;
+    }
+    assert_eq!(pages, 7);
+}
+
+#[tokio::test]
+async fn test_fake_filter_and_field_selection() {
+    let fake = fake_with_cases(30).await;
+    fake.insert(BASE_ID, TABLE, vec![json!({ "Year": 2020 })]);
+    let table = fake.client().base(BASE_ID).table(TABLE);
+
+    let (records, _) = table
+        .list()
+        .filter_by_formula("NOT({Name} = '')")
+        .execute()
+        .await
+        .unwrap();
+    assert_eq!(records.len(), 30);
+
+    let (records, _) = table
+        .list()
+        .filter_by_formula("AND({Year} >= 2000, {Year} < 2010)")
+        .fields(&["Name"])
+        .execute()
+        .await
+        .unwrap();
+    assert_eq!(records.len(), 10);
+    assert!(records.iter().all(|r| !r.fields.contains_key("Year")));
+
+    let (status, message) = api_error(table.list().filter_by_formula("{Name").execute().await);
+    assert_eq!(status, 422);
+    assert!(message.contains("formula"));
+}
+
+#[tokio::test]
+async fn test_fake_sort_order() {
+    let fake = fake_with_cases(30).await;
+    fake.insert(BASE_ID, TABLE, vec![json!({ "Year": 2020 })]);
+    let table = fake.client().base(BASE_ID).table(TABLE);
+    let names = |records: &[rsairtable::Record]| -> Vec<String> {
+        records
+            .iter()
+            .map(|r| {
+                r.fields
+                    .get("Name")
+                    .and_then(|n| n.as_str())
+                    .unwrap_or("")
+                    .to_string()
+            })
+            .collect()
+    };
+
+    // Empty cells sort first, and later keys break ties
+    let records = table
+        .select()
+        .sort(&[("Year", "desc"), ("Name", "asc")])
+        .max_records(2)
+        .execute()
+        .await
+        .unwrap();
+    assert_eq!(names(&records), ["", "Case 29"]);
+
+    let (records, _) = table
+        .list()
+        .sort(vec!["Year".to_string()])
+        .max_records(2)
+        .execute()
+        .await
+        .unwrap();
+    assert_eq!(names(&records), ["Case 0", "Case 1"]);
+
+    // Text compares as text, so "Case 9" comes after "Case 29"
+    let (records, _) = table
+        .list()
+        .sort(vec!["Name desc".to_string()])
+        .max_records(1)
+        .execute()
+        .await
+        .unwrap();
+    assert_eq!(names(&records), ["Case 9"]);
+
+    // The iterator sends the sort with every page
+    let mut iterator = table
+        .iterate()
+        .sort(vec!["Name desc".to_string()])
+        .page_size(10)
+        .build()
+        .await
+        .unwrap();
+    let first = iterator.next().await.unwrap().unwrap();
+    let second = iterator.next().await.unwrap().unwrap();
+    assert_eq!(names(&first)[0], "Case 9");
+    assert_eq!(names(&second)[0], "Case 26");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fake_cli_sort_direction() {
+    let fake = fake_with_cases(30).await;
+    let dir = std::env::temp_dir().join(format!("rsairtable-fake-sort-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("rsairtable")).unwrap();
+    let config = format!(
+        "[profiles.fake]\ntoken = \"{}\"\nendpoint_url = \"{}\"\n",
+        FAKE_API_KEY,
+        fake.url()
+    );
+    std::fs::write(dir.join("rsairtable/config.toml"), config).unwrap();
+    let first_names = |args: &[&str]| -> Vec<String> {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rsairtable"))
+            .args([
+                "base", BASE_ID, "table", TABLE, "records", "-n", "2", "--format", "json",
+            ])
+            .args(args)
+            .env("XDG_CONFIG_HOME", &dir)
+            .env("XDG_CACHE_HOME", &dir)
+            .env("RSAIRTABLE_PROFILE", "fake")
+            .env_remove("RSAIRTABLE_CONFIG")
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let records: Vec<rsairtable::Record> = serde_json::from_slice(&output.stdout).unwrap();
+        records
+            .iter()
+            .map(|r| r.fields["Name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Each -D applies to the -S at the same position
+    assert_eq!(
+        first_names(&["-S", "Name", "-D", "desc"]),
+        ["Case 9", "Case 8"]
+    );
+    assert_eq!(
+        first_names(&["-S", "Year", "-S", "Name", "-D", "desc", "-D", "asc"]),
+        ["Case 29", "Case 28"]
+    );
+    assert_eq!(first_names(&["-S", "Name desc"]), ["Case 9", "Case 8"]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_fake_record_lifecycle() {
+    let fake = fake_with_cases(0).await;
+    let table = fake.client().base(BASE_ID).table(TABLE);
+
+    let created = table
+        .create(json!({ "Name": "Smith v. Jones", "Year": 1999 }))
+        .await
+        .unwrap();
+    assert!(created.id.starts_with("rec"));
+    assert_eq!(
+        table.get(&created.id).await.unwrap().fields["Name"],
+        "Smith v. Jones"
+    );
+
+    let updated = table
+        .update(&created.id, json!({ "Court": "Supreme Court" }))
+        .await
+        .unwrap();
+    assert_eq!(
+        updated.fields["Name"], "Smith v. Jones",
+        "PATCH merges fields"
+    );
+    assert_eq!(updated.fields["Court"], "Supreme Court");
+
+    let batch = table
+        .batch_create(
+            (0..10)
+                .map(|i| json!({ "Name": format!("Batch {}", i) }))
+                .collect(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(batch.len(), 10);
+
+    let renamed = table
+        .batch_update(vec![
+            json!({ "id": batch[0].id, "fields": { "Name": "Renamed" } }),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(renamed[0].fields["Name"], "Renamed");
+
+    table.delete(&created.id).await.unwrap();
+    let (status, _) = api_error(table.get(&created.id).await);
+    assert_eq!(status, 404);
+
+    let ids: Vec<String> = batch.iter().take(5).map(|r| r.id.clone()).collect();
+    table.batch_delete(&ids).await.unwrap();
+    assert_eq!(fake.records(BASE_ID, TABLE).len(), 5);
+}
+
+#[tokio::test]
+async fn test_fake_upsert_merges_on_fields() {
+    let fake = fake_with_cases(3).await;
+    let table = fake.client().base(BASE_ID).table(TABLE);
+
+    let records = table
+        .batch_upsert(
+            vec![
+                json!({ "fields": { "Name": "Case 1", "Court": "Appeals" } }),
+                json!({ "fields": { "Name": "Case 99", "Court": "District" } }),
+            ],
+            &["Name"],
+        )
+        .await
+        .unwrap();
+    assert_eq!(records.len(), 2);
+
+    let stored = fake.records(BASE_ID, TABLE);
+    assert_eq!(stored.len(), 4, "one record updated, one created");
+    assert_eq!(stored[1].fields["Court"], "Appeals");
+}
+
+#[tokio::test]
+async fn test_fake_rejects_invalid_writes() {
+    let fake = fake_with_cases(0).await;
+    let table = fake.client().base(BASE_ID).table(TABLE);
+
+    let (status, message) = api_error(table.create(json!({ "Nmae": "typo" })).await);
+    assert_eq!(status, 422);
+    assert!(message.contains("Unknown field name"));
+
+    // The client refuses oversized batches itself, so go around it
+    let records: Vec<_> = (0..11)
+        .map(|i| json!({ "fields": { "Name": format!("Case {}", i) } }))
+        .collect();
+    let response = reqwest::Client::new()
+        .post(format!("{}/{}/{}", fake.url(), BASE_ID, TABLE))
+        .bearer_auth(FAKE_API_KEY)
+        .json(&json!({ "records": records }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 422);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["type"], "INVALID_RECORDS");
+    assert!(fake.records(BASE_ID, TABLE).is_empty());
+
+    let (status, _) = api_error(fake.client().base(BASE_ID).table("Missing").all().await);
+    assert_eq!(status, 404);
+}
+
+#[tokio::test]
+async fn test_fake_fault_injection() {
+    let fake = fake_with_cases(5).await;
+    let client = Client::from_config(fake.config().with_max_retries(0)).unwrap();
+    let table = client.base(BASE_ID).table(TABLE);
+
+    fake.fail_next(Fault::RateLimit);
+    fake.fail_next(Fault::Status(503));
+    fake.fail_next(Fault::Disconnect);
+
+    assert_eq!(api_error(table.all().await).0, 429);
+    assert_eq!(api_error(table.all().await).0, 503);
+    assert!(matches!(table.all().await, Err(Error::Http(_))));
+    assert_eq!(table.all().await.unwrap().len(), 5, "faults are one-shot");
+}
+
+#[tokio::test]
+async fn test_fake_transient_failures_are_retried() {
+    let fake = fake_with_cases(5).await;
+    let table = fake.client().base(BASE_ID).table(TABLE);
+
+    fake.fail_next(Fault::Status(503));
+    fake.fail_next(Fault::Disconnect);
+    assert_eq!(table.all().await.unwrap().len(), 5);
+    assert_eq!(fake.request_count(), 3);
+
+    // Creates are only retried when rate limited, never after a server error
+    fake.fail_next(Fault::Status(500));
+    assert_eq!(
+        api_error(table.create(json!({ "Name": "Once" })).await).0,
+        500
+    );
+    fake.fail_next(Fault::RateLimit);
+    table.create(json!({ "Name": "Once" })).await.unwrap();
+    assert_eq!(fake.records(BASE_ID, TABLE).len(), 6);
+}
+
+#[tokio::test]
+async fn test_fake_bases_pagination_and_create_base() {
+    let fake = FakeAirtable::start().await.unwrap();
+    for i in 0..1001 {
+        fake.add_base(&format!("appFakeBase{:06}", i), &format!("Base {}", i));
+    }
+    let client = fake.client();
+
+    let bases = client.bases().await.unwrap();
+    assert_eq!(bases.len(), 1001);
+    assert_eq!(bases[1000].name, "Base 1000");
+    assert_eq!(fake.request_count(), 2, "1000 bases per page");
+
+    let created = client
+        .create_base(
+            "wspFakeWorkspace1",
+            "Matters",
+            vec![TableDefinition::new(
+                "Cases",
+                vec![
+                    FieldDefinition::new("Name", "singleLineText"),
+                    FieldDefinition::new("Hours", "number"),
+                ],
+            )],
+        )
+        .await
+        .unwrap();
+    assert!(created.id.starts_with("app"));
+    assert_eq!(created.tables[0].name, "Cases");
+    assert_eq!(
+        created.tables[0].primary_field_id,
+        created.tables[0].fields[0].id
+    );
+
+    let bases = client.bases().await.unwrap();
+    assert_eq!(bases.last().unwrap().id, created.id);
+    let schema = client.base(&created.id).schema().await.unwrap();
+    assert_eq!(schema.tables[0].fields[1].name, "Hours");
+
+    match client
+        .create_base("wspFakeWorkspace1", "Empty", Vec::new())
+        .await
+    {
+        Err(Error::Api { status, .. }) => assert_eq!(status, 400),
+        other => panic!("expected API error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_fake_auth_metadata_and_comments() {
+    let fake = fake_with_cases(1).await;
+
+    let intruder =
+        Client::from_config(Config::new("patWrong".to_string()).with_endpoint_url(fake.url()))
+            .unwrap();
+    assert_eq!(api_error(intruder.whoami().await).0, 401);
+
+    let client = fake.client();
+    assert_eq!(
+        client.whoami().await.unwrap().email,
+        "fake.user@example.com"
+    );
+    assert_eq!(client.bases().await.unwrap()[0].id, BASE_ID);
+
+    let table = client.base(BASE_ID).table(TABLE);
+    let schema = table.schema().await.unwrap();
+    assert_eq!(schema.fields.len(), 3);
+    assert_eq!(schema.primary_field_id, schema.fields[0].id);
+
+    let record_id = fake.records(BASE_ID, TABLE)[0].id.clone();
+    table.add_comment(&record_id, "First").await.unwrap();
+    table.add_comment(&record_id, "Second").await.unwrap();
+    let comments = table.comments(&record_id).await.unwrap();
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0].text, "Second", "newest comment first");
+}
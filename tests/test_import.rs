@@ -0,0 +1,124 @@
+//! CSV import tests against the in-process fake Airtable server
+
+use rsairtable::testing::FakeAirtable;
+use rsairtable::ImportOptions;
+use serde_json::json;
+use std::collections::HashMap;
+
+const BASE_ID: &str = "appFakeBase000001";
+
+async fn fake_with_cases() -> FakeAirtable {
+    let fake = FakeAirtable::start().await.unwrap();
+    let judges_id = fake.add_table(BASE_ID, "Judges", &[("Name", "singleLineText")]);
+    fake.add_table(
+        BASE_ID,
+        "Cases",
+        &[
+            ("Case Number", "singleLineText"),
+            ("Year", "number"),
+            ("Open", "checkbox"),
+            ("Filed", "date"),
+            ("Tags", "multipleSelects"),
+            ("Summary", "formula"),
+        ],
+    );
+    fake.client()
+        .base(BASE_ID)
+        .table("Cases")
+        .create_field(json!({
+            "name": "Judge",
+            "type": "multipleRecordLinks",
+            "options": { "linkedTableId": judges_id }
+        }))
+        .await
+        .unwrap();
+    fake.insert(
+        BASE_ID,
+        "Judges",
+        vec![json!({ "Name": "Alsup" }), json!({ "Name": "Koh" })],
+    );
+    fake
+}
+
+#[tokio::test]
+async fn test_import_coerces_values_and_reports_rejects() {
+    let fake = fake_with_cases().await;
+    let judges = fake.records(BASE_ID, "Judges");
+    let table = fake.client().base(BASE_ID).table("Cases");
+
+    let csv = "case number,Year,Open,Filed,Tags,Summary,Court,Presiding\n\
+               C-1,\"1,999\",yes,03/14/2024,\"civil; appeal\",x,N.D. Cal,Alsup\n\
+               C-2,2001,no,01/02/2023,,x,,Koh\n\
+               C-3,twenty,yes,,,,,\n\
+               C-4,2005,x,,,,,Breyer\n";
+    let options = ImportOptions::default()
+        .with_mapping(HashMap::from([(
+            "Presiding".to_string(),
+            "Judge".to_string(),
+        )]))
+        .with_date_format("%m/%d/%Y")
+        .with_list_delimiter(';');
+
+    let report = table.import_csv(csv, &options).await.unwrap();
+    assert_eq!(report.imported, 2);
+    assert_eq!(report.ignored_columns, vec!["Summary", "Court"]);
+    let rejected: Vec<(usize, &str)> = report
+        .rejected
+        .iter()
+        .map(|r| (r.line, r.reason.as_str()))
+        .collect();
+    assert_eq!(
+        rejected,
+        vec![
+            (4, "Year: 'twenty' is not a number"),
+            (5, "Judge: no record in Judges named 'Breyer'"),
+        ]
+    );
+
+    let records = fake.records(BASE_ID, "Cases");
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].fields["Year"], json!(1999));
+    assert_eq!(records[0].fields["Open"], json!(true));
+    assert_eq!(records[0].fields["Filed"], json!("2024-03-14"));
+    assert_eq!(records[0].fields["Tags"], json!(["civil", "appeal"]));
+    assert_eq!(records[0].fields["Judge"], json!([judges[0].id]));
+    assert_eq!(records[1].fields["Judge"], json!([judges[1].id]));
+    assert!(!records[1].fields.contains_key("Tags"));
+
+    let mut out = Vec::new();
+    report.write_rejected(&mut out, ',').unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("case number,Year,Open,Filed,Tags,Summary,Court,Presiding,error\n"));
+    assert!(out.contains("C-3,twenty,yes,,,,,,line 4: Year: 'twenty' is not a number\n"));
+}
+
+#[tokio::test]
+async fn test_import_upserts_on_key_fields() {
+    let fake = fake_with_cases().await;
+    let table = fake.client().base(BASE_ID).table("Cases");
+    let options = ImportOptions::default().with_upsert_keys(vec!["Case Number".to_string()]);
+
+    table
+        .import_csv("Case Number,Year\nC-1,1999\nC-2,2001\n", &options)
+        .await
+        .unwrap();
+    let report = table
+        .import_csv("Case Number,Year\nC-2,2002\nC-3,2003\n,2004\n", &options)
+        .await
+        .unwrap();
+    assert_eq!(report.imported, 2);
+    assert_eq!(report.rejected[0].reason, "missing upsert key Case Number");
+
+    let years: Vec<_> = fake
+        .records(BASE_ID, "Cases")
+        .iter()
+        .map(|r| r.fields["Year"].clone())
+        .collect();
+    assert_eq!(years, vec![json!(1999), json!(2002), json!(2003)]);
+
+    let missing_key = ImportOptions::default().with_upsert_keys(vec!["Open".to_string()]);
+    assert!(table
+        .import_csv("Case Number\nC-9\n", &missing_key)
+        .await
+        .is_err());
+}
@@ -0,0 +1,70 @@
+//! Client metrics tests against the in-process fake Airtable server
+
+use rsairtable::metrics::{RequestKey, RetryReason};
+use rsairtable::testing::{FakeAirtable, Fault};
+use rsairtable::InMemoryMetrics;
+use serde_json::json;
+use std::sync::Arc;
+
+const BASE_ID: &str = "appFakeBase000001";
+const TABLE: &str = "Cases";
+
+#[tokio::test]
+async fn test_metrics_count_requests_retries_and_records() {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_table(BASE_ID, TABLE, &[("Name", "singleLineText")]);
+    fake.insert(
+        BASE_ID,
+        TABLE,
+        (0..150)
+            .map(|i| json!({ "Name": format!("Case {}", i) }))
+            .collect(),
+    );
+
+    let metrics = Arc::new(InMemoryMetrics::new());
+    let client = fake.client().with_metrics(metrics.clone());
+    let table = client.base(BASE_ID).table(TABLE);
+
+    fake.fail_next(Fault::RateLimit);
+    assert_eq!(table.all().await.unwrap().len(), 150);
+    table
+        .batch_create(vec![json!({ "Name": "A" }), json!({ "Name": "B" })])
+        .await
+        .unwrap();
+    assert!(table.get("recMissing0000001").await.is_err());
+
+    let snapshot = metrics.snapshot();
+    let count = |method: &str, endpoint: &str, status: u16| {
+        snapshot.requests[&RequestKey {
+            method: method.to_string(),
+            endpoint: endpoint.to_string(),
+            status: Some(status),
+        }]
+    };
+    assert_eq!(count("GET", "/{baseId}/{table}", 429), 1);
+    assert_eq!(count("GET", "/{baseId}/{table}", 200), 2);
+    assert_eq!(count("POST", "/{baseId}/{table}", 200), 1);
+    assert_eq!(count("GET", "/{baseId}/{table}/{recordId}", 404), 1);
+    assert_eq!(snapshot.total_requests(), 5);
+    assert_eq!(snapshot.requests_per_base[BASE_ID], 5);
+
+    assert_eq!(snapshot.retries[&RetryReason::RateLimited], 1);
+    assert_eq!(snapshot.throttle_waits(), 1);
+    assert!(!snapshot.throttle_wait.is_zero());
+
+    let records = snapshot.records[&(BASE_ID.to_string(), TABLE.to_string())];
+    assert_eq!(
+        records.read, 150,
+        "pages are counted once, not again by all()"
+    );
+    assert_eq!(records.written, 2);
+
+    let prometheus = snapshot.to_prometheus();
+    assert!(prometheus.contains(
+        "airtable_request_duration_seconds_count{method=\"GET\",endpoint=\"/{baseId}/{table}\"} 3"
+    ));
+    assert!(prometheus.contains("airtable_retries_total{reason=\"rate_limited\"} 1"));
+
+    metrics.reset();
+    assert_eq!(metrics.snapshot().total_requests(), 0);
+}
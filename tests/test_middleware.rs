@@ -0,0 +1,177 @@
+//! Middleware chain tests against the in-process fake Airtable server
+
+use async_trait::async_trait;
+use rsairtable::testing::{FakeAirtable, Fault};
+use rsairtable::{
+    Error, HttpResponse, Middleware, RateLimitMiddleware, RequestContext, ResponseAction, Result,
+};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const BASE_ID: &str = "appFakeBase000001";
+const TABLE: &str = "Cases";
+
+async fn fake_with_cases() -> FakeAirtable {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_table(BASE_ID, TABLE, &[("Name", "singleLineText")]);
+    fake.insert(BASE_ID, TABLE, vec![json!({ "Name": "Smith v. Jones" })]);
+    fake
+}
+
+/// Logs hook calls and tags requests with a header
+#[derive(Debug)]
+struct Recorder {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Middleware for Recorder {
+    async fn before_request(&self, ctx: &mut RequestContext) -> Result<Option<HttpResponse>> {
+        ctx.request = ctx.request.clone().header("X-Middleware", self.name);
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("before {} attempt {}", self.name, ctx.attempt));
+        Ok(None)
+    }
+
+    async fn after_response(
+        &self,
+        _ctx: &RequestContext,
+        result: &mut Result<HttpResponse>,
+    ) -> ResponseAction {
+        let status = result.as_ref().map(|r| r.status).unwrap_or(0);
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("after {} {}", self.name, status));
+        ResponseAction::Continue
+    }
+}
+
+/// Answers whoami without touching the network
+#[derive(Debug)]
+struct CannedWhoami;
+
+#[async_trait]
+impl Middleware for CannedWhoami {
+    async fn before_request(&self, ctx: &mut RequestContext) -> Result<Option<HttpResponse>> {
+        if ctx.request.url.ends_with("/meta/whoami") {
+            let body = json!({ "id": "usrCanned00000001", "email": "canned@example.com" });
+            return Ok(Some(HttpResponse::json(200, &body)));
+        }
+        Ok(None)
+    }
+}
+
+/// Rewrites 404s into a friendlier error
+#[derive(Debug)]
+struct NotFoundRewriter;
+
+#[async_trait]
+impl Middleware for NotFoundRewriter {
+    async fn after_response(
+        &self,
+        ctx: &RequestContext,
+        result: &mut Result<HttpResponse>,
+    ) -> ResponseAction {
+        if matches!(result, Ok(response) if response.status == 404) {
+            *result = Err(Error::Other(format!("Nothing at {}", ctx.endpoint().0)));
+        }
+        ResponseAction::Continue
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_runs_in_order_around_retries() {
+    let fake = fake_with_cases().await;
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let client = fake
+        .client()
+        .with_middleware(Recorder {
+            name: "outer",
+            log: log.clone(),
+        })
+        .with_middleware(Recorder {
+            name: "inner",
+            log: log.clone(),
+        });
+
+    fake.fail_next(Fault::Status(503));
+    let records = client.base(BASE_ID).table(TABLE).all().await.unwrap();
+    assert_eq!(records.len(), 1);
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "before outer attempt 0",
+            "before inner attempt 0",
+            "after inner 503",
+            "after outer 503",
+            "before outer attempt 1",
+            "before inner attempt 1",
+            "after inner 200",
+            "after outer 200",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_middleware_short_circuits_and_rewrites_errors() {
+    let fake = fake_with_cases().await;
+    let client = fake
+        .client()
+        .with_middleware(NotFoundRewriter)
+        .with_middleware(CannedWhoami);
+
+    assert_eq!(client.whoami().await.unwrap().email, "canned@example.com");
+    assert_eq!(fake.request_count(), 0, "whoami never reached the server");
+
+    match client
+        .base(BASE_ID)
+        .table(TABLE)
+        .get("recMissing0000001")
+        .await
+    {
+        Err(Error::Other(message)) => {
+            assert_eq!(message, "Nothing at /{baseId}/{table}/{recordId}")
+        }
+        other => panic!("expected rewritten error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_without_middleware_disables_retries() {
+    let fake = fake_with_cases().await;
+    let table = fake
+        .client()
+        .without_middleware()
+        .base(BASE_ID)
+        .table(TABLE);
+
+    fake.fail_next(Fault::Status(503));
+    match table.all().await {
+        Err(Error::Api { status, .. }) => assert_eq!(status, 503),
+        other => panic!("expected 503, got {:?}", other),
+    }
+    assert_eq!(fake.request_count(), 1);
+}
+
+#[tokio::test]
+async fn test_rate_limit_middleware_spaces_requests_per_base() {
+    let fake = fake_with_cases().await;
+    let client = fake.client().with_middleware(RateLimitMiddleware::new(10));
+    let table = client.base(BASE_ID).table(TABLE);
+
+    let started = Instant::now();
+    for _ in 0..4 {
+        table.all().await.unwrap();
+    }
+    assert!(
+        started.elapsed() >= Duration::from_millis(300),
+        "4 requests at 10/s take at least 300ms, took {:?}",
+        started.elapsed()
+    );
+}
@@ -0,0 +1,237 @@
+//! Declarative schema migrations applied to the in-process fake Airtable server
+
+use rsairtable::testing::{FakeAirtable, Fault, FAKE_API_KEY};
+use rsairtable::SchemaSpec;
+use std::path::Path;
+use std::process::{Command, Output};
+
+const BASE_ID: &str = "appFakeBase000001";
+
+fn rsairtable(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rsairtable"))
+        .args(args)
+        .env("XDG_CONFIG_HOME", dir)
+        .env("XDG_CACHE_HOME", dir)
+        .env("RSAIRTABLE_PROFILE", "fake")
+        .env_remove("RSAIRTABLE_CONFIG")
+        .output()
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_migrate_creates_links_between_new_tables() {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_table(BASE_ID, "Cases", &[("Name", "singleLineText")]);
+    let spec: SchemaSpec = serde_norway::from_str(
+        "
+tables:
+  - name: Cases
+    fields:
+      - { name: Name, type: singleLineText }
+      - { name: Client, type: multipleRecordLinks, options: { linkedTableId: Clients } }
+  - name: Clients
+    description: People we act for
+    fields:
+      - { name: Name, type: singleLineText }
+      - { name: Matters, type: multipleRecordLinks, options: { linkedTableId: Cases } }
+",
+    )
+    .unwrap();
+
+    let base = fake.client().base(BASE_ID);
+    let report = base.apply_migration(&spec, false).await.unwrap();
+    assert_eq!(report.applied.len(), 3);
+    assert!(report.manual.is_empty());
+    assert!(report.failed.is_none());
+
+    let schema = base.schema().await.unwrap();
+    let clients = &schema.tables[1];
+    assert_eq!(clients.description.as_deref(), Some("People we act for"));
+    let names: Vec<&str> = clients.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["Name", "Matters"]);
+    assert_eq!(
+        clients.fields[1].linked_table_id(),
+        Some(schema.tables[0].id.as_str())
+    );
+    assert!(spec.plan(&schema).is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_migrate_cli_plans_refuses_and_applies() {
+    let fake = FakeAirtable::start().await.unwrap();
+    let table_id = fake.add_table(
+        BASE_ID,
+        "Cases",
+        &[
+            ("Name", "singleLineText"),
+            ("Hours", "number"),
+            ("Legacy", "singleLineText"),
+        ],
+    );
+
+    let dir = std::env::temp_dir().join(format!("rsairtable-migrate-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("rsairtable")).unwrap();
+    let config = format!(
+        "[profiles.fake]\ntoken = \"{}\"\nendpoint_url = \"{}\"\n",
+        FAKE_API_KEY,
+        fake.url()
+    );
+    std::fs::write(dir.join("rsairtable/config.toml"), config).unwrap();
+    let spec_path = dir.join("schema.toml");
+    let spec = format!(
+        r#"
+[[tables]]
+id = "{}"
+name = "Matters"
+
+[[tables.fields]]
+name = "Title"
+id = "{}"
+type = "singleLineText"
+description = "Short matter name"
+
+[[tables.fields]]
+name = "Hours"
+type = "number"
+
+[[tables.fields]]
+name = "Opened"
+type = "date"
+options = {{ dateFormat = {{ name = "iso" }} }}
+"#,
+        table_id,
+        fake.client().base(BASE_ID).schema().await.unwrap().tables[0].fields[0].id
+    );
+    std::fs::write(&spec_path, spec).unwrap();
+    let spec_arg = spec_path.to_str().unwrap();
+
+    let output = rsairtable(&dir, &["base", BASE_ID, "schema", "migrate", spec_arg]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        stdout,
+        "~ table Cases renamed to Matters\n\
+         ~ field Matters.Name renamed to Title, description updated\n\
+         + field Matters.Opened (date)\n\
+         - field Matters.Legacy [destructive, manual]\n\
+         4 change(s), 1 destructive, 1 to make in Airtable\n\
+         Run with --apply to make these changes\n"
+    );
+
+    // Removing Legacy loses data, so the plan is shown and nothing is applied
+    let output = rsairtable(
+        &dir,
+        &["base", BASE_ID, "schema", "migrate", spec_arg, "--apply"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stdout.starts_with("~ table Cases renamed to Matters\n"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Applied"), "{}", stdout);
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Refusing to apply a plan with 1 destructive change(s)"));
+    assert_eq!(
+        fake.client().base(BASE_ID).schema().await.unwrap().tables[0].name,
+        "Cases"
+    );
+
+    // Allowed, the other steps are made and Legacy is left for Airtable
+    let output = rsairtable(
+        &dir,
+        &[
+            "base",
+            BASE_ID,
+            "schema",
+            "migrate",
+            spec_arg,
+            "--apply",
+            "--allow-destructive",
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        stdout.contains("to make in Airtable\n\nApplied 3 change(s)\n"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("1 change(s) must be made in Airtable:\n  - field Matters.Legacy"));
+
+    let table = &fake.client().base(BASE_ID).schema().await.unwrap().tables[0];
+    let fields: Vec<&str> = table.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(table.name, "Matters");
+    assert_eq!(fields, ["Title", "Hours", "Legacy", "Opened"]);
+    assert_eq!(
+        table.fields[0].description.as_deref(),
+        Some("Short matter name")
+    );
+
+    let output = rsairtable(
+        &dir,
+        &[
+            "base", BASE_ID, "schema", "migrate", spec_arg, "--format", "json",
+        ],
+    );
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        plan,
+        serde_json::json!({ "steps": [{
+            "step": "remove_field", "table": "Matters", "field": "Legacy",
+            "destructive": true, "manual": true
+        }] })
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_migrate_reports_steps_made_before_a_failure() {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_table(BASE_ID, "Cases", &[("Name", "singleLineText")]);
+    let spec: SchemaSpec = serde_json::from_value(serde_json::json!({ "tables": [
+        { "name": "Cases", "description": "Open matters", "fields": [
+            { "name": "Name", "type": "singleLineText" },
+            { "name": "Hours", "type": "number" },
+            { "name": "Billed", "type": "checkbox" }
+        ] }
+    ] }))
+    .unwrap();
+
+    // Request 1 reads the schema and 2 updates the table, so creating Hours fails
+    let base = fake.client().base(BASE_ID);
+    fake.fail_request(fake.request_count() + 3, Fault::Status(422));
+    let report = base.apply_migration(&spec, false).await.unwrap();
+
+    assert_eq!(report.applied.len(), 1);
+    let failed = report.failed.as_ref().unwrap();
+    assert_eq!(failed.step.to_string(), "+ field Cases.Hours (number)");
+    assert!(failed.error.contains("422"), "{}", failed.error);
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(
+        report.to_string(),
+        format!(
+            "Applied 1 change(s)\n  ~ table Cases description updated\n\
+             Failed: + field Cases.Hours (number): {}\n\
+             1 change(s) not attempted:\n  + field Cases.Billed (checkbox)\n",
+            failed.error
+        )
+    );
+
+    let schema = base.schema().await.unwrap();
+    assert_eq!(
+        schema.tables[0].description.as_deref(),
+        Some("Open matters")
+    );
+    assert_eq!(schema.tables[0].fields.len(), 1);
+}
@@ -6,7 +6,7 @@ use std::process::Command;
 fn test_offset_parameter_support() {
     // First, get records without offset to obtain an offset token (use default limit to ensure offset exists)
     let output = Command::new("cargo")
-        .args(&[
+        .args([
             "run",
             "--",
             "base",
@@ -41,7 +41,7 @@ fn test_offset_parameter_support() {
 
     // Now use the offset token to get the next batch
     let output2 = Command::new("cargo")
-        .args(&[
+        .args([
             "run",
             "--",
             "base",
@@ -81,7 +81,7 @@ fn test_offset_parameter_support() {
 #[test]
 fn test_offset_parameter_in_help() {
     let output = Command::new("cargo")
-        .args(&[
+        .args([
             "run",
             "--",
             "base",
@@ -109,7 +109,7 @@ fn test_offset_parameter_in_help() {
 fn test_offset_with_filters() {
     // Test that offset can be combined with field filtering
     let output = Command::new("cargo")
-        .args(&[
+        .args([
             "run",
             "--",
             "base",
@@ -135,7 +135,7 @@ fn test_offset_with_filters() {
     if let Some(offset_token) = response[1].as_str() {
         // Test offset with field filtering
         let output2 = Command::new("cargo")
-            .args(&[
+            .args([
                 "run",
                 "--",
                 "base",
@@ -165,7 +165,7 @@ fn test_offset_with_filters() {
 fn test_all_flag_functionality() {
     // Get normal record count (should be limited to 100)
     let output1 = Command::new("cargo")
-        .args(&[
+        .args([
             "run",
             "--",
             "base",
@@ -189,7 +189,7 @@ fn test_all_flag_functionality() {
 
     // Get all records using --all flag
     let output2 = Command::new("cargo")
-        .args(&[
+        .args([
             "run",
             "--",
             "base",
@@ -238,7 +238,7 @@ fn test_all_flag_functionality() {
 fn test_all_flag_conflicts() {
     // Test conflict with --limit
     let output1 = Command::new("cargo")
-        .args(&[
+        .args([
             "run", "--", "base", "table", "Matters", "records", "--all", "--limit", "50",
         ])
         .output()
@@ -256,7 +256,7 @@ fn test_all_flag_conflicts() {
 
     // Test conflict with --offset
     let output2 = Command::new("cargo")
-        .args(&[
+        .args([
             "run",
             "--",
             "base",
@@ -285,7 +285,7 @@ fn test_all_flag_conflicts() {
 #[test]
 fn test_all_flag_with_filters() {
     let output = Command::new("cargo")
-        .args(&[
+        .args([
             "run",
             "--",
             "base",
@@ -313,7 +313,7 @@ fn test_all_flag_with_filters() {
     let records = response[0].as_array().unwrap();
 
     // Verify format and that we got some records
-    assert!(records.len() > 0, "Should return some filtered records");
+    assert!(!records.is_empty(), "Should return some filtered records");
     assert_eq!(
         response.as_array().unwrap().len(),
         2,
@@ -329,7 +329,7 @@ fn test_all_flag_with_filters() {
 #[test]
 fn test_verbose_mode_with_all() {
     let output = Command::new("cargo")
-        .args(&[
+        .args([
             "run",
             "--",
             "-v",
@@ -372,7 +372,7 @@ fn test_verbose_mode_with_all() {
 #[test]
 fn test_verbose_mode_normal() {
     let output = Command::new("cargo")
-        .args(&[
+        .args([
             "run",
             "--",
             "-v",
@@ -412,7 +412,7 @@ fn test_verbose_mode_normal() {
 #[test]
 fn test_non_verbose_mode_silent() {
     let output = Command::new("cargo")
-        .args(&[
+        .args([
             "run",
             "--",
             "base",
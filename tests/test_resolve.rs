@@ -0,0 +1,163 @@
+//! Alias and name resolution tests against the in-process fake Airtable server
+
+use rsairtable::testing::{FakeAirtable, Fault, FAKE_API_KEY};
+use rsairtable::{Client, ReqwestTransport, Target};
+use std::path::Path;
+use std::process::{Command, Output};
+
+const FIRM: &str = "appFirmBase000001";
+const ARCHIVE: &str = "appArchive0000001";
+
+#[tokio::test]
+async fn test_resolve_aliases_and_base_names() {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_base(FIRM, "Law Firm");
+    fake.add_base(ARCHIVE, "Archive");
+    fake.add_base("appArchive0000002", "archive");
+    let cases_id = fake.add_table(FIRM, "Cases", &[("Name", "singleLineText")]);
+
+    let mut config = fake
+        .config()
+        .with_default_base(FIRM)
+        .with_alias("cases", format!("{}/{}", FIRM, cases_id))
+        .with_alias("firm", "law firm")
+        .with_alias("old", format!("{}/Closed", ARCHIVE));
+    config
+        .table_aliases
+        .insert("c".to_string(), "Cases".to_string());
+    let client = Client::with_transport(config, ReqwestTransport::new());
+
+    let target = client.resolve("@cases").await.unwrap();
+    assert_eq!(
+        target,
+        Target {
+            base_id: FIRM.to_string(),
+            table: Some(cases_id.clone()),
+        }
+    );
+    assert_eq!(client.resolve("firm").await.unwrap().base_id, FIRM);
+    assert_eq!(
+        client.resolve("old").await.unwrap().to_string(),
+        format!("{}/Closed", ARCHIVE)
+    );
+    assert_eq!(
+        client.resolve("c").await.unwrap().table.as_deref(),
+        Some("Cases")
+    );
+    assert_eq!(client.resolve("Law Firm").await.unwrap().base_id, FIRM);
+    assert_eq!(client.resolve("LAW FIRM").await.unwrap().base_id, FIRM);
+
+    // An exact name wins over case-insensitive matches; otherwise they are ambiguous
+    assert_eq!(client.resolve("Archive").await.unwrap().base_id, ARCHIVE);
+    let ambiguous = client.resolve("ARCHIVE").await.unwrap_err();
+    assert!(ambiguous.to_string().contains("2 bases"), "{}", ambiguous);
+    assert!(client.resolve("Nowhere").await.is_err());
+
+    let base = client.base(FIRM);
+    assert_eq!(base.resolve_table("cases").await.unwrap(), cases_id);
+    assert_eq!(base.resolve_table(&cases_id).await.unwrap(), cases_id);
+    assert!(base.resolve_table("Clients").await.is_err());
+
+    // An alias wins over a table alias of the same name
+    let mut config = fake
+        .config()
+        .with_default_base(FIRM)
+        .with_alias("c", format!("{}/Closed", ARCHIVE));
+    config
+        .table_aliases
+        .insert("c".to_string(), "Cases".to_string());
+    let both = Client::with_transport(config, ReqwestTransport::new());
+    assert_eq!(both.resolve("c").await.unwrap().base_id, ARCHIVE);
+
+    // A default base given by name is resolved for table aliases
+    let mut config = fake.config().with_default_base("Law Firm");
+    config
+        .table_aliases
+        .insert("c".to_string(), "Cases".to_string());
+    let named = Client::with_transport(config, ReqwestTransport::new());
+    assert_eq!(named.resolve("c").await.unwrap().base_id, FIRM);
+
+    // Tables addressed by ID still find their schema
+    let schema = base.table(&cases_id).schema().await.unwrap();
+    assert_eq!(schema.name, "Cases");
+}
+
+fn rsairtable(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rsairtable"))
+        .args(args)
+        .env("XDG_CONFIG_HOME", dir)
+        .env("XDG_CACHE_HOME", dir)
+        .env("RSAIRTABLE_PROFILE", "fake")
+        .env_remove("RSAIRTABLE_CONFIG")
+        .output()
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cli_table_names_skip_needless_schema_requests() {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_base(FIRM, "Law Firm");
+    let cases_id = fake.add_table(FIRM, "Cases", &[("Name", "singleLineText")]);
+
+    let dir = std::env::temp_dir().join(format!("rsairtable-resolve-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("rsairtable")).unwrap();
+    let config = format!(
+        "[aliases]\ncases = \"{}/cases\"\n\n[profiles.fake]\ntoken = \"{}\"\nendpoint_url = \"{}\"\n",
+        FIRM,
+        FAKE_API_KEY,
+        fake.url()
+    );
+    std::fs::write(dir.join("rsairtable/config.toml"), config).unwrap();
+    let records = |table: &str| {
+        let output = rsairtable(&dir, &["base", FIRM, "table", table, "records"]);
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+
+    // Table IDs need no schema; names need one lookup
+    records(&cases_id);
+    assert_eq!(fake.request_count(), 1);
+    records("cases");
+    assert_eq!(fake.request_count(), 3);
+
+    // Errors other than a missing scope are reported instead of guessed around
+    fake.fail_next(Fault::Status(422));
+    let output = rsairtable(&dir, &["base", FIRM, "table", "cases", "records"]);
+    assert!(!output.status.success());
+    assert_eq!(fake.request_count(), 4);
+
+    // Once the schema is cached, names resolve without it
+    let output = rsairtable(&dir, &["base", FIRM, "schema"]);
+    assert!(output.status.success());
+    records("cases");
+    assert_eq!(fake.request_count(), 6);
+
+    // `--stats` takes its value with `=`, so the alias after it still expands
+    let output = rsairtable(&dir, &["--stats", "-p", "fake", "@cases", "records"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(fake.request_count(), 7);
+
+    // With an explicit key, an unreadable config file only costs its aliases
+    std::fs::write(dir.join("rsairtable/config.toml"), "not = toml = [").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_rsairtable"))
+        .args(["-k", FAKE_API_KEY, "base", FIRM, "table", &cases_id])
+        .args(["import", "/nonexistent/cases.csv"])
+        .env("XDG_CONFIG_HOME", &dir)
+        .env_remove("RSAIRTABLE_CONFIG")
+        .env_remove("RSAIRTABLE_PROFILE")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Warning: ignoring aliases"), "{}", stderr);
+    assert!(stderr.contains("No such file"), "{}", stderr);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,145 @@
+//! `base schema diff` run against the in-process fake Airtable server
+
+use rsairtable::testing::{FakeAirtable, FAKE_API_KEY};
+use serde_json::Value;
+use std::path::Path;
+use std::process::{Command, Output};
+
+const STAGING: &str = "appFakeStaging001";
+const PRODUCTION: &str = "appFakeProduct001";
+
+fn rsairtable(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rsairtable"))
+        .args(args)
+        .env("XDG_CONFIG_HOME", dir)
+        .env("XDG_CACHE_HOME", dir)
+        .env("RSAIRTABLE_PROFILE", "fake")
+        .env_remove("RSAIRTABLE_CONFIG")
+        .output()
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_schema_diff_between_bases_and_snapshot() {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_table(
+        STAGING,
+        "Matters",
+        &[
+            ("Name", "singleLineText"),
+            ("Hours", "currency"),
+            ("Owner", "singleLineText"),
+        ],
+    );
+    fake.add_table(STAGING, "Clients", &[("Name", "singleLineText")]);
+    fake.add_table(
+        PRODUCTION,
+        "Matters",
+        &[("Name", "singleLineText"), ("Hours", "number")],
+    );
+
+    let dir = std::env::temp_dir().join(format!("rsairtable-schema-diff-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("rsairtable")).unwrap();
+    let config = format!(
+        "[profiles.fake]\ntoken = \"{}\"\nendpoint_url = \"{}\"\n",
+        FAKE_API_KEY,
+        fake.url()
+    );
+    std::fs::write(dir.join("rsairtable/config.toml"), config).unwrap();
+
+    // Drift: text report and a failing exit status, after the requested stats
+    let output = rsairtable(
+        &dir,
+        &[
+            "--stats=prometheus",
+            "base",
+            STAGING,
+            "schema",
+            "diff",
+            "--against",
+            PRODUCTION,
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "stdout: {}", stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("airtable_requests_total"), "{}", stderr);
+    assert!(!stderr.contains("Error"), "{}", stderr);
+    assert!(stdout.contains("~ field Matters.Hours type number → currency"));
+    assert!(stdout.contains("+ field Matters.Owner (singleLineText)"));
+    assert!(stdout.contains("+ table Clients"));
+
+    let output = rsairtable(
+        &dir,
+        &[
+            "base",
+            STAGING,
+            "schema",
+            "diff",
+            "--against",
+            PRODUCTION,
+            "--format",
+            "json",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let diff: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let kinds: Vec<&str> = diff["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["change"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, ["field_type_changed", "field_added", "table_added"]);
+
+    // A snapshot written by `base schema` matches the base it came from
+    let snapshot = dir.join("staging.json");
+    let output = rsairtable(
+        &dir,
+        &["base", STAGING, "schema", "-o", snapshot.to_str().unwrap()],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let output = rsairtable(
+        &dir,
+        &[
+            "base",
+            STAGING,
+            "schema",
+            "diff",
+            "--against",
+            snapshot.to_str().unwrap(),
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "No schema differences\n"
+    );
+
+    let output = rsairtable(
+        &dir,
+        &[
+            "base",
+            STAGING,
+            "schema",
+            "diff",
+            "--against",
+            "missing.json",
+        ],
+    );
+    // Errors exit with status 2, as in diff(1)
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Cannot read schema file missing.json")
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,173 @@
+//! Interactive shell tests against the in-process fake Airtable server
+
+use async_trait::async_trait;
+use rsairtable::shell::{CommandRunner, Shell};
+use rsairtable::testing::{FakeAirtable, FAKE_API_KEY};
+use serde_json::json;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+const BASE_ID: &str = "appFakeBase000001";
+
+/// Keeps the command lines the shell hands to the CLI
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<Vec<String>>>>);
+
+impl Recorder {
+    fn last(&self) -> Vec<String> {
+        self.0.lock().unwrap().last().cloned().unwrap_or_default()
+    }
+}
+
+#[async_trait(?Send)]
+impl CommandRunner for Recorder {
+    async fn run(&self, args: Vec<String>) -> rsairtable::Result<()> {
+        self.0.lock().unwrap().push(args);
+        Ok(())
+    }
+}
+
+async fn fake_with_cases() -> (FakeAirtable, String) {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_base(BASE_ID, "Law Firm");
+    let cases_id = fake.add_table(
+        BASE_ID,
+        "Cases",
+        &[
+            ("Case Number", "singleLineText"),
+            ("Status", "singleLineText"),
+        ],
+    );
+    fake.insert(
+        BASE_ID,
+        "Cases",
+        vec![
+            json!({ "Case Number": "C-1", "Status": "Open" }),
+            json!({ "Case Number": "C-2", "Status": "Closed" }),
+        ],
+    );
+    (fake, cases_id)
+}
+
+#[tokio::test]
+async fn test_shell_commands_follow_current_table() {
+    let (fake, cases_id) = fake_with_cases().await;
+    let recorder = Recorder::default();
+    let mut shell = Shell::new(fake.client(), recorder.clone());
+    let words = |words: &[&str]| -> Vec<String> { words.iter().map(|w| w.to_string()).collect() };
+    let table = |words: &[&str]| -> Vec<String> {
+        ["base", BASE_ID, "table", &cases_id]
+            .iter()
+            .chain(words)
+            .map(|w| w.to_string())
+            .collect()
+    };
+
+    assert!(shell.execute("records").await.is_err());
+    shell.execute("use \"law firm\"").await.unwrap();
+    assert_eq!(shell.prompt(), "rsairtable:Law Firm> ");
+    shell.execute("use cases").await.unwrap();
+    assert_eq!(shell.prompt(), "rsairtable:Law Firm/Cases> ");
+
+    shell
+        .execute("records -w \"{Status} = 'Open'\" -S Status")
+        .await
+        .unwrap();
+    assert_eq!(
+        recorder.last(),
+        table(&[
+            "records",
+            "-w",
+            "{Status} = 'Open'",
+            "-S",
+            "Status",
+            "--format",
+            "table"
+        ])
+    );
+    shell.execute("records --format csv").await.unwrap();
+    assert_eq!(recorder.last(), table(&["records", "--format", "csv"]));
+
+    shell
+        .execute(r#"create {"Case Number": "C-3"}"#)
+        .await
+        .unwrap();
+    assert_eq!(
+        recorder.last(),
+        table(&["create", "-j", r#"{"Case Number": "C-3"}"#])
+    );
+    shell
+        .execute(r#"update recX {"Status": "New"}"#)
+        .await
+        .unwrap();
+    assert_eq!(
+        recorder.last(),
+        table(&["update", "recX", "-j", r#"{"Status": "New"}"#])
+    );
+    shell.execute("get recX -x '*'").await.unwrap();
+    assert_eq!(recorder.last(), table(&["get", "recX", "-x", "*"]));
+    shell.execute("help records").await.unwrap();
+    assert_eq!(
+        recorder.last(),
+        words(&["base", "BASE", "table", "TABLE", "records", "--help"])
+    );
+
+    assert_eq!(
+        shell.complete("records -F St", 13),
+        (11, vec!["Status".to_string()])
+    );
+    shell.execute("use ..").await.unwrap();
+    assert_eq!(shell.prompt(), "rsairtable:Law Firm> ");
+    shell.execute("schema").await.unwrap();
+    assert_eq!(
+        recorder.last(),
+        words(&["base", BASE_ID, "schema", "--format", "table"])
+    );
+    assert!(shell.execute("bogus").await.is_err());
+    assert_eq!(shell.execute("exit").await.unwrap(), None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shell_runs_cli_commands() {
+    let (fake, _) = fake_with_cases().await;
+    let dir = std::env::temp_dir().join(format!("rsairtable-shell-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("rsairtable")).unwrap();
+    let config = format!(
+        "[profiles.fake]\ntoken = \"{}\"\nendpoint_url = \"{}\"\n",
+        FAKE_API_KEY,
+        fake.url()
+    );
+    std::fs::write(dir.join("rsairtable/config.toml"), config).unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rsairtable"))
+        .args(["shell", "Law Firm"])
+        .env("XDG_CONFIG_HOME", &dir)
+        .env("XDG_CACHE_HOME", &dir)
+        .env("RSAIRTABLE_PROFILE", "fake")
+        .env_remove("RSAIRTABLE_CONFIG")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"use Cases\nrecords -w \"{Status} = 'Open'\"\nrecords -n\nexit\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(
+        stdout.contains("C-1") && !stdout.contains("C-2"),
+        "{}",
+        stdout
+    );
+    // Errors from the CLI parser are reported and the shell carries on
+    assert!(stderr.contains("--limit"), "{}", stderr);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -1,14 +1,14 @@
+//! Integration tests for Step 11: Advanced CLI Commands
+//!
+//! This module tests all advanced CLI functionality including:
+//! - Base collaborators and shares (Enterprise endpoints)
+//! - Enterprise audit log, users and claims
+//! - ORM code generation
+//! - Full CLI workflow integration
+
 use std::env;
 use std::process::Command;
 
-/// Integration tests for Step 11: Advanced CLI Commands
-///
-/// This module tests all advanced CLI functionality including:
-/// - Base collaborators and shares (API limitation demos)
-/// - Enterprise features (API limitation demos)
-/// - ORM code generation
-/// - Full CLI workflow integration
-
 fn get_test_env_vars() -> Option<(String, String)> {
     let token = env::var("PERSONAL_ACCESS_TOKEN").ok()?;
     let base_id = env::var("BASE").ok()?;
@@ -20,69 +20,59 @@ mod step11_tests {
     use super::*;
 
     #[test]
-    fn test_step11_base_collaborators_limitation_demo() {
+    fn test_step11_base_collaborators_enterprise() {
         let Some((_token, base_id)) = get_test_env_vars() else {
             println!("Skipping test: Environment variables not set");
             return;
         };
 
-        println!("🧪 Testing base collaborators command (API limitation demo)");
+        println!("🧪 Testing base collaborators command (Enterprise endpoint)");
 
         let output = Command::new("cargo")
-            .args(&["run", "--", "base", &base_id, "collaborators"])
+            .args(["run", "--", "base", &base_id, "collaborators"])
             .output()
             .expect("Failed to execute command");
 
-        // Should succeed but show API limitation message
-        assert!(
-            output.status.success(),
-            "Collaborators command should succeed with limitation message"
-        );
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        assert!(
-            stdout.contains("not available via the public Airtable API"),
-            "Should explain API limitation"
-        );
-        assert!(
-            stdout.contains("web interface"),
-            "Should suggest alternative"
-        );
-
-        println!("✅ Collaborators limitation properly documented");
+        if output.status.success() {
+            // Enterprise tokens get the real data as JSON
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let _: serde_json::Value =
+                serde_json::from_str(&stdout).expect("Collaborators output should be valid JSON");
+            println!("✅ Collaborators retrieved");
+        } else {
+            // Other tokens get a clear error instead of a silent stub
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            assert!(stderr.contains("Error:"), "Should report an error: {}", stderr);
+            println!("✅ Collaborators error properly reported");
+        }
     }
 
     #[test]
-    fn test_step11_base_shares_limitation_demo() {
+    fn test_step11_base_shares_enterprise() {
         let Some((_token, base_id)) = get_test_env_vars() else {
             println!("Skipping test: Environment variables not set");
             return;
         };
 
-        println!("🧪 Testing base shares command (API limitation demo)");
+        println!("🧪 Testing base shares command (Enterprise endpoint)");
 
         let output = Command::new("cargo")
-            .args(&["run", "--", "base", &base_id, "shares"])
+            .args(["run", "--", "base", &base_id, "shares"])
             .output()
             .expect("Failed to execute command");
 
-        // Should succeed but show API limitation message
-        assert!(
-            output.status.success(),
-            "Shares command should succeed with limitation message"
-        );
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        assert!(
-            stdout.contains("not available via the public Airtable API"),
-            "Should explain API limitation"
-        );
-        assert!(
-            stdout.contains("web interface"),
-            "Should suggest alternative"
-        );
-
-        println!("✅ Shares limitation properly documented");
+        if output.status.success() {
+            // Enterprise tokens get the real data as JSON
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let _: serde_json::Value =
+                serde_json::from_str(&stdout).expect("Shares output should be valid JSON");
+            println!("✅ Shares retrieved");
+        } else {
+            // Other tokens get a clear error instead of a silent stub
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            assert!(stderr.contains("Error:"), "Should report an error: {}", stderr);
+            println!("✅ Shares error properly reported");
+        }
     }
 
     #[test]
@@ -95,7 +85,7 @@ mod step11_tests {
         println!("🧪 Testing ORM code generation");
 
         let output = Command::new("cargo")
-            .args(&["run", "--", "base", &base_id, "orm"])
+            .args(["run", "--", "base", &base_id, "orm"])
             .output()
             .expect("Failed to execute command");
 
@@ -141,95 +131,454 @@ mod step11_tests {
     }
 
     #[test]
-    fn test_step11_enterprise_audit_log_limitation() {
+    fn test_step11_enterprise_audit_log_rejects_bad_timestamp() {
         let Some((_token, _base_id)) = get_test_env_vars() else {
             println!("Skipping test: Environment variables not set");
             return;
         };
 
-        println!("🧪 Testing enterprise audit-log command (API limitation demo)");
+        println!("🧪 Testing enterprise audit-log time-range validation");
 
         let output = Command::new("cargo")
-            .args(&["run", "--", "enterprise", "audit-log"])
+            .args([
+                "run",
+                "--",
+                "enterprise",
+                "entTestAccount00",
+                "audit-log",
+                "--start-time",
+                "yesterday",
+            ])
             .output()
             .expect("Failed to execute command");
 
         assert!(
-            output.status.success(),
-            "Enterprise audit-log should succeed with limitation message"
+            !output.status.success(),
+            "Audit log should reject a malformed start time"
         );
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("Invalid timestamp"),
+            "Should explain the timestamp problem: {}",
+            stderr
+        );
+
+        println!("✅ Enterprise audit-log validates time range");
+    }
+
+    #[test]
+    fn test_step11_log_format_validation() {
+        println!("🧪 Testing --log-format validation");
+
+        let output = Command::new("cargo")
+            .args(["run", "--", "--log-format", "xml", "whoami"])
+            .output()
+            .expect("Failed to execute command");
+
         assert!(
-            stdout.contains("not available via the public Airtable API"),
-            "Should explain API limitation"
+            !output.status.success(),
+            "Unknown log formats should be rejected"
         );
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
         assert!(
-            stdout.contains("Enterprise Admin Panel"),
-            "Should suggest enterprise alternative"
+            stderr.contains("text") && stderr.contains("json"),
+            "Should list the supported formats: {}",
+            stderr
         );
 
-        println!("✅ Enterprise audit-log limitation properly documented");
+        println!("✅ --log-format only accepts text or json");
     }
 
     #[test]
-    fn test_step11_enterprise_users_limitation() {
-        let Some((_token, _base_id)) = get_test_env_vars() else {
-            println!("Skipping test: Environment variables not set");
-            return;
-        };
-
-        println!("🧪 Testing enterprise users command (API limitation demo)");
+    fn test_step11_stats_format_validation() {
+        println!("🧪 Testing --stats validation");
 
         let output = Command::new("cargo")
-            .args(&["run", "--", "enterprise", "users"])
+            .args(["run", "--", "--stats=xml", "whoami"])
             .output()
             .expect("Failed to execute command");
 
         assert!(
-            output.status.success(),
-            "Enterprise users should succeed with limitation message"
+            !output.status.success(),
+            "Unknown stats formats should be rejected"
         );
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("text") && stderr.contains("prometheus"),
+            "Should list the supported formats: {}",
+            stderr
+        );
+
+        println!("✅ --stats only accepts text or prometheus");
+    }
+
+    #[test]
+    fn test_step11_sync_requires_db_and_table() {
+        println!("🧪 Testing sync argument validation");
+
+        let output = Command::new("cargo")
+            .args(["run", "--", "sync", "--table", "Cases"])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "sync without --db should fail");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("--db"), "Should mention --db: {}", stderr);
+
+        println!("✅ sync requires --db and --table");
+    }
+
+    #[test]
+    fn test_step11_backup_restore_require_directories() {
+        println!("🧪 Testing base backup/restore argument validation");
+
+        for (subcommand, flag) in [("backup", "--out"), ("restore", "--from")] {
+            let output = Command::new("cargo")
+                .args(["run", "--", "base", "appTestBase00000", subcommand])
+                .output()
+                .expect("Failed to execute command");
+
+            assert!(
+                !output.status.success(),
+                "{} without {} should fail",
+                subcommand,
+                flag
+            );
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            assert!(stderr.contains(flag), "Should mention {}: {}", flag, stderr);
+        }
+
+        println!("✅ base backup requires --out and restore requires --from");
+    }
+
+    #[test]
+    fn test_step11_import_validates_delimiters() {
+        println!("🧪 Testing table import argument validation");
+
+        let output = Command::new("cargo")
+            .args([
+                "run", "--", "base", "appTestBase00000", "table", "Cases", "import", "cases.csv",
+                "--delimiter", "ab",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "multi-character delimiter should fail");
+        let stderr = String::from_utf8_lossy(&output.stderr);
         assert!(
-            stdout.contains("not available via the public Airtable API"),
-            "Should explain API limitation"
+            stderr.contains("single character"),
+            "Should explain the delimiter error: {}",
+            stderr
         );
+
+        println!("✅ import rejects invalid delimiters");
+    }
+
+    #[test]
+    fn test_step11_records_format_validation() {
+        println!("🧪 Testing records --format validation");
+
+        let output = Command::new("cargo")
+            .args([
+                "run", "--", "base", "appTestBase00000", "table", "Cases", "records", "--format",
+                "xml",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Unknown format should fail");
+        let stderr = String::from_utf8_lossy(&output.stderr);
         assert!(
-            stdout.contains("Enterprise Admin Panel"),
-            "Should suggest enterprise alternative"
+            stderr.contains("ndjson") && stderr.contains("yaml"),
+            "Should list the supported formats: {}",
+            stderr
         );
 
-        println!("✅ Enterprise users limitation properly documented");
+        println!("✅ records rejects unknown output formats");
     }
 
     #[test]
-    fn test_step11_enterprise_claims_limitation() {
-        let Some((_token, _base_id)) = get_test_env_vars() else {
-            println!("Skipping test: Environment variables not set");
-            return;
+    fn test_step11_upsert_requires_key() {
+        println!("🧪 Testing table upsert --stdin argument validation");
+
+        let output = Command::new("cargo")
+            .args([
+                "run", "--", "base", "appTestBase00000", "table", "People", "upsert", "--stdin",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "upsert without --key should fail");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("--key"), "Should mention --key: {}", stderr);
+
+        let output = Command::new("cargo")
+            .args([
+                "run", "--", "base", "appTestBase00000", "table", "People", "create", "--stdin",
+                "--fields", "{}",
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "--stdin conflicts with --fields");
+
+        println!("✅ bulk write arguments are validated");
+    }
+
+    #[test]
+    fn test_step11_config_profiles() {
+        println!("🧪 Testing config add/list/validate");
+
+        let dir = std::env::temp_dir().join(format!("rsairtable-step11-config-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let run = |args: &[&str]| {
+            Command::new("cargo")
+                .args(["run", "--"])
+                .args(args)
+                .env("XDG_CONFIG_HOME", &dir)
+                .env_remove("RSAIRTABLE_CONFIG")
+                .env_remove("RSAIRTABLE_PROFILE")
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        let output = run(&["config", "add", "firm", "--token", "patSecret", "--base", "appFirm", "--default"]);
+        assert!(output.status.success(), "config add failed: {}", String::from_utf8_lossy(&output.stderr));
+        let output = run(&["config", "add", "staging", "--token-env", "RSAIRTABLE_STEP11_UNSET"]);
+        assert!(output.status.success());
+
+        let config = std::fs::read_to_string(dir.join("rsairtable/config.toml")).unwrap();
+        assert!(config.contains("default_profile = \"firm\""), "{}", config);
+
+        let output = run(&["config", "list"]);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("* firm"), "{}", stdout);
+        assert!(!stdout.contains("patSecret"), "list must not print tokens: {}", stdout);
+
+        let output = run(&["config", "validate"]);
+        assert!(!output.status.success(), "staging has no token");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("✅ firm"), "{}", stdout);
+        assert!(stdout.contains("RSAIRTABLE_STEP11_UNSET is not set"), "{}", stdout);
+
+        let output = run(&["--profile", "missing", "whoami"]);
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Profile 'missing' not found"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        println!("✅ profiles are managed through the config subcommand");
+    }
+
+    #[test]
+    fn test_step11_config_aliases() {
+        println!("🧪 Testing config alias");
+
+        let dir = std::env::temp_dir().join(format!("rsairtable-step11-alias-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let run = |args: &[&str]| {
+            Command::new("cargo")
+                .args(["run", "--"])
+                .args(args)
+                .env("XDG_CONFIG_HOME", &dir)
+                .env_remove("RSAIRTABLE_CONFIG")
+                .env_remove("RSAIRTABLE_PROFILE")
+                .output()
+                .expect("Failed to execute command")
         };
 
-        println!("🧪 Testing enterprise claims command (API limitation demo)");
+        let output = run(&["config", "alias", "cases", "appTestBase000000/tblTestTable00000"]);
+        assert!(output.status.success(), "config alias failed: {}", String::from_utf8_lossy(&output.stderr));
+        let output = run(&["config", "alias", "broken", "appTestBase000000/"]);
+        assert!(!output.status.success(), "an empty table part should be rejected");
+
+        let output = run(&["config", "list"]);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("@cases → appTestBase000000/tblTestTable00000"), "{}", stdout);
+        assert!(!stdout.contains("broken"), "{}", stdout);
+
+        let output = run(&["@cases", "records", "--format", "xml"]);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("invalid value 'xml'"), "@alias should expand to a table command: {}", stderr);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        println!("✅ aliases are stored and expanded");
+    }
+
+    #[test]
+    fn test_step11_default_dry_run() {
+        println!("🧪 Testing --default-dry-run");
+
+        let dir = std::env::temp_dir().join(format!("rsairtable-step11-default-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("rsairtable")).unwrap();
+        std::fs::write(
+            dir.join("rsairtable/config.toml"),
+            "[profiles.firm]\ntoken = \"patX\"\nbase = \"appFirm0000000000\"\n\n\
+             [profiles.firm.default_export]\ntable = \"Open Cases\"\nview = \"Active\"\noutput = \"open.csv\"\nformat = \"csv\"\n",
+        )
+        .unwrap();
+
+        // Run outside the repository so its .env cannot supply a key that bypasses the profile
+        let manifest = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+        let output = Command::new("cargo")
+            .args(["run", "--manifest-path", manifest, "--", "--default-dry-run"])
+            .current_dir(&dir)
+            .env("XDG_CONFIG_HOME", &dir)
+            .env_remove("RSAIRTABLE_CONFIG")
+            .env_remove("RSAIRTABLE_PROFILE")
+            .env_remove("PERSONAL_ACCESS_TOKEN")
+            .env_remove("AIRTABLE_API_KEY")
+            .env_remove("AIRTABLE_ACCESS_TOKEN")
+            .env_remove("BASE")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "dry run failed: {}", String::from_utf8_lossy(&output.stderr));
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("profile 'firm'"), "{}", stdout);
+        assert!(stdout.contains("appFirm0000000000"), "{}", stdout);
+        assert!(stdout.contains("table:   Open Cases"), "{}", stdout);
+        assert!(stdout.contains("open.csv (csv)"), "{}", stdout);
+        assert!(!dir.join("open.csv").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        println!("✅ --default-dry-run prints the configured export");
+    }
+
+    #[test]
+    fn test_step11_get_depth_requires_expand() {
+        println!("🧪 Testing table get argument validation");
 
         let output = Command::new("cargo")
-            .args(&["run", "--", "enterprise", "claims"])
+            .args([
+                "run", "--", "base", "appTestBase00000", "table", "Cases", "get", "recTest000000000",
+                "--depth", "2",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "--depth without --expand should fail");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("--expand"), "Should mention --expand: {}", stderr);
+
+        println!("✅ get --depth requires --expand");
+    }
+
+    #[test]
+    fn test_step11_tui_requires_table() {
+        println!("🧪 Testing tui base/table argument validation");
+
+        let output = Command::new("cargo")
+            .args(["run", "--", "tui", "base", "appXXXXXXXXXXXXXX"])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "tui without a table should fail");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("table"), "Should mention table: {}", stderr);
+    }
+
+    #[test]
+    fn test_step11_completions() {
+        println!("🧪 Testing completions and cached names");
+
+        let dir = std::env::temp_dir().join(format!("rsairtable-step11-completions-{}", std::process::id()));
+        let schemas = dir.join("cache/rsairtable/schemas/default");
+        std::fs::create_dir_all(&schemas).unwrap();
+        std::fs::write(
+            schemas.join("bases.json"),
+            r#"[{"id": "appTestBase000000", "name": "Law Firm", "permissionLevel": "create"}]"#,
+        )
+        .unwrap();
+        std::fs::write(
+            schemas.join("appTestBase000000.json"),
+            r#"{"tables": [{"id": "tblTestTable00000", "name": "Cases", "primaryFieldId": "fld1",
+                "fields": [{"id": "fld1", "name": "Case Number", "type": "singleLineText"}],
+                "views": [{"id": "viw1", "name": "Open cases", "type": "grid"}]}]}"#,
+        )
+        .unwrap();
+        let run = |args: &[&str]| {
+            Command::new("cargo")
+                .args(["run", "--"])
+                .args(args)
+                .env("XDG_CACHE_HOME", dir.join("cache"))
+                .env("XDG_CONFIG_HOME", &dir)
+                .env_remove("RSAIRTABLE_CONFIG")
+                .env_remove("RSAIRTABLE_PROFILE")
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        for shell in ["bash", "zsh", "fish"] {
+            let output = run(&["completions", shell]);
+            assert!(output.status.success(), "completions {} failed", shell);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            assert!(stdout.contains("rsairtable completions names"), "{} script lacks dynamic names", shell);
+        }
+        assert!(!run(&["completions", "tcsh"]).status.success());
+
+        let output = run(&["completions", "names", "fields", "--base", "Law Firm", "--table", "cases"]);
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "Case Number\n");
+        let output = run(&["completions", "names", "views", "--base", "appTestBase000000", "--table", "Cases"]);
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "Open cases\n");
+
+        // Another profile has its own cache
+        let output = run(&["--profile", "other", "completions", "names", "bases"]);
+        assert!(output.status.success());
+        assert!(output.stdout.is_empty(), "profile 'other' should have no cached bases");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        println!("✅ completion scripts and cached names work offline");
+    }
+
+    #[test]
+    fn test_step11_enterprise_users_requires_user() {
+        println!("🧪 Testing enterprise users argument validation");
+
+        let output = Command::new("cargo")
+            .args(["run", "--", "enterprise", "entTestAccount00", "users"])
             .output()
             .expect("Failed to execute command");
 
         assert!(
-            output.status.success(),
-            "Enterprise claims should succeed with limitation message"
+            !output.status.success(),
+            "Enterprise users should require at least one user"
         );
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("USER_ID_OR_EMAIL"),
+            "Should name the missing argument: {}",
+            stderr
+        );
+
+        println!("✅ Enterprise users validates arguments");
+    }
+
+    #[test]
+    fn test_step11_enterprise_claims_requires_user() {
+        println!("🧪 Testing enterprise claims argument validation");
+
+        let output = Command::new("cargo")
+            .args(["run", "--", "enterprise", "entTestAccount00", "claims"])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            !output.status.success(),
+            "Enterprise claims should require --managed or --unmanaged"
+        );
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
         assert!(
-            stdout.contains("not available via the public Airtable API"),
-            "Should explain API limitation"
+            stderr.contains("--managed") || stderr.contains("--unmanaged"),
+            "Should name the missing options: {}",
+            stderr
         );
 
-        println!("✅ Enterprise claims limitation properly documented");
+        println!("✅ Enterprise claims validates arguments");
     }
 
     #[test]
@@ -237,7 +586,7 @@ mod step11_tests {
         println!("🧪 Testing complete CLI help structure");
 
         let output = Command::new("cargo")
-            .args(&["run", "--", "--help"])
+            .args(["run", "--", "--help"])
             .output()
             .expect("Failed to execute command");
 
@@ -271,7 +620,7 @@ mod step11_tests {
     #[test]
     fn test_step11_base_help_structure() {
         let output = Command::new("cargo")
-            .args(&["run", "--", "base", "--help"])
+            .args(["run", "--", "base", "--help"])
             .output()
             .expect("Failed to execute command");
 
@@ -301,7 +650,7 @@ mod step11_tests {
     #[test]
     fn test_step11_table_help_structure() {
         let output = Command::new("cargo")
-            .args(&["run", "--", "base", "dummy", "table", "dummy", "--help"])
+            .args(["run", "--", "base", "dummy", "table", "dummy", "--help"])
             .output()
             .expect("Failed to execute command");
 
@@ -326,7 +675,7 @@ mod step11_tests {
     #[test]
     fn test_step11_enterprise_help_structure() {
         let output = Command::new("cargo")
-            .args(&["run", "--", "enterprise", "--help"])
+            .args(["run", "--", "enterprise", "--help"])
             .output()
             .expect("Failed to execute command");
 
@@ -364,7 +713,7 @@ mod step11_integration_tests {
 
         // Test whoami
         let whoami_output = Command::new("cargo")
-            .args(&["run", "--", "whoami"])
+            .args(["run", "--", "whoami"])
             .output()
             .expect("Failed to execute whoami");
 
@@ -372,7 +721,7 @@ mod step11_integration_tests {
 
         // Test base schema
         let schema_output = Command::new("cargo")
-            .args(&["run", "--", "base", &base_id, "schema"])
+            .args(["run", "--", "base", &base_id, "schema"])
             .output()
             .expect("Failed to execute base schema");
 
@@ -380,23 +729,12 @@ mod step11_integration_tests {
 
         // Test ORM generation
         let orm_output = Command::new("cargo")
-            .args(&["run", "--", "base", &base_id, "orm"])
+            .args(["run", "--", "base", &base_id, "orm"])
             .output()
             .expect("Failed to execute ORM generation");
 
         assert!(orm_output.status.success(), "ORM generation should work");
 
-        // Test limitation demos
-        let collab_output = Command::new("cargo")
-            .args(&["run", "--", "base", &base_id, "collaborators"])
-            .output()
-            .expect("Failed to execute collaborators");
-
-        assert!(
-            collab_output.status.success(),
-            "collaborators demo should work"
-        );
-
         println!("✅ Complete Step 11 workflow functions properly");
         println!("✅ All CLI commands implemented and tested");
         println!("✅ API limitations properly documented and handled");
@@ -0,0 +1,148 @@
+//! SQLite mirror tests against the in-process fake Airtable server (requires the `sync` feature)
+#![cfg(feature = "sync")]
+
+use chrono::Duration;
+use rsairtable::sync::SqliteMirror;
+use rsairtable::testing::FakeAirtable;
+use serde_json::json;
+
+const BASE_ID: &str = "appFakeBase000001";
+const TABLE: &str = "Cases";
+
+async fn fake_with_cases(count: usize) -> FakeAirtable {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_table(
+        BASE_ID,
+        TABLE,
+        &[
+            ("Case Name", "singleLineText"),
+            ("Year", "number"),
+            ("Open", "checkbox"),
+            ("Tags", "multipleSelects"),
+        ],
+    );
+    let records = (0..count)
+        .map(|i| {
+            json!({
+                "Case Name": format!("Case {}", i),
+                "Year": 1990 + (i % 30),
+                "Open": i % 2 == 0,
+                "Tags": ["civil"]
+            })
+        })
+        .collect();
+    fake.insert(BASE_ID, TABLE, records);
+    fake
+}
+
+fn count(mirror: &SqliteMirror, sql: &str) -> i64 {
+    mirror
+        .connection()
+        .query_row(sql, [], |row| row.get(0))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_initial_sync_mirrors_records_and_schema() {
+    let fake = fake_with_cases(150).await;
+    let table = fake.client().base(BASE_ID).table(TABLE);
+    let mut mirror = SqliteMirror::open_in_memory().unwrap();
+
+    let report = mirror.sync_table(&table).await.unwrap();
+    assert!(report.full);
+    assert_eq!((report.fetched, report.total), (150, 150));
+    assert_eq!(report.sql_table, "cases");
+
+    assert_eq!(
+        count(&mirror, "SELECT COUNT(*) FROM cases WHERE year >= 2000"),
+        100
+    );
+    assert_eq!(
+        count(&mirror, "SELECT COUNT(*) FROM cases WHERE open = 1"),
+        75
+    );
+    let (name, tags): (String, String) = mirror
+        .connection()
+        .query_row(
+            "SELECT case_name, tags FROM cases ORDER BY case_name LIMIT 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(name, "Case 0");
+    assert_eq!(tags, "[\"civil\"]");
+    assert_eq!(
+        count(
+            &mirror,
+            "SELECT COUNT(*) FROM cases WHERE json_extract(fields_json, '$.\"Case Name\"') = 'Case 7'"
+        ),
+        1
+    );
+
+    let columns = mirror.columns(BASE_ID, TABLE).unwrap();
+    assert_eq!(columns.len(), 4);
+    assert_eq!(columns[0].column, "case_name");
+    assert_eq!(
+        count(
+            &mirror,
+            "SELECT COUNT(*) FROM _airtable_fields WHERE field_type = 'checkbox'"
+        ),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_incremental_sync_fetches_changes_and_detects_deletions() {
+    let fake = fake_with_cases(20).await;
+    let table = fake.client().base(BASE_ID).table(TABLE);
+    let path = std::env::temp_dir().join(format!("rsairtable-sync-{}.sqlite", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut mirror = SqliteMirror::open(&path).unwrap();
+    mirror.sync_table(&table).await.unwrap();
+    drop(mirror);
+
+    let ids: Vec<String> = fake
+        .records(BASE_ID, TABLE)
+        .iter()
+        .map(|r| r.id.clone())
+        .collect();
+    table
+        .update(&ids[0], json!({ "Case Name": "Renamed" }))
+        .await
+        .unwrap();
+    table.delete(&ids[1]).await.unwrap();
+    table
+        .create(json!({ "Case Name": "New case" }))
+        .await
+        .unwrap();
+
+    // Reopening the file resumes incrementally
+    let mut mirror = SqliteMirror::open(&path)
+        .unwrap()
+        .with_overlap(Duration::zero());
+    let requests = fake.request_count();
+    let report = mirror.sync_table(&table).await.unwrap();
+    assert!(!report.full);
+    assert_eq!(report.fetched, 2, "only the updated and created records");
+    assert_eq!(report.deleted, 1);
+    assert_eq!(report.total, 20);
+    assert_eq!(
+        fake.request_count() - requests,
+        3,
+        "schema, changed records and the ID listing"
+    );
+
+    assert_eq!(
+        count(
+            &mirror,
+            "SELECT COUNT(*) FROM cases WHERE case_name IN ('Renamed', 'New case')"
+        ),
+        2
+    );
+    let full = mirror.resync_table(&table).await.unwrap();
+    assert!(full.full);
+    assert_eq!(full.total, 20);
+
+    let _ = std::fs::remove_file(&path);
+}
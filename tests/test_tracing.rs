@@ -0,0 +1,70 @@
+//! Tracing instrumentation tests (requires the `tracing` feature)
+#![cfg(feature = "tracing")]
+
+use rsairtable::testing::{FakeAirtable, FAKE_API_KEY};
+use serde_json::json;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+/// Writer collecting log output in memory
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_operations_emit_spans_without_token() {
+    let capture = Capture::default();
+    let writer = capture.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .json()
+        .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+        .with_max_level(tracing::Level::INFO)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_table("appFakeBase000001", "Cases", &[("Name", "singleLineText")]);
+    fake.insert(
+        "appFakeBase000001",
+        "Cases",
+        vec![json!({ "Name": "A" }), json!({ "Name": "B" })],
+    );
+    let table = fake.client().base("appFakeBase000001").table("Cases");
+    table.list().execute().await.unwrap();
+
+    let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+    let spans: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    let request = spans
+        .iter()
+        .find(|line| line["span"]["name"] == "airtable.request")
+        .expect("request span should be logged");
+    assert_eq!(request["span"]["method"], "GET");
+    assert_eq!(request["span"]["status"], 200);
+    assert_eq!(request["span"]["retries"], 0);
+    assert!(request["span"]["latency_ms"].is_u64());
+
+    let list = spans
+        .iter()
+        .find(|line| line["span"]["name"] == "table.list")
+        .expect("operation span should be logged");
+    assert_eq!(list["span"]["base_id"], "appFakeBase000001");
+    assert_eq!(list["span"]["table"], "Cases");
+    assert_eq!(list["span"]["record_count"], 2);
+
+    assert!(!output.contains(FAKE_API_KEY), "token must never be logged");
+}
@@ -0,0 +1,210 @@
+//! Offline tests for the pluggable HTTP transport
+//!
+//! These tests run the client against an in-memory transport that serves canned
+//! responses, so they need neither network access nor an API token.
+
+use async_trait::async_trait;
+use rsairtable::{
+    Client, Config, Error, HttpRequest, HttpResponse, Method, RequestBody, Result, Transport,
+};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Transport returning queued responses and recording every request
+#[derive(Debug, Clone, Default)]
+struct MockTransport {
+    responses: Arc<Mutex<VecDeque<HttpResponse>>>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl MockTransport {
+    fn respond(&self, response: HttpResponse) -> &Self {
+        self.responses.lock().unwrap().push_back(response);
+        self
+    }
+
+    fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Transport for MockTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        self.requests.lock().unwrap().push(request);
+        Ok(self
+            .responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("unexpected request: no response queued"))
+    }
+}
+
+fn client(transport: &MockTransport) -> Client {
+    // Retries are covered against the fake server; every queued response is used once here
+    let config = Config::new("patTestToken".to_string())
+        .with_endpoint_url("https://airtable.test/v0")
+        .with_max_retries(0);
+    Client::with_transport(config, transport.clone())
+}
+
+fn record(id: &str, name: &str) -> serde_json::Value {
+    json!({ "id": id, "createdTime": "2024-01-01T00:00:00.000Z", "fields": { "Name": name } })
+}
+
+#[tokio::test]
+async fn test_transport_all_follows_offsets() {
+    let transport = MockTransport::default();
+    transport
+        .respond(HttpResponse::json(
+            200,
+            &json!({ "records": [record("rec00000000000001", "A")], "offset": "itrNext" }),
+        ))
+        .respond(HttpResponse::json(
+            200,
+            &json!({ "records": [record("rec00000000000002", "B")] }),
+        ));
+
+    let records = client(&transport)
+        .base("appTestBase123")
+        .table("Cases")
+        .all()
+        .await
+        .expect("listing should succeed");
+
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].id, "rec00000000000002");
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, Method::Get);
+    assert!(requests[0]
+        .url
+        .starts_with("https://airtable.test/v0/appTestBase123/Cases/"));
+    assert!(requests[1].url.ends_with("?offset=itrNext"));
+}
+
+#[tokio::test]
+async fn test_transport_sends_auth_and_json_body() {
+    let transport = MockTransport::default();
+    transport.respond(HttpResponse::json(200, &record("rec00000000000001", "New")));
+
+    let created = client(&transport)
+        .base("appTestBase123")
+        .table("Cases")
+        .create(json!({ "Name": "New" }))
+        .await
+        .expect("create should succeed");
+    assert_eq!(created.id, "rec00000000000001");
+
+    let request = &transport.requests()[0];
+    assert_eq!(request.method, Method::Post);
+    assert_eq!(
+        request.header_value("authorization"),
+        Some("Bearer patTestToken")
+    );
+    assert_eq!(request.header_value("user-agent"), Some("rsairtable/0.1.0"));
+    assert!(request.timeout.is_some());
+    match &request.body {
+        RequestBody::Json(body) => assert_eq!(body["fields"]["Name"], "New"),
+        other => panic!("expected JSON body, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_transport_error_responses_become_api_errors() {
+    let transport = MockTransport::default();
+    transport
+        .respond(HttpResponse::json(
+            422,
+            &json!({ "error": { "type": "INVALID_REQUEST_UNKNOWN", "message": "Invalid request" } }),
+        ))
+        .respond(HttpResponse::new(503, "Service Unavailable"));
+
+    let table = client(&transport).base("appTestBase123").table("Cases");
+
+    match table.get("rec00000000000001").await {
+        Err(Error::Api { status, message }) => {
+            assert_eq!(status, 422);
+            assert_eq!(message, "Invalid request");
+        }
+        other => panic!("expected API error, got {:?}", other),
+    }
+
+    match table.get("rec00000000000001").await {
+        Err(Error::Api { status, message }) => {
+            assert_eq!(status, 503);
+            assert_eq!(message, "HTTP 503");
+        }
+        other => panic!("expected API error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_transport_base_collaborators() {
+    let transport = MockTransport::default();
+    transport.respond(HttpResponse::json(
+        200,
+        &json!({
+            "id": "appTestBase123",
+            "name": "Matters",
+            "permissionLevel": "owner",
+            "workspaceId": "wspTestWorkspace1",
+            "individualCollaborators": {
+                "baseCollaborators": [{
+                    "userId": "usrTestUser00001",
+                    "email": "lawyer@example.com",
+                    "permissionLevel": "edit",
+                    "createdTime": "2024-01-01T00:00:00.000Z"
+                }],
+                "workspaceCollaborators": []
+            },
+            "inviteLinks": {
+                "workspaceInviteLinks": [{
+                    "id": "invTestLink00001",
+                    "type": "multiUse",
+                    "permissionLevel": "read",
+                    "restrictedToEmailDomains": ["example.com"]
+                }]
+            },
+            "interfaces": {
+                "pbdTestInterface1": { "id": "pbdTestInterface1", "name": "Intake" }
+            }
+        }),
+    ));
+
+    let base = client(&transport).base("appTestBase123");
+    let collaborators = base.collaborators().await.unwrap();
+    assert_eq!(collaborators.permission_level, "owner");
+    let user = &collaborators.individual_collaborators.via_base[0];
+    assert_eq!(user.email, "lawyer@example.com");
+    assert!(collaborators.group_collaborators.via_base.is_empty());
+    assert!(collaborators.invite_links.via_base.is_empty());
+    let link = &collaborators.invite_links.via_workspace[0];
+    assert_eq!(link.restricted_to_email_domains, ["example.com"]);
+    assert_eq!(collaborators.interfaces["pbdTestInterface1"].name, "Intake");
+    assert!(transport.requests()[0].url.ends_with(
+        "/meta/bases/appTestBase123?include=collaborators&include=inviteLinks&include=interfaces"
+    ));
+
+    // Only permission failures mean the token is not an Enterprise admin token
+    transport.respond(HttpResponse::json(
+        403,
+        &json!({ "error": { "type": "INVALID_PERMISSIONS", "message": "Not allowed" } }),
+    ));
+    match base.shares().await {
+        Err(Error::Auth { message }) => assert!(message.contains("Enterprise plan token")),
+        other => panic!("expected auth error, got {:?}", other),
+    }
+
+    transport.respond(HttpResponse::json(
+        404,
+        &json!({ "error": { "type": "NOT_FOUND", "message": "Could not find base" } }),
+    ));
+    match base.collaborators().await {
+        Err(Error::Api { status, .. }) => assert_eq!(status, 404),
+        other => panic!("expected API error, got {:?}", other),
+    }
+}
@@ -0,0 +1,102 @@
+//! Table browser tests against the in-process fake Airtable server
+
+use ratatui::backend::TestBackend;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Terminal;
+use rsairtable::testing::FakeAirtable;
+use rsairtable::tui::Browser;
+use serde_json::json;
+
+const BASE_ID: &str = "appFakeBase000001";
+
+async fn press(browser: &mut Browser, keys: &[KeyCode]) {
+    for key in keys {
+        assert!(browser.handle_key(KeyEvent::from(*key)).await);
+    }
+}
+
+async fn type_text(browser: &mut Browser, text: &str) {
+    for c in text.chars() {
+        press(browser, &[KeyCode::Char(c)]).await;
+    }
+}
+
+#[tokio::test]
+async fn test_browser_pages_filters_sorts_and_edits() {
+    let fake = FakeAirtable::start().await.unwrap();
+    fake.add_table(
+        BASE_ID,
+        "Cases",
+        &[("Case Number", "singleLineText"), ("Hours", "number")],
+    );
+    fake.insert(
+        BASE_ID,
+        "Cases",
+        (0..150)
+            .map(|i| json!({ "Case Number": format!("C-{:03}", i), "Hours": i % 7 }))
+            .collect(),
+    );
+    let table = fake.client().base(BASE_ID).table("Cases");
+    let mut browser = Browser::new(table).await.unwrap();
+    assert_eq!(browser.records().len(), 100);
+
+    // Moving past the last loaded row fetches the next page
+    for _ in 0..6 {
+        press(&mut browser, &[KeyCode::PageDown]).await;
+    }
+    assert_eq!(browser.records().len(), 150);
+    assert_eq!(browser.selected().unwrap().fields["Case Number"], "C-120");
+
+    press(&mut browser, &[KeyCode::Char('/')]).await;
+    type_text(&mut browser, "{Hours} = 3").await;
+    press(&mut browser, &[KeyCode::Enter]).await;
+    assert_eq!(browser.records().len(), 21);
+
+    // An empty formula clears the filter
+    press(&mut browser, &[KeyCode::Char('/')]).await;
+    press(&mut browser, &[KeyCode::Backspace; 11]).await;
+    press(&mut browser, &[KeyCode::Enter]).await;
+    assert_eq!(browser.records().len(), 100);
+
+    // Sort the Hours column descending; sorting Case Number then replaces that
+    // sort, since the browser sorts by one column at a time
+    press(
+        &mut browser,
+        &[KeyCode::Right, KeyCode::Char('s'), KeyCode::Char('s')],
+    )
+    .await;
+    assert_eq!(browser.selected().unwrap().fields["Hours"], 6);
+    press(
+        &mut browser,
+        &[KeyCode::Left, KeyCode::Char('s'), KeyCode::Char('s')],
+    )
+    .await;
+    assert_eq!(browser.selected().unwrap().fields["Case Number"], "C-149");
+
+    // Editing asks for confirmation before updating
+    press(&mut browser, &[KeyCode::Right, KeyCode::Char('e')]).await;
+    press(&mut browser, &[KeyCode::Backspace]).await;
+    type_text(&mut browser, "12").await;
+    press(&mut browser, &[KeyCode::Enter]).await;
+    assert!(fake.records(BASE_ID, "Cases")[149].fields["Hours"] != 12);
+    press(&mut browser, &[KeyCode::Char('y')]).await;
+    assert_eq!(fake.records(BASE_ID, "Cases")[149].fields["Hours"], 12);
+    assert_eq!(browser.selected().unwrap().fields["Hours"], 12);
+    assert!(browser.status().starts_with("Updated Hours"));
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 12)).unwrap();
+    press(&mut browser, &[KeyCode::Enter]).await;
+    terminal.draw(|frame| browser.draw(frame)).unwrap();
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|cell| cell.symbol())
+        .collect();
+    assert!(screen.contains("Case Number"), "{}", screen);
+    assert!(screen.contains("Comments (0)"), "{}", screen);
+
+    press(&mut browser, &[KeyCode::Esc]).await;
+    assert!(!browser.handle_key(KeyEvent::from(KeyCode::Char('q'))).await);
+}